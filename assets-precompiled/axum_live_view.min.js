@@ -1 +1 @@
-(()=>{"use strict";var e,t={579:function(e,t,n){var o=this&&this.__importDefault||function(e){return e&&e.__esModule?e:{default:e}};Object.defineProperty(t,"__esModule",{value:!0}),t.LiveView=void 0;const i=o(n(34));function r(e){if(null===document.getElementById("live-view-container"))return;var t;t=-1===location.protocol.indexOf("https")?"ws":"wss";const n=new WebSocket(`${t}://${window.location.host}${window.location.pathname}`);var o={};n.addEventListener("open",(()=>{!function(e,t){setInterval((()=>{t.debug&&console.time(a),u(e,{t:"h"})}),3e4)}(n,e)})),n.addEventListener("message",(t=>{!function(e,t,n,o){const i=JSON.parse(t.data);if("i"===i.t)n.viewState=i.d,p(e,n,o),function(e,t){const n=Object.values(l).map((e=>`[${e}]`)).join(", ");document.querySelectorAll(n).forEach((n=>{s(e,n,t)}))}(e,o);else if("r"===i.t){if(!n.viewState)return;if(!i.d)return;b(n.viewState,i.d),p(e,n,o)}else if("j"===i.t)for(const e of i.d)h(e);else"h"===i.t&&o.debug&&console.timeEnd(a)}(n,t,o,e)})),n.addEventListener("close",(()=>{!function(e){setTimeout((()=>{r(e)}),1e3)}(e)}))}t.LiveView=class{constructor(){this.options={debug:!1},r(this.options)}enableDebug(){this.options.debug=!0}disableDebug(){this.options.debug=!1}};const a="ping";function u(e,t,n){e.send(JSON.stringify(t))}const l={click:"axm-click",input:"axm-input",change:"axm-change",submit:"axm-submit",focus:"axm-focus",blur:"axm-blur",keydown:"axm-keydown",keyup:"axm-keyup",mouseenter:"axm-mouseenter",mouseover:"axm-mouseover",mouseleave:"axm-mouseleave",mouseout:"axm-mouseout",mousemove:"axm-mousemove"},c={keydown:"axm-window-keydown",keyup:"axm-window-keyup",focus:"axm-window-focus",blur:"axm-window-blur",scroll:"axm-scroll"};function s(e,t,n){t.hasAttribute(l.click)&&d(e,0,t,t,"click",l.click,(e=>({t:"click",m:e}))),(t instanceof HTMLInputElement||t instanceof HTMLTextAreaElement||t instanceof HTMLSelectElement)&&(t.hasAttribute(l.input)&&d(e,0,t,t,"input",l.input,(e=>({t:"input",m:e,d:{v:m(t)}}))),t.hasAttribute(l.change)&&d(e,0,t,t,"change",l.change,(e=>({t:"input",m:e,d:{v:m(t)}}))),t.hasAttribute(l.focus)&&d(e,0,t,t,"focus",l.focus,(e=>({t:"input",m:e,d:{v:m(t)}}))),t.hasAttribute(l.blur)&&d(e,0,t,t,"blur",l.blur,(e=>({t:"input",m:e,d:{v:m(t)}})))),t instanceof HTMLFormElement&&(t.hasAttribute(l.change)&&d(e,0,t,t,"change",l.change,(e=>{const n=new FormData(t);return{t:"form",m:e,d:{q:new URLSearchParams(n).toString()}}})),t.hasAttribute(l.submit)&&d(e,0,t,t,"submit",l.submit,(e=>{const n=new FormData(t);return{t:"form",m:e,d:{q:new URLSearchParams(n).toString()}}}))),[["mouseenter",l.mouseenter],["mouseover",l.mouseover],["mouseleave",l.mouseleave],["mouseout",l.mouseout],["mousemove",l.mousemove]].forEach((([n,o])=>{n&&o&&t.hasAttribute(o)&&d(e,0,t,t,n,o,((e,t)=>{if(t instanceof MouseEvent)return{t:"mouse",m:e,d:{cx:t.clientX,cy:t.clientY,px:t.pageX,py:t.pageY,ox:t.offsetX,oy:t.offsetY,mx:t.movementX,my:t.movementY,sx:t.screenX,sy:t.screenY}}}))})),[["keydown",l.keydown],["keyup",l.keyup]].forEach((([n,o])=>{n&&o&&t.hasAttribute(o)&&d(e,0,t,t,n,o,((e,n)=>{var o;if(n instanceof KeyboardEvent){if(t.hasAttribute("axm-key")&&(null===(o=null==t?void 0:t.getAttribute("axm-key"))||void 0===o?void 0:o.toLowerCase())!==n.key.toLowerCase())return;return{t:"key",m:e,d:{k:n.key,kc:n.code,a:n.altKey,c:n.ctrlKey,s:n.shiftKey,me:n.metaKey}}}}))}))}function d(e,t,n,o,i,r,a){var l=function(e,t){var n=v(e,"axm-debounce");return n?function(e,t){var n;return(...o)=>{n&&clearTimeout(n),n=setTimeout((()=>{e(...o)}),t)}}(t,n):(n=v(e,"axm-throttle"))?function(e,t){var n;return(...o)=>{n||(e(...o),n=setTimeout((()=>{n=null}),t))}}(t,n):t}(n,(t=>{t instanceof KeyboardEvent||t.preventDefault();const o=function(e,t){const n=e.getAttribute(t);if(n)try{return JSON.parse(n)}catch(e){return n}}(n,r);if(!o)return;const i=a(o,t);i&&u(e,i)}));document===o&&f.push({event:i,callback:l}),o.addEventListener(i,l)}var f=[];function m(e){if(e instanceof HTMLTextAreaElement)return e.value;if(e instanceof HTMLInputElement)return"radio"===e.getAttribute("type")||"checkbox"===e.getAttribute("type")?e.checked:e.value;if(e instanceof HTMLSelectElement)return e.hasAttribute("multiple")?Array.from(e.selectedOptions).map((e=>e.value)):e.value;throw"Input has no input value"}function v(e,t){const n=e.getAttribute(t);if(n){const e=parseInt(n,10);if(e)return e}return null}function p(e,t,n){if(!t.viewState)return;const o=function e(t){var n="";return t.f.forEach(((o,i)=>{if(n=n.concat(o),void 0===t.d)return;const r=t.d[i];if(null!=r)if("string"==typeof r)n=n.concat(r);else if("b"in r){const t=r.f;Object.values(r.b).forEach((o=>{const i={f:t,d:o};n=n.concat(e(i))}))}else n=n.concat(e(r))})),n}(t.viewState),r=document.querySelector("#live-view-container");r&&function(e,t,n){for(var o=0;o<f.length;o++){let e=f[o];e&&(document.removeEventListener(e.event,e.callback),f.splice(o,1))}(0,i.default)(t,n,{onNodeAdded:t=>(t instanceof Element&&s(e,t),t),onBeforeElUpdated:(e,t)=>{var n;return e instanceof HTMLInputElement&&t instanceof HTMLInputElement&&("radio"===t.getAttribute("type")||"checkbox"===t.getAttribute("type")?t.checked=e.checked:t.value=e.value),e instanceof HTMLTextAreaElement&&t instanceof HTMLTextAreaElement&&(t.value=e.value),e instanceof HTMLOptionElement&&t instanceof HTMLOptionElement&&(null===(n=t.closest("select"))||void 0===n?void 0:n.hasAttribute("multiple"))&&(t.selected=e.selected),e instanceof HTMLSelectElement&&t instanceof HTMLSelectElement&&!t.hasAttribute("multiple")&&(t.value=e.value),!0}});const r=Object.values(c).map((e=>`[${e}]`)).join(", ");document.querySelectorAll(r).forEach((t=>{!function(e,t,n){[["keydown",c.keydown],["keyup",c.keyup]].forEach((([n,o])=>{n&&o&&t.hasAttribute(o)&&d(e,0,t,document,n,o,((e,n)=>{var o;if(n instanceof KeyboardEvent){if(t.hasAttribute("axm-key")&&(null===(o=null==t?void 0:t.getAttribute("axm-key"))||void 0===o?void 0:o.toLowerCase())!==n.key.toLowerCase())return;return{t:"key",m:e,d:{k:n.key,kc:n.code,a:n.altKey,c:n.ctrlKey,s:n.shiftKey,me:n.metaKey}}}}))})),t.hasAttribute(c.focus)&&d(e,0,t,document,"focus",c.focus,(e=>({t:"window_focus",m:e}))),t.hasAttribute(c.blur)&&d(e,0,t,document,"blur",c.blur,(e=>({t:"window_blur",m:e}))),t.hasAttribute(c.scroll)&&d(e,0,t,document,"scroll",c.scroll,(e=>({t:"scroll",m:e,d:{sx:window.scrollX,sy:window.scrollY}})))}(e,t)}))}(e,r,o)}function b(e,t){function n(e,t){for(const[n,i]of Object.entries(t))if("string"==typeof i)e[n]=i;else if(null===i)delete e[n];else if("object"==typeof i){const t=e[n];if(void 0===t)continue;"d"in i?"string"==typeof t?e[n]=i:"d"in t?b(t,i):"b"in t?console.error("not implemented: b in current"):e[n]=i:"b"in i?"string"==typeof t?e[n]=i:"b"in t?o(t,i):e[n]={f:t.f,b:i.b}:"f"in i?"string"==typeof t?e[n]=i:"d"in t?b(t,i):"b"in t?console.error("not implemented: b in current, with f"):e[n]=i:console.error("unexpected diff value",i)}}function o(e,t){if(t.f&&(e.f=t.f),t.b)for(const[o,i]of Object.entries(t.b))if(null===i)delete e.b[o];else{const t=e.b[o];void 0===t||"string"==typeof t?e.b[o]=i:n(t,i)}}t.f&&(e.f=t.f),t.d&&null!==t.d&&n(e.d||{},t.d)}function h(e){const t=()=>{if("navigate_to"===e.kind.t){const t=e.kind.uri;t.startsWith("http")?window.location.href=t:window.location.pathname=t}else if("add_class"===e.kind.t){const{selector:t,klass:n}=e.kind;document.querySelectorAll(t).forEach((e=>{e.classList.add(n)}))}else if("remove_class"===e.kind.t){const{selector:t,klass:n}=e.kind;document.querySelectorAll(t).forEach((e=>{e.classList.remove(n)}))}else if("toggle_class"===e.kind.t){const{selector:t,klass:n}=e.kind;document.querySelectorAll(t).forEach((e=>{e.classList.toggle(n)}))}else if("clear_value"===e.kind.t){const{selector:t}=e.kind;document.querySelectorAll(t).forEach((e=>{(e instanceof HTMLInputElement||e instanceof HTMLSelectElement||e instanceof HTMLTextAreaElement)&&(e.value="")}))}else"set_title"===e.kind.t?document.title=e.kind.title:"history_push_state"===e.kind.t?window.history.pushState({},"",e.kind.uri):e.kind};e.delay_ms?setTimeout(t,e.delay_ms):t()}},34:(e,t,n)=>{n.r(t),n.d(t,{default:()=>b});var o,i="undefined"==typeof document?void 0:document,r=!!i&&"content"in i.createElement("template"),a=!!i&&i.createRange&&"createContextualFragment"in i.createRange();function u(e,t){var n,o,i=e.nodeName,r=t.nodeName;return i===r||(n=i.charCodeAt(0),o=r.charCodeAt(0),n<=90&&o>=97?i===r.toUpperCase():o<=90&&n>=97&&r===i.toUpperCase())}function l(e,t,n){e[n]!==t[n]&&(e[n]=t[n],e[n]?e.setAttribute(n,""):e.removeAttribute(n))}var c,s={OPTION:function(e,t){var n=e.parentNode;if(n){var o=n.nodeName.toUpperCase();"OPTGROUP"===o&&(o=(n=n.parentNode)&&n.nodeName.toUpperCase()),"SELECT"!==o||n.hasAttribute("multiple")||(e.hasAttribute("selected")&&!t.selected&&(e.setAttribute("selected","selected"),e.removeAttribute("selected")),n.selectedIndex=-1)}l(e,t,"selected")},INPUT:function(e,t){l(e,t,"checked"),l(e,t,"disabled"),e.value!==t.value&&(e.value=t.value),t.hasAttribute("value")||e.removeAttribute("value")},TEXTAREA:function(e,t){var n=t.value;e.value!==n&&(e.value=n);var o=e.firstChild;if(o){var i=o.nodeValue;if(i==n||!n&&i==e.placeholder)return;o.nodeValue=n}},SELECT:function(e,t){if(!t.hasAttribute("multiple")){for(var n,o,i=-1,r=0,a=e.firstChild;a;)if("OPTGROUP"===(o=a.nodeName&&a.nodeName.toUpperCase()))a=(n=a).firstChild;else{if("OPTION"===o){if(a.hasAttribute("selected")){i=r;break}r++}!(a=a.nextSibling)&&n&&(a=n.nextSibling,n=null)}e.selectedIndex=i}}},d=1,f=3,m=8;function v(){}function p(e){if(e)return e.getAttribute&&e.getAttribute("id")||e.id}const b=(c=function(e,t){var n,o,i,r,a=t.attributes;if(11!==t.nodeType&&11!==e.nodeType){for(var u=a.length-1;u>=0;u--)o=(n=a[u]).name,i=n.namespaceURI,r=n.value,i?(o=n.localName||o,e.getAttributeNS(i,o)!==r&&("xmlns"===n.prefix&&(o=n.name),e.setAttributeNS(i,o,r))):e.getAttribute(o)!==r&&e.setAttribute(o,r);for(var l=e.attributes,c=l.length-1;c>=0;c--)o=(n=l[c]).name,(i=n.namespaceURI)?(o=n.localName||o,t.hasAttributeNS(i,o)||e.removeAttributeNS(i,o)):t.hasAttribute(o)||e.removeAttribute(o)}},function(e,t,n){if(n||(n={}),"string"==typeof t)if("#document"===e.nodeName||"HTML"===e.nodeName||"BODY"===e.nodeName){var l=t;(t=i.createElement("html")).innerHTML=l}else b=(b=t).trim(),t=r?function(e){var t=i.createElement("template");return t.innerHTML=e,t.content.childNodes[0]}(b):a?function(e){return o||(o=i.createRange()).selectNode(i.body),o.createContextualFragment(e).childNodes[0]}(b):function(e){var t=i.createElement("body");return t.innerHTML=e,t.childNodes[0]}(b);else 11===t.nodeType&&(t=t.firstElementChild);var b,h=n.getNodeKey||p,y=n.onBeforeNodeAdded||v,g=n.onNodeAdded||v,A=n.onBeforeElUpdated||v,w=n.onElUpdated||v,k=n.onBeforeNodeDiscarded||v,E=n.onNodeDiscarded||v,x=n.onBeforeElChildrenUpdated||v,T=n.skipFromChildren||v,S=n.addChild||function(e,t){return e.appendChild(t)},N=!0===n.childrenOnly,L=Object.create(null),C=[];function O(e){C.push(e)}function M(e,t){if(e.nodeType===d)for(var n=e.firstChild;n;){var o=void 0;t&&(o=h(n))?O(o):(E(n),n.firstChild&&M(n,t)),n=n.nextSibling}}function H(e,t,n){!1!==k(e)&&(t&&t.removeChild(e),E(e),M(e,n))}function _(e){g(e);for(var t=e.firstChild;t;){var n=t.nextSibling,o=h(t);if(o){var i=L[o];i&&u(t,i)?(t.parentNode.replaceChild(i,t),I(i,t)):_(t)}else _(t);t=n}}function I(e,t,n){var o=h(t);if(o&&delete L[o],!n){if(!1===A(e,t))return;if(c(e,t),w(e),!1===x(e,t))return}"TEXTAREA"!==e.nodeName?function(e,t){var n,o,r,a,l,c=T(e),v=t.firstChild,p=e.firstChild;e:for(;v;){for(a=v.nextSibling,n=h(v);!c&&p;){if(r=p.nextSibling,v.isSameNode&&v.isSameNode(p)){v=a,p=r;continue e}o=h(p);var b=p.nodeType,g=void 0;if(b===v.nodeType&&(b===d?(n?n!==o&&((l=L[n])?r===l?g=!1:(e.insertBefore(l,p),o?O(o):H(p,e,!0),p=l):g=!1):o&&(g=!1),(g=!1!==g&&u(p,v))&&I(p,v)):b!==f&&b!=m||(g=!0,p.nodeValue!==v.nodeValue&&(p.nodeValue=v.nodeValue))),g){v=a,p=r;continue e}o?O(o):H(p,e,!0),p=r}if(n&&(l=L[n])&&u(l,v))c||S(e,l),I(l,v);else{var A=y(v);!1!==A&&(A&&(v=A),v.actualize&&(v=v.actualize(e.ownerDocument||i)),S(e,v),_(v))}v=a,p=r}!function(e,t,n){for(;t;){var o=t.nextSibling;(n=h(t))?O(n):H(t,e,!0),t=o}}(e,p,o);var w=s[e.nodeName];w&&w(e,t)}(e,t):s.TEXTAREA(e,t)}!function e(t){if(t.nodeType===d||11===t.nodeType)for(var n=t.firstChild;n;){var o=h(n);o&&(L[o]=n),e(n),n=n.nextSibling}}(e);var U,j,P=e,V=P.nodeType,R=t.nodeType;if(!N)if(V===d)R===d?u(e,t)||(E(e),P=function(e,t){for(var n=e.firstChild;n;){var o=n.nextSibling;t.appendChild(n),n=o}return t}(e,(U=t.nodeName,(j=t.namespaceURI)&&"http://www.w3.org/1999/xhtml"!==j?i.createElementNS(j,U):i.createElement(U)))):P=t;else if(V===f||V===m){if(R===V)return P.nodeValue!==t.nodeValue&&(P.nodeValue=t.nodeValue),P;P=t}if(P===t)E(e);else{if(t.isSameNode&&t.isSameNode(P))return;if(I(P,t,N),C)for(var K=0,D=C.length;K<D;K++){var q=L[C[K]];q&&H(q,q.parentNode,!1)}}return!N&&P!==e&&e.parentNode&&(P.actualize&&(P=P.actualize(e.ownerDocument||i)),e.parentNode.replaceChild(P,e)),P})}},n={};function o(e){var i=n[e];if(void 0!==i)return i.exports;var r=n[e]={exports:{}};return t[e].call(r.exports,r,r.exports,o),r.exports}o.n=e=>{var t=e&&e.__esModule?()=>e.default:()=>e;return o.d(t,{a:t}),t},o.d=(e,t)=>{for(var n in t)o.o(t,n)&&!o.o(e,n)&&Object.defineProperty(e,n,{enumerable:!0,get:t[n]})},o.o=(e,t)=>Object.prototype.hasOwnProperty.call(e,t),o.r=e=>{"undefined"!=typeof Symbol&&Symbol.toStringTag&&Object.defineProperty(e,Symbol.toStringTag,{value:"Module"}),Object.defineProperty(e,"__esModule",{value:!0})},e=o(579),window.liveView=new e.LiveView})();
\ No newline at end of file
+(()=>{"use strict";var e,t={579:((a,b,c)=>{let aW=`touchstart`,aJ=`, `,aU=`pointermove`,bm=`disabled`,bn=`axm-disable-with`,bg=` `,b1=`keyup`,aV=`pointerup`,aD=1000,aQ=`submit`,by=`axm-announcer`,b3=`scroll`,aH=`b`,aN=`focus`,bq=`radio`,b6=`,`,br=`checkbox`,b2=`key`,aZ=`wheel`,b5=`beforeunload`,bd=`plus`,aP=`form`,a_=`dragstart`,a$=`drag`,b0=`keydown`,aS=`contextmenu`,aB=1,b9=`ctrl`,bw=`f`,b8=`control`,aO=`blur`,aK=`click`,aF=`h`,bc=`+`,b4=`resize`,bf=`shift`,bx=`d`,aX=`touchend`,bo=`value`,bb=`meta`,bj=`object`,bu=`select`,aM=`change`,bh=`axm-js`,aR=`dblclick`,az=null,aL=`input`,bt=`string`,bp=`type`,ba=`alt`,aC=``,aT=`pointerdown`,bs=`multiple`,be=0,aY=Array.from,aG=JSON.parse,aE=JSON.stringify,b7=Map,bl=Math.max,ay=Object,bv=Object.entries,aI=Object.values,bk=WeakSet,bi=decodeURIComponent,aA=undefined;var $=(()=>{X.forEach((a,b)=>a2(b,a));X.clear()});var S=((a,b,c)=>{const d=G(b,O.end);if(d===aA){return};m(a,{t:`transition_end`,m:d},c)});var m=((a,b,c)=>{a.send(aE(b))});var _=(a=>{X.forEach((b,c)=>{b.refs=b.refs.filter(b=>!a.includes(b));if(b.refs.length===be){X.delete(c);a2(c,b)}})});var t=((a,b,c)=>{if(b.hasAttribute(q.click)){v(a,c,b,b,aK,q.click,a=>({t:aK,m:a}))};if(b instanceof HTMLInputElement||b instanceof HTMLTextAreaElement||b instanceof HTMLSelectElement){if(b.hasAttribute(q.input)){v(a,c,b,b,aL,q.input,a=>{const c=ad(b);return {t:aL,m:a,d:{v:c}}})};if(b.hasAttribute(q.change)){v(a,c,b,b,aM,q.change,a=>{const c=ad(b);return {t:aL,m:a,d:{v:c}}})};if(b.hasAttribute(q.focus)){v(a,c,b,b,aN,q.focus,a=>{const c=ad(b);return {t:aL,m:a,d:{v:c}}})};if(b.hasAttribute(q.blur)){v(a,c,b,b,aO,q.blur,a=>{const c=ad(b);return {t:aL,m:a,d:{v:c}}})}};if(b instanceof HTMLFormElement){if(b.hasAttribute(q.change)){v(a,c,b,b,aM,q.change,(a,c)=>{const d=new FormData(b);const e=new URLSearchParams(d).toString();const f=I(c);return {t:aP,m:a,d:f?{q:e,c:f}:{q:e}}})};if(b.hasAttribute(q.submit)){v(a,c,b,b,aQ,q.submit,a=>{const c=new FormData(b);const d=new URLSearchParams(c).toString();return {t:aP,m:a,d:{q:d}}})}};[[`mouseenter`,q.mouseenter],[`mouseover`,q.mouseover],[`mouseleave`,q.mouseleave],[`mouseout`,q.mouseout],[`mousemove`,q.mousemove],[aR,q.dblclick],[aS,q.contextmenu]].forEach(([d,e])=>{if(!d){return};if(!e){return};if(b.hasAttribute(e)){v(a,c,b,b,d,e,(a,b)=>{if(b instanceof MouseEvent){const c={cx:b.clientX,cy:b.clientY,px:b.pageX,py:b.pageY,ox:b.offsetX,oy:b.offsetY,mx:b.movementX,my:b.movementY,sx:b.screenX,sy:b.screenY};return {t:`mouse`,m:a,d:c}}else{return}})}});[[aT,q.pointerdown],[aU,q.pointermove],[aV,q.pointerup]].forEach(([d,e])=>{if(!d){return};if(!e){return};if(b.hasAttribute(e)){v(a,c,b,b,d,e,(a,b)=>{if(b instanceof PointerEvent){const c={id:b.pointerId,pt:b.pointerType,pr:b.isPrimary,cx:b.clientX,cy:b.clientY,px:b.pageX,py:b.pageY,ox:b.offsetX,oy:b.offsetY,w:b.width,h:b.height,p:b.pressure};return {t:`pointer`,m:a,d:c}}else{return}})}});[[aW,q.touchstart],[aX,q.touchend]].forEach(([d,e])=>{if(!d){return};if(!e){return};if(b.hasAttribute(e)){v(a,c,b,b,d,e,(a,b)=>{if(b instanceof TouchEvent){const c={ts:aY(b.touches).map(ab),cts:aY(b.changedTouches).map(ab)};return {t:`touch`,m:a,d:c}}else{return}})}});if(b.hasAttribute(q.wheel)){v(a,c,b,b,aZ,q.wheel,(a,b)=>{if(b instanceof WheelEvent){var c=aB;if(b.deltaMode===WheelEvent.DOM_DELTA_LINE){c=16}else if(b.deltaMode===WheelEvent.DOM_DELTA_PAGE){c=window.innerHeight};const d={dx:b.deltaX*c,dy:b.deltaY*c,dz:b.deltaZ*c,cx:b.clientX,cy:b.clientY,c:b.ctrlKey};return {t:aZ,m:a,d:d}}else{return}})};if(b.hasAttribute(q.dragstart)){v(a,c,b,b,a_,q.dragstart,(a,c)=>{if(c instanceof DragEvent){if(b.id){c.dataTransfer?.setData(ac,b.id)};return {t:a$,m:a,d:{id:b.id||az,cx:c.clientX,cy:c.clientY}}}else{return}})};if(b.hasAttribute(q.drop)){b.addEventListener(`dragover`,a=>a.preventDefault());v(a,c,b,b,`drop`,q.drop,(a,b)=>{if(b instanceof DragEvent){const c=b.dataTransfer?.getData(ac)||az;return {t:a$,m:a,d:{id:c,cx:b.clientX,cy:b.clientY}}}else{return}})};[[a6.enter,q.viewportenter],[a6.leave,q.viewportleave]].forEach(([d,e])=>{if(!d){return};if(!e){return};if(b.hasAttribute(e)){a8.observe(b);v(a,c,b,b,d,e,(a,b)=>{if(b instanceof CustomEvent){const c=b.detail;return {t:`intersection`,m:a,d:{ir:c.intersectionRatio,i:c.isIntersecting}}}else{return}})}});[[b0,q.keydown],[b1,q.keyup]].forEach(([d,e])=>{if(!d){return};if(!e){return};if(b.hasAttribute(e)){v(a,c,b,b,d,e,(a,b)=>{if(b instanceof KeyboardEvent){const c={k:b.key,kc:b.code,a:b.altKey,c:b.ctrlKey,s:b.shiftKey,me:b.metaKey};return {t:b2,m:a,d:c}}else{return}})}})});var T=((a,b)=>{const c=a.getAttribute(b)?.split(bg).filter(a=>a!==aC);if(!c||c.length===be){return};return c});var A=((a,b)=>{if(x.includes(b.type)){return !1};if(b instanceof KeyboardEvent||y.includes(b.type)){return a.hasAttribute(w)};return !0});var R=((a,b,c)=>{if(P.has(b)){return !0};const d=T(b,O.leave);if(!d){return !1};P.add(b);b.classList.add(...d);U(b,()=>{b.remove();S(a,b,c)});return !0});var ab=(a=>({id:a.identifier,cx:a.clientX,cy:a.clientY,px:a.pageX,py:a.pageY,sx:a.screenX,sy:a.screenY}));var au=((a,b,c)=>{if(!b.transition){c();return};const d=b.transition.classes.split(bg).filter(a=>a!==aC);a.classList.add(...d);setTimeout(()=>{a.classList.remove(...d);c()},b.transition.duration_ms)});var ag=((a,b,c)=>{if(c){ay.assign(b,c)};if(typeof a!==bj||a===az){return};if(typeof a.f===`number`){const c=b[a.f];if(c===aA){console.error(`unknown statics`,a.f)}else{a.f=c}};for(const [c,d] of bv(a)){if(c!==bw){ag(d,b)}}});var an=(a=>{if(a!==document.activeElement){return !1};const b=ak.get(a);if(b!==aA){return a.value!==b};return !(a instanceof HTMLSelectElement)&&a.value!==a.defaultValue});var af=((a,b,c)=>{var g=(a=>{var b=aC;const c=a.f;c.forEach((c,d)=>{b=b.concat(c);if(a.d===aA){return};const e=a.d[d];if(e===aA||e===az){return};if(typeof e===bt){b=b.concat(e)}else if(aH in e){const a=e.f;aI(e.b).forEach(c=>{const d={f:a,d:c};b=b.concat(g(d))})}else{b=b.concat(g(e))}});return b});var h=((a,b,e)=>{aa.forEach(a=>{a.target.removeEventListener(a.event,a.callback)});aa=[];d(b,e,{onNodeAdded:b=>{if(b instanceof Element){t(a,b,c);Q(a,b,c)};return b},onBeforeNodeDiscarded:b=>{if(b instanceof Element){a9(b);return !R(a,b,c)};return !0},onBeforeElUpdated:(a,b)=>{a0(a,b);if(ao(a,b)){ak.delete(a);return !0};if(a instanceof HTMLInputElement&&b instanceof HTMLInputElement){if(b.getAttribute(bp)===bq||b.getAttribute(bp)===br){b.checked=a.checked}else{b.value=a.value}};if(a instanceof HTMLTextAreaElement&&b instanceof HTMLTextAreaElement){b.value=a.value};if(a instanceof HTMLOptionElement&&b instanceof HTMLOptionElement){const c=a.closest(bu);if(c&&b.closest(bu)?.hasAttribute(aj)&&!an(c)){return !0};if(b.closest(bu)?.hasAttribute(bs)){b.selected=a.selected}};if(a instanceof HTMLSelectElement&&b instanceof HTMLSelectElement&&!b.hasAttribute(bs)){b.value=a.value};return !0}});const f=aI(r).map(a=>`[${a}]`).join(aJ);document.querySelectorAll(f).forEach(b=>{u(a,b,c)});const g=b.querySelector(`form[${ap}]`);if(g instanceof HTMLFormElement){ar(g)}});if(!b.viewState){return};const e=g(b.viewState);const f=document.querySelector(`#live-view-container`);if(!f){return};h(a,f,e)});var ae=((a,b)=>{const c=a.getAttribute(b);if(c){const a=parseInt(c,10);if(a){return a}};return az});var o=((a,b,c,d)=>{const e=aG(b.data);if(e.t===`i`){ag(e.d,c.statics,e.s);c.viewState=e.d;af(a,c,d);s(a,d)}else if(e.t===`r`){if(!c.viewState){return};if(!e.d){return};ag(e.d,c.statics,e.s);ah(c.viewState,e.d);af(a,c,d)}else if(e.t===`j`){for(const a of e.d){ai(a)}}else if(e.t===aF){if(d.debug){console.timeEnd(i)}}else if(e.t===`rs`){l=e.d}else if(e.t===aH){if(g===aA){g=e.d}}else if(e.t===`a`){_(e.d)}else{const a=e}});var s=((a,b)=>{const c=aI(q).map(a=>`[${a}]`).join(aJ);document.querySelectorAll(c).forEach(c=>{t(a,c,b)})});var I=(a=>{const b=a.target;if(b instanceof HTMLInputElement||b instanceof HTMLSelectElement||b instanceof HTMLTextAreaElement){return b.name||aA};return});var K=(a=>{var b=aA;for(const c of aY(a.attributes)){if(!c.name.startsWith(J)){continue};b=b||{};b[c.name.slice(J.length)]=L(c.value)};return b});var u=((a,b,c)=>{[[b0,r.keydown],[b1,r.keyup]].forEach(([d,e])=>{if(!d){return};if(!e){return};if(b.hasAttribute(e)){v(a,c,b,document,d,e,(a,b)=>{if(b instanceof KeyboardEvent){const c={k:b.key,kc:b.code,a:b.altKey,c:b.ctrlKey,s:b.shiftKey,me:b.metaKey};return {t:b2,m:a,d:c}}else{return}})}});if(b.hasAttribute(r.focus)){v(a,c,b,document,aN,r.focus,a=>({t:`window_focus`,m:a}))};if(b.hasAttribute(r.blur)){v(a,c,b,document,aO,r.blur,a=>({t:`window_blur`,m:a}))};if(b.hasAttribute(r.scroll)){v(a,c,b,document,b3,r.scroll,a=>{const b={sx:window.scrollX,sy:window.scrollY};return {t:b3,m:a,d:b}})};if(b.hasAttribute(r.resize)){v(a,c,b,window,b4,r.resize,a=>({t:b4,m:a,d:{w:window.innerWidth,h:window.innerHeight}}))};if(b.hasAttribute(r.visibilitychange)){v(a,c,b,document,`visibilitychange`,r.visibilitychange,a=>({t:`visibility`,m:a,d:{h:document.visibilityState===`hidden`}}))};[[`online`,r.online],[`offline`,r.offline]].forEach(([d,e])=>{if(!d){return};if(!e){return};if(b.hasAttribute(e)){v(a,c,b,window,d,e,a=>({t:`network`,m:a,d:{o:navigator.onLine}}))}});if(b.hasAttribute(r.beforeunload)){v(a,c,b,window,b5,r.beforeunload,a=>({t:`window_beforeunload`,m:a}))}});var av=(a=>{var b=document.getElementById(by);if(!b){b=document.createElement(`div`);b.id=by;b.setAttribute(`aria-live`,`polite`);b.setAttribute(`role`,`status`);b.setAttribute(`style`,`position: absolute; width: 1px; height: 1px; overflow: hidden; clip: rect(0 0 0 0); white-space: nowrap;`);document.body.appendChild(b)};b.textContent=aC;const c=b;setTimeout(()=>{c.textContent=a},50)});var ar=(a=>{if(aq.has(a)){return};aq.add(a);a.submit()});var ah=((a,b)=>{var c=((a,b)=>{for(const [c,e] of bv(b)){if(typeof e===bt){a[c]=e}else if(e===az){delete a[c]}else if(typeof e===bj){const b=a[c];if(b===aA){continue};if(bx in e){if(typeof b===bt){a[c]=e}else if(bx in b){ah(b,e)}else if(aH in b){console.error(`not implemented: b in current`)}else{a[c]=e}}else if(aH in e){if(typeof b===bt){a[c]=e}else{if(!(aH in b)){a[c]={f:b.f,b:e.b}}else{d(b,e)}}}else if(bw in e){if(typeof b===bt){a[c]=e}else if(bx in b){ah(b,e)}else if(aH in b){console.error(`not implemented: b in current, with f`)}else{a[c]=e}}else{console.error(`unexpected diff value`,e)}}else{const a=e}}});var d=((a,b)=>{if(b.f){a.f=b.f};if(b.b){for(const [d,e] of bv(b.b)){if(e===az){delete a.b[d]}else{const b=a.b[d];if(b===aA||typeof b===bt){a.b[d]=e}else{c(b,e)}}}}});if(b.f){a.f=b.f};if(b.d&&b.d!==az){c(a.d||{},b.d)}});var U=((a,b)=>{const c=window.getComputedStyle(a);const d=bl(V(c.animationDuration)+ V(c.animationDelay),V(c.transitionDuration)+ V(c.transitionDelay));if(d>be){setTimeout(b,d)}else{b()}});var N=((a,b)=>{var c=ae(a,`axm-debounce`);if(c){return aw(b,c)};var c=ae(a,`axm-throttle`);if(c){return ax(b,c)};return b});var v=((a,b,c,d,e,f,g)=>{const h=N(c,d=>{const h=G(c,f);if(!h){return};const i=g(h,d);if(!i){return};if(`m` in i){const a=K(c);if(a){i.v=a};i.r=Z(c,e===aK||e===aQ)};am(c);m(a,i,b)});const i=a=>{if(a instanceof KeyboardEvent&&!B(c,a)){return};if(A(c,a)){a.preventDefault()};h(a)};if(!(d instanceof Element)){aa.push({target:d,event:e,callback:i})};const j=z.includes(e)&&!c.hasAttribute(w);d.addEventListener(e,i,{passive:j})});var h=(a=>{const b=document.getElementById(`live-view-container`);if(b===az){return};if(f===aA){f=b.getAttribute(`data-axm-session`)};var c;if(location.protocol.indexOf(`https`)===-aB){c=`ws`}else{c=`wss`};const d=new URLSearchParams();if(f){d.set(`axm-session`,f)};if(g){d.set(`axm-build`,g)};const e=d.toString()?`?${d}`:aC;const h=new WebSocket(`${c}://${window.location.host}${window.location.pathname}${e}`);var i={statics:{}};h.addEventListener(`open`,()=>{n(h,a)});h.addEventListener(`message`,b=>{o(h,b,i,a)});h.addEventListener(`close`,()=>{p(a)})});var a4=(a=>{if(a instanceof HTMLInputElement){return a.getAttribute(bo)}else{return a.textContent}});var M=(a=>typeof a===bj&&a!==az&&bh in a);var H=(a=>{try{const b=aG(bi(a));return M(b)?b:aA}catch{return}});var a5=((a,b)=>{if(a instanceof HTMLInputElement){a.setAttribute(bo,b);a.value=b}else{a.textContent=b}});var n=((a,b)=>{if(j){const c={t:`rc`};m(a,c,b)};j=!0;setInterval(()=>{const c={t:aF};if(b.debug){console.time(i)};m(a,c,b)},30*aD)});var Q=((a,b,c)=>{const d=T(b,O.enter);if(!d){return};b.classList.add(...d);U(b,()=>{b.classList.remove(...d);S(a,b,c)})});var aw=((a,b)=>{var c;return (...d)=>{if(c){clearTimeout(c)};c=setTimeout(()=>{a(...d)},b)}});var p=(a=>{$();const b=l;l=k;setTimeout(()=>{h(a)},b)});var a2=((a,b)=>{a.classList.remove(W);if(b.disabled===!1){a.removeAttribute(bm)};if(b.text!==aA&&b.text!==az){a5(a,b.text)}});var G=((a,b)=>{const c=a.getAttribute(b);if(!c){return};const d=H(c);if(d){d[bh].forEach(a=>ai(a));const a=d[`axm-push`];return a===aA?aA:encodeURIComponent(aE(a))};return c});var ad=(a=>{if(a instanceof HTMLTextAreaElement){return a.value}else if(a instanceof HTMLInputElement){if(a.getAttribute(bp)===bq||a.getAttribute(bp)===br){return a.checked}else{return a.value}}else if(a instanceof HTMLSelectElement){if(a.hasAttribute(bs)){return aY(a.selectedOptions).map(a=>a.value)}else{return a.value}}else{throw `Input has no input value`}});var ao=((a,b)=>al(a)&&b.hasAttribute(aj)&&!an(a));var al=(a=>a instanceof HTMLInputElement||a instanceof HTMLTextAreaElement||a instanceof HTMLSelectElement);var am=(a=>{const b=a instanceof HTMLFormElement?aY(a.elements):[a];b.forEach(a=>{if(al(a)){ak.set(a,a.value)}})});var a3=(a=>a instanceof HTMLButtonElement||a instanceof HTMLInputElement||a instanceof HTMLSelectElement||a instanceof HTMLTextAreaElement||a instanceof HTMLFieldSetElement);var a9=(a=>{[a,...aY(a.querySelectorAll(`*`))].forEach(a=>{a8.unobserve(a);a7.delete(a)})});var Z=((a,b)=>{const c=Y++;const d=[a];if(b&&a instanceof HTMLFormElement){a.querySelectorAll(`button:not([type]), button[type=submit], input[type=submit]`).forEach(a=>{d.push(a)})};d.forEach(a=>{const d=X.get(a);if(d){d.refs.push(c);return};const e={refs:[c]};if(b&&a3(a)){e.disabled=a.hasAttribute(bm);if(a.hasAttribute(bn)){e.text=a4(a)}};X.set(a,e);a1(a,e)});return c});var D=(a=>{a=a.replace(/\s/g,aC).toLowerCase();if(a===bc){return bd};if(a.endsWith(`++`)){a=`${a.slice(be,-2)}+plus`};return a.split(bc).map(a=>C.get(a)||a).join(bc)});var ax=((a,b)=>{var c;return (...d)=>{if(c){return}else{a(...d);c=setTimeout(()=>{c=az},b)}}});var V=(a=>bl(be,...a.split(b6).map(a=>{const b=a.trim();const c=parseFloat(b);if(isNaN(c)){return be};return b.endsWith(`ms`)?c:c*aD})));var B=((a,b)=>{const c=a.getAttribute(`axm-key`);if(!c){return !0};return c.split(b6).some(a=>F(D(a),b))});var L=(a=>{try{return aG(bi(a))}catch{return a}});var F=((a,b)=>{const c=a.split(bc);const d=c.pop();if(!d){return !1};const e=d.length===aB&&!/[a-z0-9]/.test(d)&&!c.includes(bf);const f=E.every(([a,f])=>{if(a===d){return !0};if(a===bf&&e){return !0};return f(b)===c.includes(a)});if(!f){return !1};const g=b.key.toLowerCase();switch(d){case `space`:return g===bg;case bd:return g===bc;case b9:return g===b8}if(g===d){return !0};if(/^[a-z]$/.test(d)){return b.code===`Key${d.toUpperCase()}`};if(/^[0-9]$/.test(d)){return b.code===`Digit${d}`};return !1});var a0=((a,b)=>{const c=X.get(a);if(!c){return};if(c.disabled!==aA){c.disabled=b.hasAttribute(bm)};if(c.text!==aA){c.text=a4(b)};a1(b,c)});var ai=(a=>{const b=()=>{if(a.kind.t===`navigate_to`){const b=a.kind.uri;if(b.startsWith(`http`)){window.location.href=b}else{window.location.pathname=b}}else if(a.kind.t===`add_class`){const {selector:b,klass:c}=a.kind;document.querySelectorAll(b).forEach(a=>{a.classList.add(c)})}else if(a.kind.t===`remove_class`){const {selector:b,klass:c}=a.kind;document.querySelectorAll(b).forEach(a=>{a.classList.remove(c)})}else if(a.kind.t===`toggle_class`){const {selector:b,klass:c}=a.kind;document.querySelectorAll(b).forEach(a=>{a.classList.toggle(c)})}else if(a.kind.t===`clear_value`){const {selector:b}=a.kind;document.querySelectorAll(b).forEach(a=>{if(a instanceof HTMLInputElement||a instanceof HTMLSelectElement||a instanceof HTMLTextAreaElement){a.value=aC}})}else if(a.kind.t===`set_title`){document.title=a.kind.title}else if(a.kind.t===`history_push_state`){window.history.pushState({},aC,a.kind.uri)}else if(a.kind.t===`reload`){window.location.reload()}else if(a.kind.t===aN){const b=document.querySelector(a.kind.selector);if(b instanceof HTMLElement){b.focus()}}else if(a.kind.t===aO){const b=document.querySelector(a.kind.selector);if(b instanceof HTMLElement){b.blur()}}else if(a.kind.t===`focus_first`){const b=document.querySelector(a.kind.selector);if(!b){return};const c=aY(b.querySelectorAll(at)).find(a=>a instanceof HTMLElement&&!a.hasAttribute(bm)&&a.offsetParent!==az);if(c instanceof HTMLElement){c.focus()}}else if(a.kind.t===`set_attribute`){const {selector:b,name:c,value:d}=a.kind;document.querySelectorAll(b).forEach(a=>{a.setAttribute(c,d)})}else if(a.kind.t===`remove_attribute`){const {selector:b,name:c}=a.kind;document.querySelectorAll(b).forEach(a=>{a.removeAttribute(c)})}else if(a.kind.t===`show`){const {selector:b,options:c}=a.kind;document.querySelectorAll(b).forEach(a=>{if(!(a instanceof HTMLElement)){return};a.style.display=c.display||`block`;au(a,c,()=>{})})}else if(a.kind.t===`hide`){const {selector:b,options:c}=a.kind;document.querySelectorAll(b).forEach(a=>{if(!(a instanceof HTMLElement)){return};au(a,c,()=>{a.style.display=`none`})})}else if(a.kind.t===`scroll_into_view`){document.querySelector(a.kind.selector)?.scrollIntoView({block:`nearest`})}else if(a.kind.t===`dispatch_event`){const {selector:b,event:c,detail:d}=a.kind;document.querySelectorAll(b).forEach(a=>{a.dispatchEvent(new CustomEvent(c,{bubbles:!0,detail:d}))})}else if(a.kind.t===`announce`){av(a.kind.text)}else if(a.kind.t===`trigger_action`){const b=document.querySelector(a.kind.selector);if(b instanceof HTMLFormElement){ar(b)}}else{const b=a.kind}};if(a.delay_ms){setTimeout(b,a.delay_ms)}else{b()}});var a1=((a,b)=>{a.classList.add(W);if(b.disabled!==aA){a.setAttribute(bm,aC)};if(b.text!==aA){a5(a,a.getAttribute(bn)||aC)}});ay.defineProperty(b,`__esModule`,{value:!0});const d=c(34).default;class e{constructor(){this.options={debug:!1};h(this.options)}enableDebug(){this.options.debug=!0}disableDebug(){this.options.debug=!1}}var f;var g;const i=`ping`;var j=!1;const k=aD;var l=k;const q={click:`axm-click`,input:`axm-input`,change:`axm-change`,submit:`axm-submit`,focus:`axm-focus`,blur:`axm-blur`,keydown:`axm-keydown`,keyup:`axm-keyup`,mouseenter:`axm-mouseenter`,mouseover:`axm-mouseover`,mouseleave:`axm-mouseleave`,mouseout:`axm-mouseout`,mousemove:`axm-mousemove`,dblclick:`axm-dblclick`,contextmenu:`axm-contextmenu`,pointerdown:`axm-pointerdown`,pointermove:`axm-pointermove`,pointerup:`axm-pointerup`,touchstart:`axm-touchstart`,touchend:`axm-touchend`,wheel:`axm-wheel`,dragstart:`axm-dragstart`,drop:`axm-drop`,viewportenter:`axm-viewport-enter`,viewportleave:`axm-viewport-leave`};const r={keydown:`axm-window-keydown`,keyup:`axm-window-keyup`,focus:`axm-window-focus`,blur:`axm-window-blur`,scroll:`axm-scroll`,resize:`axm-window-resize`,visibilitychange:`axm-visibility-change`,online:`axm-online`,offline:`axm-offline`,beforeunload:`axm-window-beforeunload`};const w=`axm-prevent-default`;const x=[a_,b5];const y=[aT,aU,aV,aW,aX,aZ,aR,aS];const z=[aW,aX,aZ];const C=new b7([[b8,b9],[`option`,ba],[`cmd`,bb],[`command`,bb],[`super`,bb],[`esc`,`escape`],[`up`,`arrowup`],[`down`,`arrowdown`],[`left`,`arrowleft`],[`right`,`arrowright`]]);const E=[[b9,a=>a.ctrlKey],[ba,a=>a.altKey],[bf,a=>a.shiftKey],[bb,a=>a.metaKey]];const J=`axm-value-`;const O={enter:`axm-transition-enter`,leave:`axm-transition-leave`,end:`axm-transition-end`};const P=new bk();const W=`axm-loading`;const X=new b7();var Y=be;const a6={enter:`axm:viewport-enter`,leave:`axm:viewport-leave`};const a7=new bk();const a8=new IntersectionObserver(a=>{a.forEach(a=>{const b=a.target;if(a.isIntersecting&&!a7.has(b)){a7.add(b);b.dispatchEvent(new CustomEvent(a6.enter,{detail:a}))}else if(!a.isIntersecting&&a7.has(b)){a7.delete(b);b.dispatchEvent(new CustomEvent(a6.leave,{detail:a}))}})});var aa=[];const ac=`application/x-axm-id`;const aj=`axm-controlled`;const ak=new WeakMap();const ap=`axm-trigger-action`;const aq=new bk();const at=[`a[href]`,`button`,`input:not([type=hidden])`,bu,`textarea`,`[tabindex]:not([tabindex='-1'])`,`[contenteditable]`].join(aJ);b.LiveView=e}),34:(e,t,n)=>{n.r(t),n.d(t,{default:()=>b});var o,i="undefined"==typeof document?void 0:document,r=!!i&&"content"in i.createElement("template"),a=!!i&&i.createRange&&"createContextualFragment"in i.createRange();function u(e,t){var n,o,i=e.nodeName,r=t.nodeName;return i===r||(n=i.charCodeAt(0),o=r.charCodeAt(0),n<=90&&o>=97?i===r.toUpperCase():o<=90&&n>=97&&r===i.toUpperCase())}function l(e,t,n){e[n]!==t[n]&&(e[n]=t[n],e[n]?e.setAttribute(n,""):e.removeAttribute(n))}var c,s={OPTION:function(e,t){var n=e.parentNode;if(n){var o=n.nodeName.toUpperCase();"OPTGROUP"===o&&(o=(n=n.parentNode)&&n.nodeName.toUpperCase()),"SELECT"!==o||n.hasAttribute("multiple")||(e.hasAttribute("selected")&&!t.selected&&(e.setAttribute("selected","selected"),e.removeAttribute("selected")),n.selectedIndex=-1)}l(e,t,"selected")},INPUT:function(e,t){l(e,t,"checked"),l(e,t,"disabled"),e.value!==t.value&&(e.value=t.value),t.hasAttribute("value")||e.removeAttribute("value")},TEXTAREA:function(e,t){var n=t.value;e.value!==n&&(e.value=n);var o=e.firstChild;if(o){var i=o.nodeValue;if(i==n||!n&&i==e.placeholder)return;o.nodeValue=n}},SELECT:function(e,t){if(!t.hasAttribute("multiple")){for(var n,o,i=-1,r=0,a=e.firstChild;a;)if("OPTGROUP"===(o=a.nodeName&&a.nodeName.toUpperCase()))a=(n=a).firstChild;else{if("OPTION"===o){if(a.hasAttribute("selected")){i=r;break}r++}!(a=a.nextSibling)&&n&&(a=n.nextSibling,n=null)}e.selectedIndex=i}}},d=1,f=3,m=8;function v(){}function p(e){if(e)return e.getAttribute&&e.getAttribute("id")||e.id}const b=(c=function(e,t){var n,o,i,r,a=t.attributes;if(11!==t.nodeType&&11!==e.nodeType){for(var u=a.length-1;u>=0;u--)o=(n=a[u]).name,i=n.namespaceURI,r=n.value,i?(o=n.localName||o,e.getAttributeNS(i,o)!==r&&("xmlns"===n.prefix&&(o=n.name),e.setAttributeNS(i,o,r))):e.getAttribute(o)!==r&&e.setAttribute(o,r);for(var l=e.attributes,c=l.length-1;c>=0;c--)o=(n=l[c]).name,(i=n.namespaceURI)?(o=n.localName||o,t.hasAttributeNS(i,o)||e.removeAttributeNS(i,o)):t.hasAttribute(o)||e.removeAttribute(o)}},function(e,t,n){if(n||(n={}),"string"==typeof t)if("#document"===e.nodeName||"HTML"===e.nodeName||"BODY"===e.nodeName){var l=t;(t=i.createElement("html")).innerHTML=l}else b=(b=t).trim(),t=r?function(e){var t=i.createElement("template");return t.innerHTML=e,t.content.childNodes[0]}(b):a?function(e){return o||(o=i.createRange()).selectNode(i.body),o.createContextualFragment(e).childNodes[0]}(b):function(e){var t=i.createElement("body");return t.innerHTML=e,t.childNodes[0]}(b);else 11===t.nodeType&&(t=t.firstElementChild);var b,h=n.getNodeKey||p,y=n.onBeforeNodeAdded||v,g=n.onNodeAdded||v,A=n.onBeforeElUpdated||v,w=n.onElUpdated||v,k=n.onBeforeNodeDiscarded||v,E=n.onNodeDiscarded||v,x=n.onBeforeElChildrenUpdated||v,T=n.skipFromChildren||v,S=n.addChild||function(e,t){return e.appendChild(t)},N=!0===n.childrenOnly,L=Object.create(null),C=[];function O(e){C.push(e)}function M(e,t){if(e.nodeType===d)for(var n=e.firstChild;n;){var o=void 0;t&&(o=h(n))?O(o):(E(n),n.firstChild&&M(n,t)),n=n.nextSibling}}function H(e,t,n){!1!==k(e)&&(t&&t.removeChild(e),E(e),M(e,n))}function _(e){g(e);for(var t=e.firstChild;t;){var n=t.nextSibling,o=h(t);if(o){var i=L[o];i&&u(t,i)?(t.parentNode.replaceChild(i,t),I(i,t)):_(t)}else _(t);t=n}}function I(e,t,n){var o=h(t);if(o&&delete L[o],!n){if(!1===A(e,t))return;if(c(e,t),w(e),!1===x(e,t))return}"TEXTAREA"!==e.nodeName?function(e,t){var n,o,r,a,l,c=T(e),v=t.firstChild,p=e.firstChild;e:for(;v;){for(a=v.nextSibling,n=h(v);!c&&p;){if(r=p.nextSibling,v.isSameNode&&v.isSameNode(p)){v=a,p=r;continue e}o=h(p);var b=p.nodeType,g=void 0;if(b===v.nodeType&&(b===d?(n?n!==o&&((l=L[n])?r===l?g=!1:(e.insertBefore(l,p),o?O(o):H(p,e,!0),p=l):g=!1):o&&(g=!1),(g=!1!==g&&u(p,v))&&I(p,v)):b!==f&&b!=m||(g=!0,p.nodeValue!==v.nodeValue&&(p.nodeValue=v.nodeValue))),g){v=a,p=r;continue e}o?O(o):H(p,e,!0),p=r}if(n&&(l=L[n])&&u(l,v))c||S(e,l),I(l,v);else{var A=y(v);!1!==A&&(A&&(v=A),v.actualize&&(v=v.actualize(e.ownerDocument||i)),S(e,v),_(v))}v=a,p=r}!function(e,t,n){for(;t;){var o=t.nextSibling;(n=h(t))?O(n):H(t,e,!0),t=o}}(e,p,o);var w=s[e.nodeName];w&&w(e,t)}(e,t):s.TEXTAREA(e,t)}!function e(t){if(t.nodeType===d||11===t.nodeType)for(var n=t.firstChild;n;){var o=h(n);o&&(L[o]=n),e(n),n=n.nextSibling}}(e);var U,j,P=e,V=P.nodeType,R=t.nodeType;if(!N)if(V===d)R===d?u(e,t)||(E(e),P=function(e,t){for(var n=e.firstChild;n;){var o=n.nextSibling;t.appendChild(n),n=o}return t}(e,(U=t.nodeName,(j=t.namespaceURI)&&"http://www.w3.org/1999/xhtml"!==j?i.createElementNS(j,U):i.createElement(U)))):P=t;else if(V===f||V===m){if(R===V)return P.nodeValue!==t.nodeValue&&(P.nodeValue=t.nodeValue),P;P=t}if(P===t)E(e);else{if(t.isSameNode&&t.isSameNode(P))return;if(I(P,t,N),C)for(var K=0,D=C.length;K<D;K++){var q=L[C[K]];q&&H(q,q.parentNode,!1)}}return!N&&P!==e&&e.parentNode&&(P.actualize&&(P=P.actualize(e.ownerDocument||i)),e.parentNode.replaceChild(P,e)),P})}},n={};function o(e){var i=n[e];if(void 0!==i)return i.exports;var r=n[e]={exports:{}};return t[e].call(r.exports,r,r.exports,o),r.exports}o.n=e=>{var t=e&&e.__esModule?()=>e.default:()=>e;return o.d(t,{a:t}),t},o.d=(e,t)=>{for(var n in t)o.o(t,n)&&!o.o(e,n)&&Object.defineProperty(e,n,{enumerable:!0,get:t[n]})},o.o=(e,t)=>Object.prototype.hasOwnProperty.call(e,t),o.r=e=>{"undefined"!=typeof Symbol&&Symbol.toStringTag&&Object.defineProperty(e,Symbol.toStringTag,{value:"Module"}),Object.defineProperty(e,"__esModule",{value:!0})},e=o(579),window.liveView=new e.LiveView})();
\ No newline at end of file
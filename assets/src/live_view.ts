@@ -25,14 +25,32 @@ interface LiveViewOptions {
 
 interface State {
   viewState?: Template;
+  // the fixed parts of templates, sent once per connection and referenced by id
+  statics: Statics;
 }
 
+type Statics = { [id: string]: string[] }
+
+// the token of the view's session, if the server has sessions enabled
+var sessionToken: string | null | undefined
+
+// the build of the server the page was loaded from, if the server has live reload enabled. Sent
+// when reconnecting so the server can tell us to reload if it has been restarted
+var buildId: string | undefined
+
 function connect(options: LiveViewOptions) {
   // only connect if there is a live view on the page
-  if (document.getElementById("live-view-container") === null) {
+  const container = document.getElementById("live-view-container")
+  if (container === null) {
     return
   }
 
+  // only read the token on the first connect since rendering the view replaces the container's
+  // attributes
+  if (sessionToken === undefined) {
+    sessionToken = container.getAttribute("data-axm-session")
+  }
+
   var proto: string
   if (location.protocol.indexOf("https") === -1) {
     proto = "ws"
@@ -40,9 +58,18 @@ function connect(options: LiveViewOptions) {
     proto = "wss"
   }
 
-  const socket = new WebSocket(`${proto}://${window.location.host}${window.location.pathname}`);
+  const params = new URLSearchParams()
+  if (sessionToken) {
+    params.set("axm-session", sessionToken)
+  }
+  if (buildId) {
+    params.set("axm-build", buildId)
+  }
+  const query = params.toString() ? `?${params}` : ""
 
-  var state: State = {}
+  const socket = new WebSocket(`${proto}://${window.location.host}${window.location.pathname}${query}`);
+
+  var state: State = { statics: {} }
 
   socket.addEventListener("open", () => {
     onOpen(socket, options)
@@ -57,7 +84,7 @@ function connect(options: LiveViewOptions) {
   })
 }
 
-type MessageFromView = InitialRender | Render | JsCommands | HealthPong
+type MessageFromView = InitialRender | Render | JsCommands | HealthPong | ServerRestarting | BuildId | Ack
 
 interface Template {
   f: string[],
@@ -94,11 +121,13 @@ interface TemplateDiffLoop {
 type InitialRender = {
   t: "i",
   d: Template,
+  s?: Statics,
 }
 
 type Render = {
   t: "r",
   d: TemplateDiff | null,
+  s?: Statics,
 }
 
 type JsCommands = {
@@ -108,8 +137,25 @@ type JsCommands = {
 
 type HealthPong = { t: "h" }
 
+// the server is shutting down, `d` is how many milliseconds to wait before reconnecting
+type ServerRestarting = { t: "rs", d: number }
+
+// sent when live reload is enabled
+type BuildId = { t: "b", d: string }
+
+// the events with these refs have been processed
+type Ack = { t: "a", d: number[] }
+
 const pingTimeLabel = "ping"
 
+// whether a socket has been opened before, used to tell the server about reconnects
+var hasConnected = false
+
+const defaultReconnectDelay = 1000
+
+// how long to wait before reconnecting after the socket is closed
+var reconnectDelay = defaultReconnectDelay
+
 function socketSend(
   socket: WebSocket,
   msg: MessageToView,
@@ -122,6 +168,12 @@ function onOpen(
   socket: WebSocket,
   options: LiveViewOptions,
 ) {
+  if (hasConnected) {
+    const msg: MessageToView = { t: "rc" }
+    socketSend(socket, msg, options)
+  }
+  hasConnected = true
+
   setInterval(() => {
     const msg: MessageToView = { t: "h" }
     if (options.debug) {
@@ -140,6 +192,7 @@ function onMessage(
   const msg: MessageFromView = JSON.parse(event.data)
 
   if (msg.t === "i") {
+    resolveStatics(msg.d, state.statics, msg.s)
     state.viewState = msg.d
     updateDomFromState(socket, state, options)
     bindInitialEvents(socket, options)
@@ -147,6 +200,7 @@ function onMessage(
   } else if (msg.t === "r") {
     if (!state.viewState) { return }
     if (!msg.d) { return }
+    resolveStatics(msg.d, state.statics, msg.s)
     patchTemplate(state.viewState, msg.d)
     updateDomFromState(socket, state, options)
 
@@ -161,15 +215,33 @@ function onMessage(
       console.timeEnd(pingTimeLabel)
     }
 
+  } else if (msg.t === "rs") {
+    // the server will close the socket
+    reconnectDelay = msg.d
+
+  } else if (msg.t === "b") {
+    if (buildId === undefined) {
+      buildId = msg.d
+    }
+
+  } else if (msg.t === "a") {
+    acknowledge(msg.d)
+
   } else {
     const _: never = msg
   }
 }
 
 function onClose(options: LiveViewOptions) {
+  // events that are in flight will never be acknowledged
+  clearLoading()
+
+  const delay = reconnectDelay
+  reconnectDelay = defaultReconnectDelay
+
   setTimeout(() => {
     connect(options)
-  }, 1000)
+  }, delay)
 }
 
 const axm = {
@@ -186,6 +258,18 @@ const axm = {
   mouseleave: "axm-mouseleave",
   mouseout: "axm-mouseout",
   mousemove: "axm-mousemove",
+  dblclick: "axm-dblclick",
+  contextmenu: "axm-contextmenu",
+  pointerdown: "axm-pointerdown",
+  pointermove: "axm-pointermove",
+  pointerup: "axm-pointerup",
+  touchstart: "axm-touchstart",
+  touchend: "axm-touchend",
+  wheel: "axm-wheel",
+  dragstart: "axm-dragstart",
+  drop: "axm-drop",
+  viewportenter: "axm-viewport-enter",
+  viewportleave: "axm-viewport-leave",
 }
 
 const axm_window = {
@@ -194,6 +278,11 @@ const axm_window = {
   focus: "axm-window-focus",
   blur: "axm-window-blur",
   scroll: "axm-scroll",
+  resize: "axm-window-resize",
+  visibilitychange: "axm-visibility-change",
+  online: "axm-online",
+  offline: "axm-offline",
+  beforeunload: "axm-window-beforeunload",
 }
 
 function bindInitialEvents(socket: WebSocket, options: LiveViewOptions) {
@@ -249,11 +338,12 @@ function addEventListeners(
 
   if (element instanceof HTMLFormElement) {
     if (element.hasAttribute(axm.change)) {
-      on(socket, options, element, element, "change", axm.change, (msg) => {
+      on(socket, options, element, element, "change", axm.change, (msg, event) => {
         // workaround for https://github.com/microsoft/TypeScript/issues/30584
         const form = new FormData(element) as any
         const query = new URLSearchParams(form).toString()
-        return { t: "form", m: msg, d: { q: query } }
+        const changed = changedField(event)
+        return { t: "form", m: msg, d: changed ? { q: query, c: changed } : { q: query } }
       })
     }
 
@@ -273,6 +363,8 @@ function addEventListeners(
     ["mouseleave", axm.mouseleave],
     ["mouseout", axm.mouseout],
     ["mousemove", axm.mousemove],
+    ["dblclick", axm.dblclick],
+    ["contextmenu", axm.contextmenu],
   ].forEach(([event, axm]) => {
     if (!event) { return }
     if (!axm) { return }
@@ -300,6 +392,134 @@ function addEventListeners(
     }
   });
 
+  [
+    ["pointerdown", axm.pointerdown],
+    ["pointermove", axm.pointermove],
+    ["pointerup", axm.pointerup],
+  ].forEach(([event, axm]) => {
+    if (!event) { return }
+    if (!axm) { return }
+
+    if (element.hasAttribute(axm)) {
+      on(socket, options, element, element, event, axm, (msg, event) => {
+        if (event instanceof PointerEvent) {
+          const data: PointerData = {
+            id: event.pointerId,
+            pt: event.pointerType,
+            pr: event.isPrimary,
+            cx: event.clientX,
+            cy: event.clientY,
+            px: event.pageX,
+            py: event.pageY,
+            ox: event.offsetX,
+            oy: event.offsetY,
+            w: event.width,
+            h: event.height,
+            p: event.pressure,
+          }
+          return { t: "pointer", m: msg, d: data }
+        } else {
+          return
+        }
+      })
+    }
+  });
+
+  [
+    ["touchstart", axm.touchstart],
+    ["touchend", axm.touchend],
+  ].forEach(([event, axm]) => {
+    if (!event) { return }
+    if (!axm) { return }
+
+    if (element.hasAttribute(axm)) {
+      on(socket, options, element, element, event, axm, (msg, event) => {
+        if (event instanceof TouchEvent) {
+          const data: TouchData = {
+            ts: Array.from(event.touches).map(touchPointData),
+            cts: Array.from(event.changedTouches).map(touchPointData),
+          }
+          return { t: "touch", m: msg, d: data }
+        } else {
+          return
+        }
+      })
+    }
+  });
+
+  if (element.hasAttribute(axm.wheel)) {
+    on(socket, options, element, element, "wheel", axm.wheel, (msg, event) => {
+      if (event instanceof WheelEvent) {
+        // normalize to pixels
+        var scale = 1
+        if (event.deltaMode === WheelEvent.DOM_DELTA_LINE) {
+          scale = 16
+        } else if (event.deltaMode === WheelEvent.DOM_DELTA_PAGE) {
+          scale = window.innerHeight
+        }
+
+        const data: WheelData = {
+          dx: event.deltaX * scale,
+          dy: event.deltaY * scale,
+          dz: event.deltaZ * scale,
+          cx: event.clientX,
+          cy: event.clientY,
+          c: event.ctrlKey,
+        }
+        return { t: "wheel", m: msg, d: data }
+      } else {
+        return
+      }
+    })
+  }
+
+  if (element.hasAttribute(axm.dragstart)) {
+    on(socket, options, element, element, "dragstart", axm.dragstart, (msg, event) => {
+      if (event instanceof DragEvent) {
+        if (element.id) {
+          event.dataTransfer?.setData(dragMimeType, element.id)
+        }
+        return { t: "drag", m: msg, d: { id: element.id || null, cx: event.clientX, cy: event.clientY } }
+      } else {
+        return
+      }
+    })
+  }
+
+  if (element.hasAttribute(axm.drop)) {
+    // elements only accept drops if `dragover` is cancelled
+    element.addEventListener("dragover", (event) => event.preventDefault())
+
+    on(socket, options, element, element, "drop", axm.drop, (msg, event) => {
+      if (event instanceof DragEvent) {
+        const id = event.dataTransfer?.getData(dragMimeType) || null
+        return { t: "drag", m: msg, d: { id: id, cx: event.clientX, cy: event.clientY } }
+      } else {
+        return
+      }
+    })
+  }
+
+  [
+    [viewportEvents.enter, axm.viewportenter],
+    [viewportEvents.leave, axm.viewportleave],
+  ].forEach(([event, axm]) => {
+    if (!event) { return }
+    if (!axm) { return }
+
+    if (element.hasAttribute(axm)) {
+      viewportObserver.observe(element)
+      on(socket, options, element, element, event, axm, (msg, event) => {
+        if (event instanceof CustomEvent) {
+          const entry: IntersectionObserverEntry = event.detail
+          return { t: "intersection", m: msg, d: { ir: entry.intersectionRatio, i: entry.isIntersecting } }
+        } else {
+          return
+        }
+      })
+    }
+  });
+
   [
     ["keydown", axm.keydown],
     ["keyup", axm.keyup],
@@ -310,13 +530,6 @@ function addEventListeners(
     if (element.hasAttribute(axm)) {
       on(socket, options, element, element, event, axm, (msg, event) => {
         if (event instanceof KeyboardEvent) {
-          if (
-            element.hasAttribute("axm-key") &&
-            element?.getAttribute("axm-key")?.toLowerCase() !== event.key.toLowerCase()
-          ) {
-            return;
-          }
-
           const data: KeyData = {
             k: event.key,
             kc: event.code,
@@ -350,13 +563,6 @@ function addDocumentEventListeners(
     if (element.hasAttribute(axm)) {
       on(socket, options, element, document, event, axm, (msg, event) => {
         if (event instanceof KeyboardEvent) {
-          if (
-            element.hasAttribute("axm-key") &&
-            element?.getAttribute("axm-key")?.toLowerCase() !== event.key.toLowerCase()
-          ) {
-            return;
-          }
-
           const data: KeyData = {
             k: event.key,
             kc: event.code,
@@ -394,47 +600,254 @@ function addDocumentEventListeners(
       return { t: "scroll", m: msg, d: data }
     })
   }
+
+  if (element.hasAttribute(axm_window.resize)) {
+    on(socket, options, element, window, "resize", axm_window.resize, (msg) => {
+      return { t: "resize", m: msg, d: { w: window.innerWidth, h: window.innerHeight } }
+    })
+  }
+
+  if (element.hasAttribute(axm_window.visibilitychange)) {
+    on(socket, options, element, document, "visibilitychange", axm_window.visibilitychange, (msg) => {
+      return { t: "visibility", m: msg, d: { h: document.visibilityState === "hidden" } }
+    })
+  }
+
+  [
+    ["online", axm_window.online],
+    ["offline", axm_window.offline],
+  ].forEach(([event, axm]) => {
+    if (!event) { return }
+    if (!axm) { return }
+
+    if (element.hasAttribute(axm)) {
+      on(socket, options, element, window, event, axm, (msg) => {
+        return { t: "network", m: msg, d: { o: navigator.onLine } }
+      })
+    }
+  });
+
+  if (element.hasAttribute(axm_window.beforeunload)) {
+    on(socket, options, element, window, "beforeunload", axm_window.beforeunload, (msg) => {
+      return { t: "window_beforeunload", m: msg }
+    })
+  }
 }
 
 function on(
   socket: WebSocket,
   options: LiveViewOptions,
   element: Element,
-  listenForEventOn: Element | typeof document,
+  listenForEventOn: Element | Document | Window,
   eventName: string,
   attr: string,
   f: (msg: string | JSON, event: Event) => MessageToView | undefined,
 ) {
-  var callback: (event: Event) => void = delayOrThrottle(element, (event: Event) => {
-    if (!(event instanceof KeyboardEvent)) {
-      event.preventDefault()
-    }
-
-    const decodeMsg = msgAttr(element, attr)
+  const handler: (event: Event) => void = delayOrThrottle(element, (event: Event) => {
+    const decodeMsg = bindingMsg(element, attr)
     if (!decodeMsg) { return }
     const payload = f(decodeMsg, event)
     if (!payload) { return }
+    if ("m" in payload) {
+      const values = valueAttrs(element)
+      if (values) { payload.v = values }
+      payload.r = startLoading(element, eventName === "click" || eventName === "submit")
+    }
+    rememberSentValues(element)
     socketSend(socket, payload, options)
   })
 
-  if (document === listenForEventOn) {
+  // filter keystrokes before debouncing so ignored keys don't reset the timer, and cancel events
+  // before it as well since that only works while the event is being dispatched
+  const callback = (event: Event) => {
+    if (event instanceof KeyboardEvent && !keyMatches(element, event)) { return }
+    if (shouldPreventDefault(element, event)) {
+      event.preventDefault()
+    }
+    handler(event)
+  }
+
+  if (!(listenForEventOn instanceof Element)) {
     documentEventListeners.push({
+      target: listenForEventOn,
       event: eventName,
       callback: callback,
     })
   }
 
-  listenForEventOn.addEventListener(eventName, callback)
+  // passive listeners let the browser scroll without waiting for the listener to run
+  const passive = passiveEvents.includes(eventName) && !element.hasAttribute(preventDefaultAttr)
+  listenForEventOn.addEventListener(eventName, callback, { passive: passive })
 }
 
-function msgAttr(element: Element, attr: string): string | JSON | undefined {
-    const value = element.getAttribute(attr)
-    if (!value) { return }
-    try {
-      return JSON.parse(value)
-    } catch {
-      return value
-    }
+const preventDefaultAttr = "axm-prevent-default"
+
+// cancelling `dragstart` would prevent the drag and cancelling `beforeunload` makes the browser
+// ask whether to leave the page
+const neverPreventDefault = ["dragstart", "beforeunload"]
+
+// binding these shouldn't stop scrolling, text selection, or the context menu, so like keystrokes
+// they're only cancelled with `axm-prevent-default`
+const optInPreventDefault = [
+  "pointerdown",
+  "pointermove",
+  "pointerup",
+  "touchstart",
+  "touchend",
+  "wheel",
+  "dblclick",
+  "contextmenu",
+]
+
+const passiveEvents = ["touchstart", "touchend", "wheel"]
+
+function shouldPreventDefault(element: Element, event: Event): boolean {
+  if (neverPreventDefault.includes(event.type)) { return false }
+  if (event instanceof KeyboardEvent || optInPreventDefault.includes(event.type)) {
+    return element.hasAttribute(preventDefaultAttr)
+  }
+  return true
+}
+
+// `axm-key` holds comma separated alternatives, such as `ctrl+k,meta+k`. Literals are normalized
+// by the `html!` macro but values from blocks or `if` aren't, so they're normalized here as well
+function keyMatches(element: Element, event: KeyboardEvent): boolean {
+  const combos = element.getAttribute("axm-key")
+  if (!combos) { return true }
+  return combos.split(",").some((combo) => comboMatches(normalizeCombo(combo), event))
+}
+
+const keyAliases = new Map([
+  ["control", "ctrl"],
+  ["option", "alt"],
+  ["cmd", "meta"],
+  ["command", "meta"],
+  ["super", "meta"],
+  ["esc", "escape"],
+  ["up", "arrowup"],
+  ["down", "arrowdown"],
+  ["left", "arrowleft"],
+  ["right", "arrowright"],
+])
+
+// lowercase, without whitespace, and with aliases resolved, like the `html!` macro does
+function normalizeCombo(combo: string): string {
+  combo = combo.replace(/\s/g, "").toLowerCase()
+  // `ctrl++` binds the plus key
+  if (combo === "+") { return "plus" }
+  if (combo.endsWith("++")) { combo = `${combo.slice(0, -2)}+plus` }
+  return combo
+    .split("+")
+    .map((part) => keyAliases.get(part) || part)
+    .join("+")
+}
+
+const keyModifiers: [string, (event: KeyboardEvent) => boolean][] = [
+  ["ctrl", (event) => event.ctrlKey],
+  ["alt", (event) => event.altKey],
+  ["shift", (event) => event.shiftKey],
+  ["meta", (event) => event.metaKey],
+]
+
+function comboMatches(combo: string, event: KeyboardEvent): boolean {
+  const parts = combo.split("+")
+  const key = parts.pop()
+  if (!key) { return false }
+
+  // characters like `?` need shift on most layouts, so only check shift when asked for it
+  const shiftAgnostic = key.length === 1 && !/[a-z0-9]/.test(key) && !parts.includes("shift")
+
+  const modifiersMatch = keyModifiers.every(([name, pressed]) => {
+    // pressing a modifier on its own sets its own flag
+    if (name === key) { return true }
+    if (name === "shift" && shiftAgnostic) { return true }
+    return pressed(event) === parts.includes(name)
+  })
+  if (!modifiersMatch) { return false }
+
+  const eventKey = event.key.toLowerCase()
+  switch (key) {
+    case "space": return eventKey === " "
+    case "plus": return eventKey === "+"
+    case "ctrl": return eventKey === "control"
+  }
+  if (eventKey === key) { return true }
+
+  // match the physical key as well, so `alt+k` works on macOS where alt changes `event.key`
+  if (/^[a-z]$/.test(key)) { return event.code === `Key${key.toUpperCase()}` }
+  if (/^[0-9]$/.test(key)) { return event.code === `Digit${key}` }
+  return false
+}
+
+// decode the message bound to `attr`, after running any JS commands bound along with it
+function bindingMsg(element: Element, attr: string): string | undefined {
+  const msg = element.getAttribute(attr)
+  if (!msg) { return }
+
+  const chain = jsChainAttr(msg)
+  if (chain) {
+    chain["axm-js"].forEach((jsCommand) => handleJsCommand(jsCommand))
+    const push = chain["axm-push"]
+    // the server expects messages in the same percent encoded form they're rendered in
+    return push === undefined ? undefined : encodeURIComponent(JSON.stringify(push))
+  }
+
+  return msg
+}
+
+// bindings are rendered as percent encoded JSON so they must be decoded before checking for JS
+// commands
+function jsChainAttr(msg: string): JsChain | undefined {
+  try {
+    const decoded = JSON.parse(decodeURIComponent(msg))
+    return isJsChain(decoded) ? decoded : undefined
+  } catch {
+    return
+  }
+}
+
+// the name of the form field that triggered a `change` event
+function changedField(event: Event): string | undefined {
+  const target = event.target
+  if (
+    target instanceof HTMLInputElement ||
+    target instanceof HTMLSelectElement ||
+    target instanceof HTMLTextAreaElement
+  ) {
+    return target.name || undefined
+  }
+  return
+}
+
+const valueAttrPrefix = "axm-value-"
+
+// values bound with `axm-value-*` are percent encoded JSON. Anything else, such as values set by
+// other scripts, is sent as a string
+function valueAttrs(element: Element): EventValues["v"] {
+  var values: EventValues["v"] = undefined
+  for (const attr of Array.from(element.attributes)) {
+    if (!attr.name.startsWith(valueAttrPrefix)) { continue }
+    values = values || {}
+    values[attr.name.slice(valueAttrPrefix.length)] = decodeValue(attr.value)
+  }
+  return values
+}
+
+function decodeValue(value: string): JSON | string {
+  try {
+    return JSON.parse(decodeURIComponent(value))
+  } catch {
+    return value
+  }
+}
+
+interface JsChain {
+  "axm-js": JsCommand[],
+  "axm-push"?: string | JSON,
+}
+
+function isJsChain(msg: string | JSON): msg is JsChain {
+  return typeof msg === "object" && msg !== null && "axm-js" in msg
 }
 
 function delayOrThrottle<In extends unknown[]>(element: Element, f: Fn<In>): Fn<In> {
@@ -451,30 +864,316 @@ function delayOrThrottle<In extends unknown[]>(element: Element, f: Fn<In>): Fn<
   return f
 }
 
+const transitionAttrs = {
+  enter: "axm-transition-enter",
+  leave: "axm-transition-leave",
+  end: "axm-transition-end",
+}
+
+// elements that are currently running their leave transition, and will be removed once it's done
+const leaving = new WeakSet<Element>()
+
+function transitionEnter(socket: WebSocket, element: Element, options: LiveViewOptions) {
+  const classes = classesAttr(element, transitionAttrs.enter)
+  if (!classes) { return }
+
+  element.classList.add(...classes)
+  afterAnimations(element, () => {
+    element.classList.remove(...classes)
+    transitionEnd(socket, element, options)
+  })
+}
+
+// returns whether the element should be kept in the DOM for now
+function transitionLeave(socket: WebSocket, element: Element, options: LiveViewOptions): boolean {
+  if (leaving.has(element)) { return true }
+
+  const classes = classesAttr(element, transitionAttrs.leave)
+  if (!classes) { return false }
+
+  leaving.add(element)
+  element.classList.add(...classes)
+  afterAnimations(element, () => {
+    element.remove()
+    transitionEnd(socket, element, options)
+  })
+  return true
+}
+
+function transitionEnd(socket: WebSocket, element: Element, options: LiveViewOptions) {
+  const msg = bindingMsg(element, transitionAttrs.end)
+  if (msg === undefined) { return }
+  socketSend(socket, { t: "transition_end", m: msg }, options)
+}
+
+function classesAttr(element: Element, attr: string): string[] | undefined {
+  const classes = element.getAttribute(attr)?.split(" ").filter((klass) => klass !== "")
+  if (!classes || classes.length === 0) { return }
+  return classes
+}
+
+// call `f` once the CSS animations and transitions on `element` have finished
+function afterAnimations(element: Element, f: () => void) {
+  const style = window.getComputedStyle(element)
+  const duration = Math.max(
+    cssDurationMs(style.animationDuration) + cssDurationMs(style.animationDelay),
+    cssDurationMs(style.transitionDuration) + cssDurationMs(style.transitionDelay),
+  )
+  if (duration > 0) {
+    setTimeout(f, duration)
+  } else {
+    f()
+  }
+}
+
+// parse durations like "0.3s, 150ms" and return the longest in milliseconds
+function cssDurationMs(value: string): number {
+  return Math.max(0, ...value.split(",").map((part) => {
+    const trimmed = part.trim()
+    const number = parseFloat(trimmed)
+    if (isNaN(number)) { return 0 }
+    return trimmed.endsWith("ms") ? number : number * 1000
+  }))
+}
+
+const loadingClass = "axm-loading"
+
+interface Loading {
+  // refs of the events that are in flight for the element
+  refs: number[],
+  // the state as rendered by the server, restored once the events have been acknowledged
+  disabled?: boolean,
+  text?: string | null,
+}
+
+// elements with events in flight, and elements disabled because of them
+const loading = new Map<Element, Loading>()
+
+var nextRef = 0
+
+// put `element` in a loading state until the event has been acknowledged and return the event's ref
+function startLoading(element: Element, disable: boolean): number {
+  const ref = nextRef++
+
+  const targets = [element]
+  if (disable && element instanceof HTMLFormElement) {
+    element.querySelectorAll("button:not([type]), button[type=submit], input[type=submit]").forEach((button) => {
+      targets.push(button)
+    })
+  }
+
+  targets.forEach((target) => {
+    const existing = loading.get(target)
+    if (existing) {
+      existing.refs.push(ref)
+      return
+    }
+
+    const state: Loading = { refs: [ref] }
+    if (disable && canDisable(target)) {
+      state.disabled = target.hasAttribute("disabled")
+      if (target.hasAttribute("axm-disable-with")) {
+        state.text = elementText(target)
+      }
+    }
+    loading.set(target, state)
+    applyLoading(target, state)
+  })
+
+  return ref
+}
+
+function acknowledge(refs: number[]) {
+  loading.forEach((state, element) => {
+    state.refs = state.refs.filter((ref) => !refs.includes(ref))
+    if (state.refs.length === 0) {
+      loading.delete(element)
+      restoreLoading(element, state)
+    }
+  })
+}
+
+function clearLoading() {
+  loading.forEach((state, element) => restoreLoading(element, state))
+  loading.clear()
+}
+
+// called when morphdom is about to update an element. Whatever the server rendered becomes the
+// state to restore, and the element stays in the loading state
+function keepLoading(fromEl: Element, toEl: Element) {
+  const state = loading.get(fromEl)
+  if (!state) { return }
+
+  if (state.disabled !== undefined) {
+    state.disabled = toEl.hasAttribute("disabled")
+  }
+  if (state.text !== undefined) {
+    state.text = elementText(toEl)
+  }
+  applyLoading(toEl, state)
+}
+
+function applyLoading(element: Element, state: Loading) {
+  element.classList.add(loadingClass)
+  if (state.disabled !== undefined) {
+    element.setAttribute("disabled", "")
+  }
+  if (state.text !== undefined) {
+    setElementText(element, element.getAttribute("axm-disable-with") || "")
+  }
+}
+
+function restoreLoading(element: Element, state: Loading) {
+  element.classList.remove(loadingClass)
+  if (state.disabled === false) {
+    element.removeAttribute("disabled")
+  }
+  if (state.text !== undefined && state.text !== null) {
+    setElementText(element, state.text)
+  }
+}
+
+function canDisable(element: Element): boolean {
+  return element instanceof HTMLButtonElement ||
+    element instanceof HTMLInputElement ||
+    element instanceof HTMLSelectElement ||
+    element instanceof HTMLTextAreaElement ||
+    element instanceof HTMLFieldSetElement
+}
+
+// the text of buttons, which is the `value` for `<input type="submit">`
+function elementText(element: Element): string | null {
+  if (element instanceof HTMLInputElement) {
+    return element.getAttribute("value")
+  } else {
+    return element.textContent
+  }
+}
+
+function setElementText(element: Element, text: string) {
+  if (element instanceof HTMLInputElement) {
+    element.setAttribute("value", text)
+    element.value = text
+  } else {
+    element.textContent = text
+  }
+}
+
+// the intersection observer dispatches these events on the elements so they can be handled like
+// any other event
+const viewportEvents = {
+  enter: "axm:viewport-enter",
+  leave: "axm:viewport-leave",
+}
+
+// elements that are currently in view
+const inViewport = new WeakSet<Element>()
+
+const viewportObserver = new IntersectionObserver((entries) => {
+  entries.forEach((entry) => {
+    const element = entry.target
+    if (entry.isIntersecting && !inViewport.has(element)) {
+      inViewport.add(element)
+      element.dispatchEvent(new CustomEvent(viewportEvents.enter, { detail: entry }))
+
+    // the observer also reports elements that start out of view, which isn't a leave
+    } else if (!entry.isIntersecting && inViewport.has(element)) {
+      inViewport.delete(element)
+      element.dispatchEvent(new CustomEvent(viewportEvents.leave, { detail: entry }))
+    }
+  })
+})
+
+// stop observing removed elements, morphdom only tells us about the root of the removed tree
+function unobserveViewport(element: Element) {
+  [element, ...Array.from(element.querySelectorAll("*"))].forEach((el) => {
+    viewportObserver.unobserve(el)
+    inViewport.delete(el)
+  })
+}
+
 interface DocumentEventListener {
+  target: Document | Window,
   event: string,
   callback: (event: Event) => void,
 }
 
 var documentEventListeners: DocumentEventListener[] = []
 
-type MessageToView =
+type MessageToView = EventToView | HealthPing | Reconnect
+
+type EventToView = (
   Click
   | Form
   | Input
   | Key
   | WindowFocus
   | WindowBlur
+  | WindowBeforeunload
+  | Resize
+  | Visibility
+  | Network
+  | Intersection
+  | TransitionEnd
   | Mouse
   | Scroll
-  | HealthPing
+  | Pointer
+  | Touch
+  | Wheel
+  | Drag
+) & EventRef & EventValues
+
+// identifies the event when the server acknowledges it
+interface EventRef { r?: number }
+
+// collected from the element's `axm-value-*` attributes
+interface EventValues { v?: { [name: string]: JSON | string } }
 
 interface HealthPing { t: "h" }
 
+interface Reconnect { t: "rc" }
+
 interface Click { t: "click", m: string | JSON }
 
 interface WindowFocus { t: "window_focus", m: string | JSON }
 interface WindowBlur { t: "window_blur", m: string | JSON }
+interface WindowBeforeunload { t: "window_beforeunload", m: string | JSON }
+
+interface Resize {
+  t: "resize",
+  m: string | JSON,
+  d: {
+    w: number,
+    h: number,
+  }
+}
+
+interface Visibility {
+  t: "visibility",
+  m: string | JSON,
+  d: {
+    h: boolean,
+  }
+}
+
+interface Intersection {
+  t: "intersection",
+  m: string | JSON,
+  d: {
+    ir: number,
+    i: boolean,
+  }
+}
+
+interface Network {
+  t: "network",
+  m: string | JSON,
+  d: {
+    o: boolean,
+  }
+}
+
+interface TransitionEnd { t: "transition_end", m: string | JSON }
 
 interface Scroll {
   t: "scroll",
@@ -489,7 +1188,8 @@ interface Form {
   t: "form",
   m: string | JSON,
   d: {
-    q: string
+    q: string,
+    c?: string,
   }
 }
 
@@ -535,6 +1235,88 @@ interface MouseData {
   sy: number,
 }
 
+interface Pointer {
+  t: "pointer",
+  m: string | JSON,
+  d: PointerData,
+}
+
+interface PointerData {
+  id: number,
+  pt: string,
+  pr: boolean,
+  cx: number,
+  cy: number,
+  px: number,
+  py: number,
+  ox: number,
+  oy: number,
+  w: number,
+  h: number,
+  p: number,
+}
+
+interface Touch {
+  t: "touch",
+  m: string | JSON,
+  d: TouchData,
+}
+
+interface TouchData {
+  ts: TouchPointData[],
+  cts: TouchPointData[],
+}
+
+interface TouchPointData {
+  id: number,
+  cx: number,
+  cy: number,
+  px: number,
+  py: number,
+  sx: number,
+  sy: number,
+}
+
+function touchPointData(touch: globalThis.Touch): TouchPointData {
+  return {
+    id: touch.identifier,
+    cx: touch.clientX,
+    cy: touch.clientY,
+    px: touch.pageX,
+    py: touch.pageY,
+    sx: touch.screenX,
+    sy: touch.screenY,
+  }
+}
+
+interface Wheel {
+  t: "wheel",
+  m: string | JSON,
+  d: WheelData,
+}
+
+interface WheelData {
+  dx: number,
+  dy: number,
+  dz: number,
+  cx: number,
+  cy: number,
+  c: boolean,
+}
+
+// used to pass the id of the dragged element to the drop target
+const dragMimeType = "application/x-axm-id"
+
+interface Drag {
+  t: "drag",
+  m: string | JSON,
+  d: {
+    id: string | null,
+    cx: number,
+    cy: number,
+  }
+}
+
 type InputValue = string | string[] | boolean
 
 function inputValue(element: Element): InputValue {
@@ -616,21 +1398,34 @@ function updateDomFromState(socket: WebSocket, state: State, options: LiveViewOp
   }
 
   function patchDom(socket: WebSocket, element: Element, html: string) {
-    for (var i = 0; i < documentEventListeners.length; i++) {
-      let e = documentEventListeners[i]
-      if (!e) { continue }
-      document.removeEventListener(e.event, e.callback)
-      documentEventListeners.splice(i, 1);
-    }
+    documentEventListeners.forEach((e) => {
+      e.target.removeEventListener(e.event, e.callback)
+    })
+    documentEventListeners = []
 
     morphdom(element, html, {
       onNodeAdded: (node) => {
         if (node instanceof Element) {
           addEventListeners(socket, node, options)
+          transitionEnter(socket, node, options)
         }
         return node
       },
+      onBeforeNodeDiscarded: (node) => {
+        if (node instanceof Element) {
+          unobserveViewport(node)
+          return !transitionLeave(socket, node, options)
+        }
+        return true
+      },
       onBeforeElUpdated: (fromEl, toEl) => {
+        keepLoading(fromEl, toEl)
+
+        if (isControlled(fromEl, toEl)) {
+          sentValues.delete(fromEl)
+          return true
+        }
+
         if (fromEl instanceof HTMLInputElement && toEl instanceof HTMLInputElement) {
           if (toEl.getAttribute("type") === "radio" || toEl.getAttribute("type") === "checkbox") {
             toEl.checked = fromEl.checked;
@@ -644,6 +1439,10 @@ function updateDomFromState(socket: WebSocket, state: State, options: LiveViewOp
         }
 
         if (fromEl instanceof HTMLOptionElement && toEl instanceof HTMLOptionElement) {
+          const select = fromEl.closest("select")
+          if (select && toEl.closest("select")?.hasAttribute(controlledAttr) && !hasPendingEdits(select)) {
+            return true
+          }
           if (toEl.closest("select")?.hasAttribute("multiple")) {
             toEl.selected = fromEl.selected
           }
@@ -661,6 +1460,35 @@ function updateDomFromState(socket: WebSocket, state: State, options: LiveViewOp
     document.querySelectorAll(attrs).forEach((el) => {
       addDocumentEventListeners(socket, el, options)
     })
+
+    const form = element.querySelector(`form[${triggerActionAttr}]`)
+    if (form instanceof HTMLFormElement) {
+      triggerAction(form)
+    }
+  }
+}
+
+// replace statics ids (`f: 1`) with the actual statics, after storing any new statics
+function resolveStatics(value: any, statics: Statics, newStatics?: Statics) {
+  if (newStatics) {
+    Object.assign(statics, newStatics)
+  }
+
+  if (typeof value !== "object" || value === null) { return }
+
+  if (typeof value.f === "number") {
+    const fixed = statics[value.f]
+    if (fixed === undefined) {
+      console.error("unknown statics", value.f)
+    } else {
+      value.f = fixed
+    }
+  }
+
+  for (const [key, nested] of Object.entries(value)) {
+    if (key !== "f") {
+      resolveStatics(nested, statics)
+    }
   }
 }
 
@@ -778,6 +1606,23 @@ type JsCommandKind =
   | { t: "clear_value", selector: string }
   | { t: "set_title", title: string }
   | { t: "history_push_state", uri: string }
+  | { t: "reload" }
+  | { t: "focus", selector: string }
+  | { t: "blur", selector: string }
+  | { t: "focus_first", selector: string }
+  | { t: "set_attribute", selector: string, name: string, value: string }
+  | { t: "remove_attribute", selector: string, name: string }
+  | { t: "show", selector: string, options: VisibilityOptions }
+  | { t: "hide", selector: string, options: VisibilityOptions }
+  | { t: "scroll_into_view", selector: string }
+  | { t: "dispatch_event", selector: string, event: string, detail: unknown }
+  | { t: "announce", text: string }
+  | { t: "trigger_action", selector: string }
+
+interface VisibilityOptions {
+  display: string | null,
+  transition: { classes: string, duration_ms: number } | null,
+}
 
 function handleJsCommand(cmd: JsCommand) {
   const run = () => {
@@ -821,6 +1666,78 @@ function handleJsCommand(cmd: JsCommand) {
     } else if (cmd.kind.t === "history_push_state") {
       window.history.pushState({}, "", cmd.kind.uri);
 
+    } else if (cmd.kind.t === "reload") {
+      window.location.reload()
+
+    } else if (cmd.kind.t === "focus") {
+      const element = document.querySelector(cmd.kind.selector)
+      if (element instanceof HTMLElement) {
+        element.focus()
+      }
+
+    } else if (cmd.kind.t === "blur") {
+      const element = document.querySelector(cmd.kind.selector)
+      if (element instanceof HTMLElement) {
+        element.blur()
+      }
+
+    } else if (cmd.kind.t === "focus_first") {
+      const container = document.querySelector(cmd.kind.selector)
+      if (!container) { return }
+      const focusable = Array.from(container.querySelectorAll(focusableSelector)).find((element) => {
+        return element instanceof HTMLElement && !element.hasAttribute("disabled") && element.offsetParent !== null
+      })
+      if (focusable instanceof HTMLElement) {
+        focusable.focus()
+      }
+
+    } else if (cmd.kind.t === "set_attribute") {
+      const { selector, name, value } = cmd.kind
+      document.querySelectorAll(selector).forEach((element) => {
+        element.setAttribute(name, value)
+      })
+
+    } else if (cmd.kind.t === "remove_attribute") {
+      const { selector, name } = cmd.kind
+      document.querySelectorAll(selector).forEach((element) => {
+        element.removeAttribute(name)
+      })
+
+    } else if (cmd.kind.t === "show") {
+      const { selector, options } = cmd.kind
+      document.querySelectorAll(selector).forEach((element) => {
+        if (!(element instanceof HTMLElement)) { return }
+        element.style.display = options.display || "block"
+        transition(element, options, () => {})
+      })
+
+    } else if (cmd.kind.t === "hide") {
+      const { selector, options } = cmd.kind
+      document.querySelectorAll(selector).forEach((element) => {
+        if (!(element instanceof HTMLElement)) { return }
+        transition(element, options, () => {
+          element.style.display = "none"
+        })
+      })
+
+    } else if (cmd.kind.t === "scroll_into_view") {
+      document.querySelector(cmd.kind.selector)?.scrollIntoView({ block: "nearest" })
+
+    } else if (cmd.kind.t === "dispatch_event") {
+      const { selector, event, detail } = cmd.kind
+      document.querySelectorAll(selector).forEach((element) => {
+        element.dispatchEvent(new CustomEvent(event, { bubbles: true, detail: detail }))
+      })
+
+    } else if (cmd.kind.t === "announce") {
+      announce(cmd.kind.text)
+
+    } else if (cmd.kind.t === "trigger_action") {
+      const form = document.querySelector(cmd.kind.selector)
+      if (form instanceof HTMLFormElement) {
+        triggerAction(form)
+      }
+
     } else {
       const _: never = cmd.kind
     }
@@ -833,6 +1750,99 @@ function handleJsCommand(cmd: JsCommand) {
   }
 }
 
+const controlledAttr = "axm-controlled"
+
+type ValueElement = HTMLInputElement | HTMLTextAreaElement | HTMLSelectElement
+
+// the values of inputs when they were last sent to the server
+const sentValues = new WeakMap<Element, string>()
+
+function isValueElement(element: Element): element is ValueElement {
+  return element instanceof HTMLInputElement ||
+    element instanceof HTMLTextAreaElement ||
+    element instanceof HTMLSelectElement
+}
+
+function rememberSentValues(element: Element) {
+  const inputs = element instanceof HTMLFormElement ? Array.from(element.elements) : [element]
+  inputs.forEach((input) => {
+    if (isValueElement(input)) {
+      sentValues.set(input, input.value)
+    }
+  })
+}
+
+// whether the user has edited a focused input since its value was last sent or rendered
+function hasPendingEdits(element: ValueElement): boolean {
+  if (element !== document.activeElement) { return false }
+  const sent = sentValues.get(element)
+  if (sent !== undefined) { return element.value !== sent }
+  return !(element instanceof HTMLSelectElement) && element.value !== element.defaultValue
+}
+
+// whether the server rendered value of an `axm-controlled` input should replace the current one
+function isControlled(fromEl: Element, toEl: Element): boolean {
+  return isValueElement(fromEl) && toEl.hasAttribute(controlledAttr) && !hasPendingEdits(fromEl)
+}
+
+const triggerActionAttr = "axm-trigger-action"
+
+// forms that have been submitted. The attribute is left in place, since removing it would make
+// morphdom add it back on the next patch, so this keeps them from being submitted twice while
+// the page unloads
+const triggeredForms = new WeakSet<HTMLFormElement>()
+
+// submit `form` as a regular HTTP request. `submit()` doesn't fire the `submit` event so this
+// bypasses `axm-submit`
+function triggerAction(form: HTMLFormElement) {
+  if (triggeredForms.has(form)) { return }
+  triggeredForms.add(form)
+  form.submit()
+}
+
+const focusableSelector = [
+  "a[href]",
+  "button",
+  "input:not([type=hidden])",
+  "select",
+  "textarea",
+  "[tabindex]:not([tabindex='-1'])",
+  "[contenteditable]",
+].join(", ")
+
+function transition(element: HTMLElement, options: VisibilityOptions, done: () => void) {
+  if (!options.transition) {
+    done()
+    return
+  }
+
+  const classes = options.transition.classes.split(" ").filter((klass) => klass !== "")
+  element.classList.add(...classes)
+  setTimeout(() => {
+    element.classList.remove(...classes)
+    done()
+  }, options.transition.duration_ms)
+}
+
+function announce(text: string) {
+  var region = document.getElementById("axm-announcer")
+  if (!region) {
+    region = document.createElement("div")
+    region.id = "axm-announcer"
+    region.setAttribute("aria-live", "polite")
+    region.setAttribute("role", "status")
+    region.setAttribute(
+      "style",
+      "position: absolute; width: 1px; height: 1px; overflow: hidden; clip: rect(0 0 0 0); white-space: nowrap;",
+    )
+    document.body.appendChild(region)
+  }
+  // clear first so repeating the same text is announced again
+  region.textContent = ""
+  const target = region
+  setTimeout(() => { target.textContent = text }, 50)
+}
+
 type Fn<
   In extends unknown[],
 > = (...args: In) => void;
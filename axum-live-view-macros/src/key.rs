@@ -0,0 +1,190 @@
+//! Parsing of `axm-key` values.
+//!
+//! `axm-key` accepts comma separated alternatives, each made of modifiers and a key joined with
+//! `+`, such as `ctrl+k, meta+k`. The combos are normalized so the client only has to handle a
+//! single format: lowercase, modifiers in a fixed order, and no whitespace.
+
+const MODIFIERS: &[&str] = &["ctrl", "alt", "shift", "meta"];
+
+/// Values of `KeyboardEvent.key`, lowercased, other than function keys which are checked
+/// separately.
+const NAMED_KEYS: &[&str] = &[
+    "enter",
+    "escape",
+    "tab",
+    "backspace",
+    "delete",
+    "insert",
+    "home",
+    "end",
+    "pageup",
+    "pagedown",
+    "arrowup",
+    "arrowdown",
+    "arrowleft",
+    "arrowright",
+    "space",
+    "plus",
+    "capslock",
+    "numlock",
+    "scrolllock",
+    "contextmenu",
+    "pause",
+    "printscreen",
+    "clear",
+    "help",
+    "altgraph",
+    "fn",
+    "audiovolumeup",
+    "audiovolumedown",
+    "audiovolumemute",
+    "mediaplaypause",
+    "mediastop",
+    "mediatracknext",
+    "mediatrackprevious",
+    "browserback",
+    "browserforward",
+];
+
+/// Parse and normalize the value of an `axm-key` attribute.
+pub(crate) fn parse_combos(value: &str) -> Result<String, String> {
+    let combos = value
+        .split(',')
+        .map(|combo| parse_combo(combo.trim()))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(combos.join(","))
+}
+
+fn parse_combo(combo: &str) -> Result<String, String> {
+    if combo.is_empty() {
+        return Err("empty key combo in `axm-key`".to_owned());
+    }
+
+    // `ctrl++` binds the plus key
+    let (combo, plus) = match combo.strip_suffix("++") {
+        Some(rest) => (rest, true),
+        None if combo == "+" => ("", true),
+        None => (combo, false),
+    };
+
+    let mut parts = combo.split('+').map(str::trim).collect::<Vec<_>>();
+    if plus {
+        parts.retain(|part| !part.is_empty());
+        parts.push("plus");
+    }
+
+    let key = parts.pop().unwrap_or_default();
+    if key.is_empty() {
+        return Err(format!("missing key in `{}`", combo));
+    }
+    let key = parse_key(key)?;
+
+    let mut modifiers = Vec::new();
+    for part in parts {
+        let modifier = parse_modifier(part).ok_or_else(|| {
+            format!(
+                "unknown modifier `{}`. Expected one of ctrl, alt, shift, or meta",
+                part
+            )
+        })?;
+        if modifiers.contains(&modifier) {
+            return Err(format!("duplicate modifier `{}`", modifier));
+        }
+        modifiers.push(modifier);
+    }
+    modifiers.sort_by_key(|modifier| MODIFIERS.iter().position(|m| m == modifier));
+
+    let mut out = modifiers.join("+");
+    if !out.is_empty() {
+        out.push('+');
+    }
+    out.push_str(&key);
+    Ok(out)
+}
+
+fn parse_modifier(modifier: &str) -> Option<&'static str> {
+    match &*modifier.to_lowercase() {
+        "ctrl" | "control" => Some("ctrl"),
+        "alt" | "option" => Some("alt"),
+        "shift" => Some("shift"),
+        "meta" | "cmd" | "command" | "super" => Some("meta"),
+        _ => None,
+    }
+}
+
+fn parse_key(key: &str) -> Result<String, String> {
+    let mut chars = key.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        if c == ',' {
+            return Err("`,` separates alternatives and can't be used as a key".to_owned());
+        }
+        return Ok(c.to_lowercase().to_string());
+    }
+
+    if let Some(modifier) = parse_modifier(key) {
+        return Ok(modifier.to_owned());
+    }
+
+    let key = key.to_lowercase();
+    let key = match &*key {
+        "esc" => "escape".to_owned(),
+        "up" | "down" | "left" | "right" => format!("arrow{}", key),
+        _ => key,
+    };
+
+    if NAMED_KEYS.contains(&&*key) || is_function_key(&key) {
+        Ok(key)
+    } else {
+        Err(format!("unknown key `{}`", key))
+    }
+}
+
+/// `f1` through `f24`.
+fn is_function_key(key: &str) -> bool {
+    let n = key
+        .strip_prefix('f')
+        .filter(|n| !n.starts_with('0'))
+        .and_then(|n| n.parse::<u8>().ok());
+    matches!(n, Some(1..=24))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn valid_combos() {
+        assert_eq!(parse_combos("escape").unwrap(), "escape");
+        assert_eq!(parse_combos("Escape").unwrap(), "escape");
+        assert_eq!(parse_combos("ctrl+k").unwrap(), "ctrl+k");
+        assert_eq!(parse_combos("shift+Enter").unwrap(), "shift+enter");
+        assert_eq!(
+            parse_combos("meta+shift+ctrl+P").unwrap(),
+            "ctrl+shift+meta+p"
+        );
+        assert_eq!(parse_combos("ctrl+k, cmd+k").unwrap(), "ctrl+k,meta+k");
+        assert_eq!(parse_combos("esc").unwrap(), "escape");
+        assert_eq!(parse_combos("alt+up").unwrap(), "alt+arrowup");
+        assert_eq!(parse_combos("ctrl++").unwrap(), "ctrl+plus");
+        assert_eq!(parse_combos("+").unwrap(), "plus");
+        assert_eq!(parse_combos("?").unwrap(), "?");
+        assert_eq!(parse_combos("shift").unwrap(), "shift");
+        assert_eq!(parse_combos("CapsLock").unwrap(), "capslock");
+        assert_eq!(parse_combos("ctrl+F13").unwrap(), "ctrl+f13");
+        assert_eq!(parse_combos("f24").unwrap(), "f24");
+        assert_eq!(parse_combos("ContextMenu").unwrap(), "contextmenu");
+    }
+
+    #[test]
+    fn invalid_combos() {
+        assert!(parse_combos("").is_err());
+        assert!(parse_combos("ctrl+").is_err());
+        assert!(parse_combos("ctl+k").is_err());
+        assert!(parse_combos("ctrl+ctrl+k").is_err());
+        assert!(parse_combos("a+b").is_err());
+        assert!(parse_combos("ctrl+foo").is_err());
+        assert!(parse_combos("ctrl+k,").is_err());
+        assert!(parse_combos("f25").is_err());
+        assert!(parse_combos("f01").is_err());
+    }
+}
@@ -44,7 +44,7 @@
 #![cfg_attr(test, allow(clippy::float_cmp))]
 
 use proc_macro2::TokenStream;
-use quote::{quote, ToTokens};
+use quote::{format_ident, quote, quote_spanned, ToTokens};
 use std::fmt::Write;
 use syn::{
     parse::{Parse, ParseStream},
@@ -53,6 +53,12 @@ use syn::{
     Block, Ident, LitStr, Token,
 };
 
+use self::live_state::{ReadFields, Reads};
+
+mod key;
+mod live_form;
+mod live_state;
+
 #[proc_macro]
 #[allow(missing_docs)]
 pub fn html(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
@@ -69,6 +75,24 @@ pub fn html(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     tokens.into()
 }
 
+#[proc_macro_derive(LiveState, attributes(live_state))]
+#[allow(missing_docs)]
+pub fn derive_live_state(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    match syn::parse(input).and_then(live_state::expand) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.into_compile_error().into(),
+    }
+}
+
+#[proc_macro_derive(LiveForm, attributes(live_form))]
+#[allow(missing_docs)]
+pub fn derive_live_form(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    match syn::parse(input).and_then(live_form::expand) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.into_compile_error().into(),
+    }
+}
+
 #[derive(Debug, Clone)]
 struct Tree {
     nodes: Vec<HtmlNode>,
@@ -162,7 +186,7 @@ struct TagClose {
 impl Parse for TagNode {
     fn parse(input: ParseStream) -> syn::Result<Self> {
         input.parse::<Token![<]>()?;
-        let open = input.parse()?;
+        let open = input.parse::<Ident>()?;
 
         let mut attrs = Vec::new();
         loop {
@@ -178,6 +202,17 @@ impl Parse for TagNode {
             }
         }
 
+        for attr in &mut attrs {
+            if let Attr::Axm {
+                ident: AttrIdent::Axm(name),
+                event,
+                ..
+            } = attr
+            {
+                *event = event_kind(&open.to_string(), name);
+            }
+        }
+
         if input.parse::<Token![/]>().is_ok() {
             input.parse::<Token![>]>()?;
             return Ok(Self {
@@ -189,6 +224,10 @@ impl Parse for TagNode {
 
         input.parse::<Token![>]>()?;
 
+        if open == "form" {
+            mark_no_js_submit(&mut attrs);
+        }
+
         let next_is_close = || input.peek(Token![<]) && input.peek2(Token![/]);
         let mut inner = Vec::new();
         while !next_is_close() {
@@ -216,6 +255,52 @@ impl Parse for TagNode {
     }
 }
 
+/// Mark the `axm-submit` binding of a form without a `method` so it can be posted without
+/// JavaScript, and move it last so the opening tag closes right after it.
+fn mark_no_js_submit(attrs: &mut Vec<Attr>) {
+    let has_method = attrs.iter().any(
+        |attr| matches!(attr, Attr::Normal { ident: AttrIdent::Lit(name), .. } if name == "method"),
+    );
+    if has_method {
+        return;
+    }
+
+    let idx = attrs.iter().position(|attr| {
+        matches!(attr, Attr::Axm { ident: AttrIdent::Axm(name), .. } if name == "axm-submit")
+    });
+    if let Some(idx) = idx {
+        let mut attr = attrs.remove(idx);
+        if let Attr::Axm { no_js_submit, .. } = &mut attr {
+            *no_js_submit = true;
+        }
+        attrs.push(attr);
+    }
+}
+
+/// The kind of data the `axm-*` binding `name` on a `tag` element sends, from
+/// `axum_live_view::event_data::kind`. Constructors bound to it must fit the kind.
+fn event_kind(tag: &str, name: &str) -> &'static str {
+    let input_element = matches!(tag, "input" | "textarea" | "select");
+
+    match name.trim_start_matches("axm-") {
+        "input" | "change" | "focus" | "blur" if input_element => "Input",
+        "change" | "submit" if tag == "form" => "Form",
+        "keydown" | "keyup" | "window-keydown" | "window-keyup" => "Key",
+        "mouseenter" | "mouseover" | "mouseleave" | "mouseout" | "mousemove" | "dblclick"
+        | "contextmenu" => "Mouse",
+        "pointerdown" | "pointermove" | "pointerup" => "Pointer",
+        "touchstart" | "touchend" => "Touch",
+        "wheel" => "Wheel",
+        "dragstart" | "drop" => "Drag",
+        "scroll" => "Scroll",
+        "window-resize" => "Resize",
+        "visibility-change" => "Visibility",
+        "online" | "offline" => "Network",
+        "viewport-enter" | "viewport-leave" => "Intersection",
+        _ => "NoData",
+    }
+}
+
 #[derive(Debug, Clone)]
 enum Attr {
     Normal {
@@ -225,6 +310,10 @@ enum Attr {
     Axm {
         ident: AttrIdent,
         value: AxmAttrValue,
+        /// Whether this is the `axm-submit` of a form that can be posted without JavaScript.
+        no_js_submit: bool,
+        /// The kind of data the event sends, see [`event_kind`].
+        event: &'static str,
     },
 }
 
@@ -233,12 +322,24 @@ impl Parse for Attr {
         let ident = input.parse::<AttrIdent>()?;
 
         match ident {
-            AttrIdent::Lit(_) => {
-                let value = if input.parse::<Token![=]>().is_ok() {
+            AttrIdent::Lit(ref name) => {
+                let mut value = if input.parse::<Token![=]>().is_ok() {
                     input.parse()?
                 } else {
                     NormalAttrValue::Unit(Unit)
                 };
+                if name.starts_with("axm-value-") {
+                    value = value.lit_strs_into_blocks().map_blocks(&|block| {
+                        syn::parse_quote! {{ axum_live_view::__private::ValueAttr(#block) }}
+                    });
+                }
+                if name == "axm-key" {
+                    if let NormalAttrValue::LitStr(lit_str) = &value {
+                        let combos = key::parse_combos(&lit_str.value())
+                            .map_err(|err| syn::Error::new(lit_str.span(), err))?;
+                        value = NormalAttrValue::LitStr(LitStr::new(&combos, lit_str.span()));
+                    }
+                }
                 Ok(Self::Normal { ident, value })
             }
             AttrIdent::Axm(_) => {
@@ -246,12 +347,68 @@ impl Parse for Attr {
                 Ok(Self::Axm {
                     ident,
                     value: input.parse()?,
+                    no_js_submit: false,
+                    // set by the tag once all its attributes are parsed
+                    event: "NoData",
                 })
             }
         }
     }
 }
 
+/// `axm-*` attributes that bind events to messages.
+const AXM_BINDINGS: &[&str] = &[
+    "click",
+    "input",
+    "change",
+    "submit",
+    "focus",
+    "blur",
+    "keydown",
+    "keyup",
+    "window-keydown",
+    "window-keyup",
+    "window-focus",
+    "window-blur",
+    "window-resize",
+    "window-beforeunload",
+    "visibility-change",
+    "online",
+    "offline",
+    "mouseenter",
+    "mouseover",
+    "mouseleave",
+    "mouseout",
+    "mousemove",
+    "dblclick",
+    "contextmenu",
+    "pointerdown",
+    "pointermove",
+    "pointerup",
+    "touchstart",
+    "touchend",
+    "wheel",
+    "dragstart",
+    "drop",
+    "scroll",
+    "viewport-enter",
+    "viewport-leave",
+    "transition-end",
+];
+
+/// `axm-*` attributes with literal values that configure the bindings.
+const AXM_OPTIONS: &[&str] = &[
+    "throttle",
+    "debounce",
+    "key",
+    "prevent-default",
+    "transition-enter",
+    "transition-leave",
+    "disable-with",
+    "trigger-action",
+    "controlled",
+];
+
 #[derive(Debug, Clone)]
 enum AttrIdent {
     Lit(String),
@@ -278,17 +435,13 @@ impl Parse for AttrIdent {
             }
 
             match out.strip_prefix("axm-") {
-                Some(ident) => match ident {
-                    "click" | "input" | "change" | "submit" | "focus" | "blur" | "keydown"
-                    | "keyup" | "window-keydown" | "window-keyup" | "window-focus"
-                    | "window-blur" | "mouseenter" | "mouseover" | "mouseleave" | "mouseout"
-                    | "mousemove" | "scroll" => Ok(Self::Axm(out)),
-                    "throttle" | "debounce" | "key" => Ok(Self::Lit(out)),
-                    _ => Err(syn::Error::new(
-                        idents_span,
-                        format!("unknown `{out}` attribute"),
-                    )),
-                },
+                Some(ident) if AXM_BINDINGS.contains(&ident) => Ok(Self::Axm(out)),
+                Some(ident) if AXM_OPTIONS.contains(&ident) => Ok(Self::Lit(out)),
+                Some(ident) if ident.starts_with("value-") => Ok(Self::Lit(out)),
+                Some(_) => Err(syn::Error::new(
+                    idents_span,
+                    format!("unknown `{out}` attribute"),
+                )),
                 None => Ok(Self::Lit(out)),
             }
         }
@@ -335,6 +488,31 @@ impl Parse for NormalAttrValue {
     }
 }
 
+impl NormalAttrValue {
+    /// Turn literal strings into blocks so they're rendered like other values.
+    fn lit_strs_into_blocks(self) -> Self {
+        match self {
+            NormalAttrValue::LitStr(lit_str) => {
+                NormalAttrValue::Block(syn::parse_quote!({ #lit_str }))
+            }
+            NormalAttrValue::If(if_) => {
+                NormalAttrValue::If(if_.map(|value| Box::new(value.lit_strs_into_blocks())))
+            }
+            other => other,
+        }
+    }
+
+    fn map_blocks(self, f: &dyn Fn(Block) -> Block) -> Self {
+        match self {
+            NormalAttrValue::Block(block) => NormalAttrValue::Block(f(block)),
+            NormalAttrValue::If(if_) => {
+                NormalAttrValue::If(if_.map(|value| Box::new(value.map_blocks(f))))
+            }
+            other => other,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 struct Unit;
 
@@ -615,11 +793,15 @@ where
 
         out.extend(quote! {
             {
-                use axum_live_view::__private::DynamicFragmentVecExt;
+                use axum_live_view::__private::{
+                    CtorBindingSpec as _, DynamicFragmentVecExt, JsBindingSpec as _,
+                    MsgBindingSpec as _,
+                };
+                static __FIXED: &[&str] = &[#(#parts),*];
                 #inside_braces
                 axum_live_view::__private::HtmlBuilder {
                     dynamic: __dynamic,
-                    fixed: &[#(#parts),*],
+                    fixed: __FIXED,
                 }.into_html()
             }
         });
@@ -699,10 +881,10 @@ impl NodeToTokens for Attr {
                     ident.node_to_tokens(fixed, out);
                     fixed.append("=");
                     fixed.start_new_part();
-                    out.extend(quote! {
+                    out.extend(Reads::of(block).reuse_unchanged(quote! {
                         #[allow(unused_braces)]
                         __dynamic.push_fragment(format!("{}", #block));
-                    });
+                    }));
                 }
                 NormalAttrValue::If(if_) => {
                     let if_ = if_.clone().map(|attr_value| Self::Normal {
@@ -717,21 +899,40 @@ impl NodeToTokens for Attr {
                 }
                 NormalAttrValue::None => {}
             },
-            Attr::Axm { ident, value } => match value {
+            Attr::Axm {
+                ident,
+                value,
+                no_js_submit,
+                event,
+            } => match value {
                 AxmAttrValue::Block(block) => {
                     fixed.append(" ");
                     ident.node_to_tokens(fixed, out);
                     fixed.append("=");
                     fixed.start_new_part();
-                    out.extend(quote! {
+                    let push = if *no_js_submit {
+                        quote! { push_submit }
+                    } else {
+                        quote! { push_message }
+                    };
+                    let event = format_ident!("{}", event);
+                    let spec = quote_spanned! {block.span()=>
+                        (&&axum_live_view::__private::BindingSpec::<
+                            _,
+                            axum_live_view::event_data::kind::#event,
+                        >::new(#block)).binding_spec()
+                    };
+                    out.extend(Reads::of(block).reuse_unchanged(quote! {
                         #[allow(unused_braces)]
-                        __dynamic.push_message(#block);
-                    });
+                        __dynamic.#push(#spec);
+                    }));
                 }
                 AxmAttrValue::If(if_) => {
                     let if_ = if_.clone().map(|attr_value| Self::Axm {
                         ident: ident.clone(),
                         value: *attr_value,
+                        no_js_submit: *no_js_submit,
+                        event,
                     });
                     if_.node_to_tokens(fixed, out);
                 }
@@ -759,16 +960,16 @@ impl NodeToTokens for Block {
     fn node_to_tokens(&self, fixed: &mut FixedParts, out: &mut TokenStream) {
         fixed.start_new_part();
 
-        out.extend(quote! {
+        out.extend(Reads::of(self).reuse_unchanged(quote! {
             #[allow(unused_braces)]
             __dynamic.push_fragment(#self);
-        });
+        }));
     }
 }
 
 impl<T> NodeToTokens for If<T>
 where
-    T: NodeToTokens,
+    T: NodeToTokens + ReadFields,
 {
     fn node_to_tokens(&self, fixed: &mut FixedParts, out: &mut TokenStream) {
         let Self {
@@ -781,38 +982,40 @@ where
 
         let then_tree = ToTokensViaNodeToTokens(then_tree);
 
-        if let Some(else_tree) = else_tree {
+        let push = if let Some(else_tree) = else_tree {
             match else_tree {
                 ElseBranch::If(else_if) => {
                     let else_if = ToTokensViaNodeToTokens(else_if);
-                    out.extend(quote! {
+                    quote! {
                         if #cond {
                             __dynamic.push_fragment(#then_tree);
                         } else {
                             __dynamic.push_fragment(#else_if);
                         }
-                    });
+                    }
                 }
                 ElseBranch::Else(else_) => {
                     let else_ = ToTokensViaNodeToTokens(else_);
-                    out.extend(quote! {
+                    quote! {
                         if #cond {
                             __dynamic.push_fragment(#then_tree);
                         } else {
                             __dynamic.push_fragment(#else_);
                         }
-                    });
+                    }
                 }
-            };
+            }
         } else {
-            out.extend(quote! {
+            quote! {
                 if #cond {
                     __dynamic.push_fragment(#then_tree);
                 } else {
                     __dynamic.push_fragment("");
                 }
-            });
-        }
+            }
+        };
+
+        out.extend(Reads::of(self).reuse_unchanged(push));
     }
 }
 
@@ -828,21 +1031,21 @@ impl NodeToTokens for For {
         tree.node_to_tokens(&mut fixed, &mut inside);
         let FixedParts { parts } = fixed;
 
-        out.extend(quote! {
-            let mut __dynamic_loop_parts = Vec::new();
-            for #pat in #expr {
-                let __parts = {
-                    let mut __dynamic = std::vec::Vec::<axum_live_view::__private::DynamicFragment<_>>::new();
-                    #inside
-                    __dynamic
-                };
-                __dynamic_loop_parts.push(__parts);
+        out.extend(Reads::of(self).reuse_unchanged(quote! {
+            {
+                static __FIXED: &[&str] = &[#(#parts),*];
+                let mut __dynamic_loop_parts = Vec::new();
+                for #pat in #expr {
+                    let __parts = {
+                        let mut __dynamic = std::vec::Vec::<axum_live_view::__private::DynamicFragment<_>>::new();
+                        #inside
+                        __dynamic
+                    };
+                    __dynamic_loop_parts.push(__parts);
+                }
+                __dynamic.push_fragments(__FIXED, __dynamic_loop_parts);
             }
-            __dynamic.push_fragments(
-                &[#(#parts),*],
-                __dynamic_loop_parts,
-            );
-        });
+        }));
     }
 }
 
@@ -862,11 +1065,11 @@ impl NodeToTokens for Match {
             })
             .collect::<TokenStream>();
 
-        out.extend(quote! {
+        out.extend(Reads::of(self).reuse_unchanged(quote! {
             match #expr {
                 #arms
             }
-        })
+        }))
     }
 }
 
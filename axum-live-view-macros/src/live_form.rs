@@ -0,0 +1,442 @@
+//! `#[derive(LiveForm)]`.
+//!
+//! Input names have to match what serde deserializes so the relevant `#[serde(...)]` attributes
+//! are parsed as well.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{parse::ParseStream, LitStr};
+
+pub(crate) fn expand(input: syn::DeriveInput) -> syn::Result<TokenStream> {
+    let rename_all = serde_rename_all(&input.attrs)?;
+
+    match &input.data {
+        syn::Data::Struct(data) => expand_struct(&input, &data.fields, rename_all),
+        syn::Data::Enum(data) => expand_enum(&input, data, rename_all),
+        syn::Data::Union(_) => Err(syn::Error::new_spanned(
+            &input.ident,
+            "`LiveForm` can only be derived for structs and enums",
+        )),
+    }
+}
+
+fn expand_struct(
+    input: &syn::DeriveInput,
+    fields: &syn::Fields,
+    rename_all: Option<RenameRule>,
+) -> syn::Result<TokenStream> {
+    let fields = match fields {
+        syn::Fields::Named(fields) => &fields.named,
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "`LiveForm` can only be derived for structs with named fields",
+            ))
+        }
+    };
+
+    let mut where_clause = input
+        .generics
+        .where_clause
+        .clone()
+        .unwrap_or_else(|| syn::parse_quote!(where));
+    let mut labels = Vec::new();
+
+    for field in fields {
+        let serde = SerdeAttrs::parse(&field.attrs)?;
+        if serde.skip {
+            continue;
+        }
+
+        let ident = field.ident.as_ref().expect("named field");
+        let field_name = ident.to_string();
+        let field_name = field_name.trim_start_matches("r#");
+        let name = match serde.rename {
+            Some(rename) => rename,
+            None => rename_all.map_or_else(
+                || field_name.to_owned(),
+                |rule| rule.apply_to_field(field_name),
+            ),
+        };
+        let label = field_label(&field.attrs)?.unwrap_or_else(|| LitStr::new(&name, ident.span()));
+
+        let ty = &field.ty;
+        where_clause
+            .predicates
+            .push(syn::parse_quote!(#ty: axum_live_view::form::FormField));
+
+        labels.push(quote! {
+            <label>
+                #label
+                { <#ty as axum_live_view::form::FormField>::render_field(changeset, #name) }
+            </label>
+        });
+    }
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, _) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics axum_live_view::form::LiveForm for #ident #ty_generics
+        #where_clause
+        {
+            fn render_fields<M>(
+                changeset: &axum_live_view::form::Changeset<Self>,
+            ) -> axum_live_view::Html<M> {
+                axum_live_view::html! {
+                    #(#labels)*
+                }
+            }
+        }
+    })
+}
+
+fn expand_enum(
+    input: &syn::DeriveInput,
+    data: &syn::DataEnum,
+    rename_all: Option<RenameRule>,
+) -> syn::Result<TokenStream> {
+    let mut options = Vec::new();
+
+    for variant in &data.variants {
+        if !matches!(variant.fields, syn::Fields::Unit) {
+            return Err(syn::Error::new_spanned(
+                variant,
+                "`LiveForm` can only be derived for enums without fields",
+            ));
+        }
+
+        let serde = SerdeAttrs::parse(&variant.attrs)?;
+        if serde.skip {
+            continue;
+        }
+
+        let variant_name = variant.ident.to_string();
+        let name = match serde.rename {
+            Some(rename) => rename,
+            None => rename_all.map_or_else(
+                || variant_name.clone(),
+                |rule| rule.apply_to_variant(&variant_name),
+            ),
+        };
+        options.push(name);
+    }
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics axum_live_view::form::FormOptions for #ident #ty_generics
+        #where_clause
+        {
+            fn options() -> &'static [&'static str] {
+                &[#(#options),*]
+            }
+        }
+
+        impl #impl_generics axum_live_view::form::FormField for #ident #ty_generics
+        #where_clause
+        {
+            fn render_field<T, M>(
+                changeset: &axum_live_view::form::Changeset<T>,
+                name: &str,
+            ) -> axum_live_view::Html<M> {
+                axum_live_view::form::select(
+                    changeset,
+                    name,
+                    axum_live_view::form::options::<Self>(),
+                )
+            }
+
+            fn render_optional_field<T, M>(
+                changeset: &axum_live_view::form::Changeset<T>,
+                name: &str,
+            ) -> axum_live_view::Html<M> {
+                axum_live_view::form::select(
+                    changeset,
+                    name,
+                    std::iter::once(("", "")).chain(axum_live_view::form::options::<Self>()),
+                )
+            }
+        }
+    })
+}
+
+fn field_label(attrs: &[syn::Attribute]) -> syn::Result<Option<LitStr>> {
+    let mut label = None;
+
+    for attr in attrs {
+        if !attr.path.is_ident("live_form") {
+            continue;
+        }
+
+        attr.parse_args_with(|input: ParseStream| {
+            mod kw {
+                syn::custom_keyword!(label);
+            }
+
+            input.parse::<kw::label>()?;
+            input.parse::<syn::Token![=]>()?;
+            label = Some(input.parse::<LitStr>()?);
+            Ok(())
+        })?;
+    }
+
+    Ok(label)
+}
+
+/// The `#[serde(...)]` attributes of a field or variant that affect its name.
+#[derive(Default)]
+struct SerdeAttrs {
+    rename: Option<String>,
+    skip: bool,
+}
+
+impl SerdeAttrs {
+    fn parse(attrs: &[syn::Attribute]) -> syn::Result<Self> {
+        let mut out = Self::default();
+
+        for meta in serde_metas(attrs)? {
+            match meta {
+                syn::NestedMeta::Meta(syn::Meta::NameValue(meta))
+                    if meta.path.is_ident("rename") =>
+                {
+                    out.rename = Some(lit_str(&meta.lit)?);
+                }
+                // `rename(serialize = "...", deserialize = "...")`
+                syn::NestedMeta::Meta(syn::Meta::List(list)) if list.path.is_ident("rename") => {
+                    for nested in list.nested {
+                        if let syn::NestedMeta::Meta(syn::Meta::NameValue(meta)) = nested {
+                            if meta.path.is_ident("deserialize") {
+                                out.rename = Some(lit_str(&meta.lit)?);
+                            }
+                        }
+                    }
+                }
+                syn::NestedMeta::Meta(syn::Meta::Path(path))
+                    if path.is_ident("skip") || path.is_ident("skip_deserializing") =>
+                {
+                    out.skip = true;
+                }
+                _ => {}
+            }
+        }
+
+        Ok(out)
+    }
+}
+
+fn serde_rename_all(attrs: &[syn::Attribute]) -> syn::Result<Option<RenameRule>> {
+    let mut rule = None;
+
+    for meta in serde_metas(attrs)? {
+        let lit = match meta {
+            syn::NestedMeta::Meta(syn::Meta::NameValue(meta))
+                if meta.path.is_ident("rename_all") =>
+            {
+                meta.lit
+            }
+            _ => continue,
+        };
+
+        let value = lit_str(&lit)?;
+        rule = Some(RenameRule::parse(&value).ok_or_else(|| {
+            syn::Error::new(lit.span(), format!("unknown rename rule `{}`", value))
+        })?);
+    }
+
+    Ok(rule)
+}
+
+fn serde_metas(attrs: &[syn::Attribute]) -> syn::Result<Vec<syn::NestedMeta>> {
+    let mut metas = Vec::new();
+
+    for attr in attrs {
+        if !attr.path.is_ident("serde") {
+            continue;
+        }
+
+        if let syn::Meta::List(list) = attr.parse_meta()? {
+            metas.extend(list.nested);
+        }
+    }
+
+    Ok(metas)
+}
+
+fn lit_str(lit: &syn::Lit) -> syn::Result<String> {
+    match lit {
+        syn::Lit::Str(lit) => Ok(lit.value()),
+        _ => Err(syn::Error::new_spanned(lit, "expected a string literal")),
+    }
+}
+
+/// The rules supported by `#[serde(rename_all = "...")]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RenameRule {
+    Lower,
+    Upper,
+    Pascal,
+    Camel,
+    Snake,
+    ScreamingSnake,
+    Kebab,
+    ScreamingKebab,
+}
+
+impl RenameRule {
+    fn parse(rule: &str) -> Option<Self> {
+        match rule {
+            "lowercase" => Some(Self::Lower),
+            "UPPERCASE" => Some(Self::Upper),
+            "PascalCase" => Some(Self::Pascal),
+            "camelCase" => Some(Self::Camel),
+            "snake_case" => Some(Self::Snake),
+            "SCREAMING_SNAKE_CASE" => Some(Self::ScreamingSnake),
+            "kebab-case" => Some(Self::Kebab),
+            "SCREAMING-KEBAB-CASE" => Some(Self::ScreamingKebab),
+            _ => None,
+        }
+    }
+
+    /// Apply the rule to a `snake_case` field name.
+    fn apply_to_field(self, field: &str) -> String {
+        match self {
+            Self::Lower | Self::Snake => field.to_owned(),
+            Self::Upper | Self::ScreamingSnake => field.to_ascii_uppercase(),
+            Self::Pascal => {
+                let mut pascal = String::new();
+                let mut capitalize = true;
+                for c in field.chars() {
+                    if c == '_' {
+                        capitalize = true;
+                    } else if capitalize {
+                        pascal.push(c.to_ascii_uppercase());
+                        capitalize = false;
+                    } else {
+                        pascal.push(c);
+                    }
+                }
+                pascal
+            }
+            Self::Camel => lowercase_first(&Self::Pascal.apply_to_field(field)),
+            Self::Kebab => field.replace('_', "-"),
+            Self::ScreamingKebab => Self::ScreamingSnake.apply_to_field(field).replace('_', "-"),
+        }
+    }
+
+    /// Apply the rule to a `PascalCase` variant name.
+    fn apply_to_variant(self, variant: &str) -> String {
+        match self {
+            Self::Pascal => variant.to_owned(),
+            Self::Lower => variant.to_ascii_lowercase(),
+            Self::Upper => variant.to_ascii_uppercase(),
+            Self::Camel => lowercase_first(variant),
+            Self::Snake => {
+                let mut snake = String::new();
+                for (idx, c) in variant.char_indices() {
+                    if idx > 0 && c.is_uppercase() {
+                        snake.push('_');
+                    }
+                    snake.push(c.to_ascii_lowercase());
+                }
+                snake
+            }
+            Self::ScreamingSnake => Self::Snake.apply_to_variant(variant).to_ascii_uppercase(),
+            Self::Kebab => Self::Snake.apply_to_variant(variant).replace('_', "-"),
+            Self::ScreamingKebab => Self::ScreamingSnake
+                .apply_to_variant(variant)
+                .replace('_', "-"),
+        }
+    }
+}
+
+fn lowercase_first(s: &str) -> String {
+    let mut chars = s.chars();
+    match chars.next() {
+        Some(first) => first.to_lowercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rename_fields() {
+        let field = "first_name";
+        assert_eq!(RenameRule::Lower.apply_to_field(field), "first_name");
+        assert_eq!(RenameRule::Upper.apply_to_field(field), "FIRST_NAME");
+        assert_eq!(RenameRule::Pascal.apply_to_field(field), "FirstName");
+        assert_eq!(RenameRule::Camel.apply_to_field(field), "firstName");
+        assert_eq!(RenameRule::Snake.apply_to_field(field), "first_name");
+        assert_eq!(
+            RenameRule::ScreamingSnake.apply_to_field(field),
+            "FIRST_NAME"
+        );
+        assert_eq!(RenameRule::Kebab.apply_to_field(field), "first-name");
+        assert_eq!(
+            RenameRule::ScreamingKebab.apply_to_field(field),
+            "FIRST-NAME"
+        );
+    }
+
+    #[test]
+    fn rename_variants() {
+        let variant = "VeryTall";
+        assert_eq!(RenameRule::Lower.apply_to_variant(variant), "verytall");
+        assert_eq!(RenameRule::Upper.apply_to_variant(variant), "VERYTALL");
+        assert_eq!(RenameRule::Pascal.apply_to_variant(variant), "VeryTall");
+        assert_eq!(RenameRule::Camel.apply_to_variant(variant), "veryTall");
+        assert_eq!(RenameRule::Snake.apply_to_variant(variant), "very_tall");
+        assert_eq!(
+            RenameRule::ScreamingSnake.apply_to_variant(variant),
+            "VERY_TALL"
+        );
+        assert_eq!(RenameRule::Kebab.apply_to_variant(variant), "very-tall");
+        assert_eq!(
+            RenameRule::ScreamingKebab.apply_to_variant(variant),
+            "VERY-TALL"
+        );
+    }
+
+    #[test]
+    fn serde_names() {
+        let input: syn::DeriveInput = syn::parse_quote! {
+            #[serde(rename_all = "camelCase")]
+            struct Form {
+                first_name: String,
+                #[serde(rename = "surname")]
+                last_name: String,
+                #[serde(rename(serialize = "a", deserialize = "b"))]
+                both: String,
+                #[serde(skip)]
+                skipped: String,
+            }
+        };
+        let fields = match &input.data {
+            syn::Data::Struct(data) => &data.fields,
+            _ => unreachable!(),
+        };
+        let attrs = fields
+            .iter()
+            .map(|field| SerdeAttrs::parse(&field.attrs).unwrap())
+            .map(|attrs| (attrs.rename, attrs.skip))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            serde_rename_all(&input.attrs).unwrap(),
+            Some(RenameRule::Camel)
+        );
+        assert_eq!(
+            attrs,
+            [
+                (None, false),
+                (Some("surname".to_owned()), false),
+                (Some("b".to_owned()), false),
+                (None, true),
+            ]
+        );
+    }
+}
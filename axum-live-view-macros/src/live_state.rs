@@ -0,0 +1,597 @@
+//! `#[derive(LiveState)]` and the analysis `html!` uses to find the fields each dynamic part of a
+//! template reads.
+//!
+//! The analysis is purely syntactic and errs on the side of caution. A dynamic part is only
+//! tracked if the only variables it uses are fields of `self`. Anything else, such as local
+//! variables, methods on `self`, or calls to functions that might return something different each
+//! time, makes the part untracked and it'll be rendered every time.
+
+use crate::{
+    Attr, AxmAttrValue, ElseBranch, For, HtmlNode, If, Match, NormalAttrValue, TagClose, TagNode,
+    Tree,
+};
+use proc_macro2::{Delimiter, Spacing, TokenStream, TokenTree};
+use quote::{quote, ToTokens};
+use std::collections::BTreeSet;
+use syn::parse::ParseStream;
+
+pub(crate) fn expand(input: syn::DeriveInput) -> syn::Result<TokenStream> {
+    let fields = match input.data {
+        syn::Data::Struct(data) => data.fields,
+        _ => {
+            return Err(syn::Error::new_spanned(
+                input.ident,
+                "`LiveState` can only be derived for structs",
+            ))
+        }
+    };
+
+    let mut where_clause = input
+        .generics
+        .where_clause
+        .clone()
+        .unwrap_or_else(|| syn::parse_quote!(where));
+    let mut hash_fields = Vec::new();
+
+    for (idx, field) in fields.iter().enumerate() {
+        let (name, member) = match &field.ident {
+            Some(ident) => (ident.to_string(), quote! { #ident }),
+            None => {
+                let index = syn::Index::from(idx);
+                (idx.to_string(), quote! { #index })
+            }
+        };
+
+        match field_mode(field)? {
+            FieldMode::Tracked => {
+                let ty = &field.ty;
+                where_clause
+                    .predicates
+                    .push(syn::parse_quote!(#ty: std::hash::Hash));
+                hash_fields.push(quote! {
+                    fingerprint.field(#name, &self.#member);
+                });
+            }
+            FieldMode::Untracked => hash_fields.push(quote! {
+                fingerprint.untracked_field(#name);
+            }),
+            FieldMode::Skip => {}
+        }
+    }
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, _) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics axum_live_view::live_view::LiveState for #ident #ty_generics
+        #where_clause
+        {
+            fn fingerprint_fields(&self) -> axum_live_view::live_view::Fingerprint {
+                let mut fingerprint = axum_live_view::live_view::Fingerprint::default();
+                #(#hash_fields)*
+                fingerprint
+            }
+        }
+    })
+}
+
+/// How a field takes part in change tracking, set with `#[live_state(...)]`.
+enum FieldMode {
+    Tracked,
+    /// `#[live_state(skip)]`, changes are ignored.
+    Skip,
+    /// `#[live_state(untracked)]`, the field counts as changed after every update.
+    Untracked,
+}
+
+fn field_mode(field: &syn::Field) -> syn::Result<FieldMode> {
+    let mut mode = FieldMode::Tracked;
+
+    for attr in &field.attrs {
+        if !attr.path.is_ident("live_state") {
+            continue;
+        }
+
+        attr.parse_args_with(|input: ParseStream| {
+            mod kw {
+                syn::custom_keyword!(skip);
+                syn::custom_keyword!(untracked);
+            }
+
+            let lookahead = input.lookahead1();
+            if lookahead.peek(kw::skip) {
+                input.parse::<kw::skip>()?;
+                mode = FieldMode::Skip;
+            } else if lookahead.peek(kw::untracked) {
+                input.parse::<kw::untracked>()?;
+                mode = FieldMode::Untracked;
+            } else {
+                return Err(lookahead.error());
+            }
+            Ok(())
+        })?;
+    }
+
+    Ok(mode)
+}
+
+/// The fields of `self` read by a dynamic part of a template.
+#[derive(Debug, Default)]
+pub(crate) struct Reads {
+    fields: BTreeSet<String>,
+    untracked: bool,
+}
+
+impl Reads {
+    pub(crate) fn of<T>(node: &T) -> Self
+    where
+        T: ReadFields + ?Sized,
+    {
+        let mut reads = Self::default();
+        node.read_fields(&mut reads, &[]);
+        reads
+    }
+
+    /// Wrap the code that pushes a dynamic part such that the part from the previous render is
+    /// reused if none of the fields it reads have changed.
+    pub(crate) fn reuse_unchanged(self, push: TokenStream) -> TokenStream {
+        if self.untracked || self.fields.is_empty() {
+            return push;
+        }
+
+        let fields = self.fields.iter();
+        // fields are only tracked for the type whose template is rendering. `Self` is always in
+        // scope since the part reads `self`
+        quote! {
+            if !__dynamic.reuse_fragment(
+                __FIXED,
+                std::any::type_name::<Self>(),
+                &[#(#fields),*],
+            ) {
+                #push
+            }
+        }
+    }
+
+    fn tokens(&mut self, tokens: TokenStream, bound: &[String]) {
+        let tokens = tokens.into_iter().collect::<Vec<_>>();
+
+        for (idx, token) in tokens.iter().enumerate() {
+            let prev = idx.checked_sub(1).and_then(|idx| tokens.get(idx));
+            let next = tokens.get(idx + 1);
+
+            match token {
+                TokenTree::Group(group) => self.tokens(group.stream(), bound),
+                TokenTree::Literal(lit) => {
+                    // `format!("{foo}")` captures `foo` without it appearing as an identifier
+                    if captures_variable(&lit.to_string()) {
+                        self.untracked = true;
+                    }
+                }
+                TokenTree::Punct(_) => {}
+                TokenTree::Ident(ident) => {
+                    let name = ident.to_string();
+
+                    let prev2 = idx.checked_sub(2).and_then(|idx| tokens.get(idx));
+                    let is_method = is_punct(prev, '.') && !is_punct(prev2, '.');
+
+                    // functions such as `Local::now()` or `rand::random()` aren't pure so the part
+                    // can't be reused. Methods only see what they're called on and camel case
+                    // names are tuple structs and variants
+                    if !is_method
+                        && is_call(&tokens[idx + 1..])
+                        && !KEYWORDS.contains(&&*name)
+                        && !is_camel_case(&name)
+                    {
+                        self.untracked = true;
+                        continue;
+                    }
+
+                    // macros can expand to anything, including reads of fields or global state
+                    if is_macro(next, tokens.get(idx + 2)) {
+                        self.untracked = true;
+                        continue;
+                    }
+
+                    if is_method || is_punct(prev, '\'') {
+                        // field access, method call, or lifetime
+                        continue;
+                    }
+
+                    if is_punct(prev, ':') && is_punct(prev2, ':') {
+                        // the last segment of a path must be a variant or struct. Anything else
+                        // could be a static, or a function that's called later
+                        if !is_punct(next, ':') && !is_camel_case(&name) {
+                            self.untracked = true;
+                        }
+                        continue;
+                    }
+
+                    if name == "self" {
+                        match (next, tokens.get(idx + 2), tokens.get(idx + 3)) {
+                            (
+                                Some(TokenTree::Punct(dot)),
+                                Some(field @ (TokenTree::Ident(_) | TokenTree::Literal(_))),
+                                after,
+                            ) if dot.as_char() == '.'
+                                && dot.spacing() == Spacing::Alone
+                                && !is_group(after, Delimiter::Parenthesis) =>
+                            {
+                                let field = field.to_string();
+                                // `self.0.1` is lexed as `self` `.` `0.1`
+                                let field = field.split('.').next().unwrap_or_default();
+                                self.fields.insert(field.to_owned());
+                            }
+                            // methods can read any field and `self` itself can be passed anywhere
+                            _ => self.untracked = true,
+                        }
+                        continue;
+                    }
+
+                    // uppercase names that aren't camel case, such as `MAX`, are consts or
+                    // statics which might not be pure
+                    let is_allowed = KEYWORDS.contains(&&*name)
+                        || is_camel_case(&name)
+                        // paths
+                        || is_punct(next, ':')
+                        || bound.contains(&name);
+
+                    if !is_allowed {
+                        self.untracked = true;
+                    }
+                }
+            }
+        }
+    }
+}
+
+const KEYWORDS: &[&str] = &[
+    "as", "else", "false", "if", "in", "match", "move", "mut", "ref", "true",
+];
+
+fn is_punct(token: Option<&TokenTree>, c: char) -> bool {
+    matches!(token, Some(TokenTree::Punct(punct)) if punct.as_char() == c)
+}
+
+fn is_group(token: Option<&TokenTree>, delimiter: Delimiter) -> bool {
+    matches!(token, Some(TokenTree::Group(group)) if group.delimiter() == delimiter)
+}
+
+/// Whether an identifier followed by `bang` and `args` is a macro invocation, rather than for
+/// example the left side of `!=`.
+fn is_macro(bang: Option<&TokenTree>, args: Option<&TokenTree>) -> bool {
+    let is_bang = matches!(
+        bang,
+        Some(TokenTree::Punct(punct)) if punct.as_char() == '!' && punct.spacing() == Spacing::Alone
+    );
+    is_bang && matches!(args, Some(TokenTree::Group(_)))
+}
+
+/// Whether `name` looks like a type, struct, or variant, such as `Some` or `Self`, rather than a
+/// const or static such as `MAX`.
+fn is_camel_case(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_uppercase()) && name.contains(|c: char| c.is_lowercase())
+}
+
+/// Whether `tokens` are the arguments of a call, possibly with a turbofish such as
+/// `::<u32>()`.
+fn is_call(tokens: &[TokenTree]) -> bool {
+    let mut tokens = tokens.iter();
+    match tokens.next() {
+        Some(TokenTree::Group(group)) => return group.delimiter() == Delimiter::Parenthesis,
+        Some(TokenTree::Punct(punct)) if punct.as_char() == ':' => {}
+        _ => return false,
+    }
+    if !is_punct(tokens.next(), ':') || !is_punct(tokens.next(), '<') {
+        return false;
+    }
+
+    let mut depth = 1;
+    for token in tokens.by_ref() {
+        match token {
+            TokenTree::Punct(punct) if punct.as_char() == '<' => depth += 1,
+            TokenTree::Punct(punct) if punct.as_char() == '>' => {
+                depth -= 1;
+                if depth == 0 {
+                    break;
+                }
+            }
+            _ => {}
+        }
+    }
+    is_group(tokens.next(), Delimiter::Parenthesis)
+}
+
+fn captures_variable(lit: &str) -> bool {
+    lit.starts_with(['"', 'r'])
+        && lit
+            .split('{')
+            .skip(1)
+            .any(|part| part.starts_with(|c: char| c == '_' || c.is_alphabetic()))
+}
+
+fn bindings(pat: &syn::Pat, out: &mut Vec<String>) {
+    match pat {
+        syn::Pat::Ident(pat) => {
+            out.push(pat.ident.to_string());
+            if let Some((_, subpat)) = &pat.subpat {
+                bindings(subpat, out);
+            }
+        }
+        syn::Pat::Box(pat) => bindings(&pat.pat, out),
+        syn::Pat::Or(pat) => pat.cases.iter().for_each(|pat| bindings(pat, out)),
+        syn::Pat::Reference(pat) => bindings(&pat.pat, out),
+        syn::Pat::Slice(pat) => pat.elems.iter().for_each(|pat| bindings(pat, out)),
+        syn::Pat::Struct(pat) => pat
+            .fields
+            .iter()
+            .for_each(|field| bindings(&field.pat, out)),
+        syn::Pat::Tuple(pat) => pat.elems.iter().for_each(|pat| bindings(pat, out)),
+        syn::Pat::TupleStruct(pat) => pat.pat.elems.iter().for_each(|pat| bindings(pat, out)),
+        syn::Pat::Type(pat) => bindings(&pat.pat, out),
+        _ => {}
+    }
+}
+
+fn with_bindings(bound: &[String], pat: &syn::Pat) -> Vec<String> {
+    let mut bound = bound.to_vec();
+    bindings(pat, &mut bound);
+    bound
+}
+
+pub(crate) trait ReadFields {
+    fn read_fields(&self, reads: &mut Reads, bound: &[String]);
+}
+
+impl<T> ReadFields for Box<T>
+where
+    T: ReadFields,
+{
+    fn read_fields(&self, reads: &mut Reads, bound: &[String]) {
+        T::read_fields(self, reads, bound)
+    }
+}
+
+impl<T> ReadFields for [T]
+where
+    T: ReadFields,
+{
+    fn read_fields(&self, reads: &mut Reads, bound: &[String]) {
+        for node in self {
+            node.read_fields(reads, bound);
+        }
+    }
+}
+
+impl ReadFields for syn::Expr {
+    fn read_fields(&self, reads: &mut Reads, bound: &[String]) {
+        reads.tokens(self.to_token_stream(), bound)
+    }
+}
+
+impl ReadFields for syn::Block {
+    fn read_fields(&self, reads: &mut Reads, bound: &[String]) {
+        reads.tokens(self.to_token_stream(), bound)
+    }
+}
+
+impl ReadFields for Tree {
+    fn read_fields(&self, reads: &mut Reads, bound: &[String]) {
+        self.nodes.read_fields(reads, bound)
+    }
+}
+
+impl ReadFields for HtmlNode {
+    fn read_fields(&self, reads: &mut Reads, bound: &[String]) {
+        match self {
+            HtmlNode::Doctype(_) | HtmlNode::LitStr(_) => {}
+            HtmlNode::TagNode(inner) => inner.read_fields(reads, bound),
+            HtmlNode::Block(inner) => inner.read_fields(reads, bound),
+            HtmlNode::If(inner) => inner.read_fields(reads, bound),
+            HtmlNode::For(inner) => inner.read_fields(reads, bound),
+            HtmlNode::Match(inner) => inner.read_fields(reads, bound),
+        }
+    }
+}
+
+impl ReadFields for TagNode {
+    fn read_fields(&self, reads: &mut Reads, bound: &[String]) {
+        self.attrs.read_fields(reads, bound);
+        if let Some(TagClose { inner, close: _ }) = &self.close {
+            inner.read_fields(reads, bound);
+        }
+    }
+}
+
+impl ReadFields for Attr {
+    fn read_fields(&self, reads: &mut Reads, bound: &[String]) {
+        match self {
+            Attr::Normal { ident: _, value } => value.read_fields(reads, bound),
+            Attr::Axm {
+                ident: _,
+                value,
+                no_js_submit: _,
+                event: _,
+            } => value.read_fields(reads, bound),
+        }
+    }
+}
+
+impl ReadFields for NormalAttrValue {
+    fn read_fields(&self, reads: &mut Reads, bound: &[String]) {
+        match self {
+            NormalAttrValue::Block(block) => block.read_fields(reads, bound),
+            NormalAttrValue::If(if_) => if_.read_fields(reads, bound),
+            NormalAttrValue::LitStr(_) | NormalAttrValue::Unit(_) | NormalAttrValue::None => {}
+        }
+    }
+}
+
+impl ReadFields for AxmAttrValue {
+    fn read_fields(&self, reads: &mut Reads, bound: &[String]) {
+        match self {
+            AxmAttrValue::Block(block) => block.read_fields(reads, bound),
+            AxmAttrValue::If(if_) => if_.read_fields(reads, bound),
+        }
+    }
+}
+
+impl<T> ReadFields for If<T>
+where
+    T: ReadFields,
+{
+    fn read_fields(&self, reads: &mut Reads, bound: &[String]) {
+        let then_bound = match &self.cond {
+            syn::Expr::Let(let_) => {
+                let_.expr.read_fields(reads, bound);
+                with_bindings(bound, &let_.pat)
+            }
+            cond => {
+                cond.read_fields(reads, bound);
+                bound.to_vec()
+            }
+        };
+
+        self.then_tree.read_fields(reads, &then_bound);
+
+        match &self.else_tree {
+            Some(ElseBranch::If(else_if)) => else_if.read_fields(reads, bound),
+            Some(ElseBranch::Else(else_)) => else_.read_fields(reads, bound),
+            None => {}
+        }
+    }
+}
+
+impl ReadFields for For {
+    fn read_fields(&self, reads: &mut Reads, bound: &[String]) {
+        self.expr.read_fields(reads, bound);
+        self.tree
+            .read_fields(reads, &with_bindings(bound, &self.pat));
+    }
+}
+
+impl ReadFields for Match {
+    fn read_fields(&self, reads: &mut Reads, bound: &[String]) {
+        self.expr.read_fields(reads, bound);
+
+        for arm in &self.arms {
+            let bound = with_bindings(bound, &arm.pat);
+            if let Some(guard) = &arm.guard {
+                guard.read_fields(reads, &bound);
+            }
+            arm.tree.read_fields(reads, &bound);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reads(tokens: TokenStream) -> Option<Vec<String>> {
+        let node = syn::parse2::<HtmlNode>(tokens).unwrap();
+        let reads = Reads::of(&node);
+        (!reads.untracked).then(|| reads.fields.into_iter().collect())
+    }
+
+    #[test]
+    fn fields_of_self() {
+        assert_eq!(
+            reads(quote! { { self.count } }),
+            Some(vec!["count".to_owned()])
+        );
+        assert_eq!(
+            reads(quote! { { (self.a.len(), &self.b[0]) } }),
+            Some(vec!["a".to_owned(), "b".to_owned()])
+        );
+        assert_eq!(reads(quote! { { self.0 } }), Some(vec!["0".to_owned()]));
+        assert_eq!(
+            reads(quote! { { Msg::Select(self.id, Kind::Primary, None) } }),
+            Some(vec!["id".to_owned()])
+        );
+        assert_eq!(
+            reads(quote! { { self.count != 0 } }),
+            Some(vec!["count".to_owned()])
+        );
+    }
+
+    #[test]
+    fn locals_are_untracked() {
+        assert_eq!(reads(quote! { { count } }), None);
+        assert_eq!(reads(quote! { { self.count + offset } }), None);
+        assert_eq!(reads(quote! { { self.render_row() } }), None);
+        assert_eq!(reads(quote! { { helper(self) } }), None);
+    }
+
+    #[test]
+    fn function_calls_are_untracked() {
+        assert_eq!(reads(quote! { { helper(self.count) } }), None);
+        assert_eq!(reads(quote! { { Local::now() } }), None);
+        assert_eq!(reads(quote! { { rand::random::<u32>() } }), None);
+        assert_eq!(reads(quote! { { (self.count, Local::now()) } }), None);
+        assert_eq!(
+            reads(quote! { { Some(self.items.len().to_string()) } }),
+            Some(vec!["items".to_owned()])
+        );
+        assert_eq!(reads(quote! { { format!("{count}") } }), None);
+        assert_eq!(reads(quote! { { Point { x: self.x, y: y } } }), None);
+        assert_eq!(
+            reads(quote! { { self.items.iter().map(|x| x + 1).sum::<u32>() } }),
+            None
+        );
+    }
+
+    #[test]
+    fn macros_are_untracked() {
+        assert_eq!(reads(quote! { { format!("{}", self.count) } }), None);
+        assert_eq!(reads(quote! { { label!() } }), None);
+        assert_eq!(
+            reads(quote! { { (self.title, count_of!(self.items)) } }),
+            None
+        );
+    }
+
+    #[test]
+    fn consts_and_statics_are_untracked() {
+        assert_eq!(reads(quote! { { self.count + OFFSET } }), None);
+        assert_eq!(reads(quote! { { Self::MAX - self.count } }), None);
+        assert_eq!(reads(quote! { { VISITS.load(Ordering::Relaxed) } }), None);
+        assert_eq!(reads(quote! { { config::offset } }), None);
+        assert_eq!(reads(quote! { { self.items.iter().map(Self::row) } }), None);
+        assert_eq!(
+            reads(quote! { { Msg::Clicked == self.last } }),
+            Some(vec!["last".to_owned()])
+        );
+    }
+
+    #[test]
+    fn bindings_of_control_flow() {
+        assert_eq!(
+            reads(quote! {
+                for (idx, item) in self.items.iter().enumerate() {
+                    <li class={ self.class }>{ idx } { item }</li>
+                }
+            }),
+            Some(vec!["class".to_owned(), "items".to_owned()])
+        );
+        assert_eq!(
+            reads(quote! {
+                if let Some(user) = &self.user {
+                    { user.name }
+                } else {
+                    { user }
+                }
+            }),
+            None
+        );
+        assert_eq!(
+            reads(quote! {
+                match &self.state {
+                    State::Loaded { value } if *value > 0 => <p>{ value }</p>,
+                    State::Loading => "loading",
+                }
+            }),
+            Some(vec!["state".to_owned()])
+        );
+    }
+}
@@ -0,0 +1,238 @@
+//! Dashboard for inspecting running live views.
+//!
+//! The dashboard lists every connected live view along with its type, the URI it was mounted at,
+//! when it connected, how many messages it has received, the size of the last diff sent to the
+//! client, and how many messages are waiting in its [`ViewHandle`] mailbox. It also allows
+//! disconnecting individual views.
+//!
+//! The dashboard is itself a live view so it updates in real time.
+//!
+//! Note that disconnecting a view closes its WebSocket connection. The JavaScript client will
+//! reconnect and thus create a fresh instance of the view.
+//!
+//! The dashboard exposes information about all your users' connections and allows disconnecting
+//! them, so make sure to put it behind some kind of authentication.
+//!
+//! # Example
+//!
+//! ```
+//! use axum::Router;
+//!
+//! let app = Router::new()
+//!     // your other routes...
+//!     .nest("/admin/live-view", axum_live_view::dashboard::routes());
+//! # let _: Router = app;
+//! ```
+//!
+//! [`ViewHandle`]: crate::live_view::ViewHandle
+
+use self::registry::ConnectionInfo;
+use crate as axum_live_view;
+use crate::{
+    event_data::EventData, html, instrument::ConnectionId, live_view::Updated,
+    live_view::ViewHandle, Html, LiveView, LiveViewUpgrade,
+};
+use axum::{extract::OriginalUri, response::Response, routing::get, Router};
+use http::{HeaderMap, Uri};
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, SystemTime};
+
+mod registry;
+
+pub(crate) use self::registry::{register, Registration};
+
+/// Routes that serve the dashboard.
+///
+/// The routes include the dashboard page itself and the JavaScript client it needs, so they work
+/// regardless of where they're nested.
+///
+/// See the [module docs](self) for an example.
+pub fn routes<S, B>() -> Router<S, B>
+where
+    B: axum::body::HttpBody + Send + 'static,
+    S: Clone + Send + Sync + 'static,
+{
+    Router::new()
+        .route("/", get(dashboard))
+        .route("/live-view.js", crate::precompiled_js())
+}
+
+async fn dashboard(live: LiveViewUpgrade, OriginalUri(uri): OriginalUri) -> Response {
+    let script_src = format!("{}/live-view.js", uri.path().trim_end_matches('/'));
+
+    live.response(move |embed| {
+        html! {
+            <!DOCTYPE html>
+            <html>
+                <head>
+                    <title>"axum-live-view dashboard"</title>
+                    <style>
+                        "body { font-family: sans-serif; }"
+                        "table { border-collapse: collapse; }"
+                        "th, td { padding: 4px 12px; text-align: left; border-bottom: 1px solid #ddd; }"
+                    </style>
+                </head>
+                <body>
+                    { embed.embed(Dashboard::new()) }
+                    <script src={ script_src }></script>
+                </body>
+            </html>
+        }
+    })
+}
+
+struct Dashboard {
+    connections: Vec<ConnectionInfo>,
+}
+
+impl Dashboard {
+    fn new() -> Self {
+        Self {
+            connections: registry::snapshot(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+enum Msg {
+    Refresh,
+    Disconnect(ConnectionId),
+}
+
+impl LiveView for Dashboard {
+    type Message = Msg;
+
+    fn mount(&mut self, _: Uri, _: &HeaderMap, handle: ViewHandle<Self::Message>) {
+        crate::util::spawn_unit(async move {
+            let mut interval = tokio::time::interval(Duration::from_secs(1));
+            loop {
+                interval.tick().await;
+                if handle.send(Msg::Refresh).await.is_err() {
+                    break;
+                }
+            }
+        });
+    }
+
+    fn update(mut self, msg: Msg, _: Option<EventData>) -> Updated<Self> {
+        match msg {
+            Msg::Refresh => {}
+            Msg::Disconnect(id) => {
+                if !registry::disconnect(id) {
+                    tracing::debug!(%id, "tried to disconnect unknown connection");
+                }
+            }
+        }
+
+        self.connections = registry::snapshot();
+
+        Updated::new(self)
+    }
+
+    fn render(&self) -> Html<Self::Message> {
+        let now = SystemTime::now();
+
+        html! {
+            <h1>"Live views"</h1>
+
+            if self.connections.is_empty() {
+                <p>"No connected views"</p>
+            } else {
+                <table>
+                    <thead>
+                        <tr>
+                            <th>"Id"</th>
+                            <th>"View"</th>
+                            <th>"URI"</th>
+                            <th>"Connected"</th>
+                            <th>"Messages"</th>
+                            <th>"Last diff size"</th>
+                            <th>"Mailbox depth"</th>
+                            <th></th>
+                        </tr>
+                    </thead>
+                    <tbody>
+                        for connection in &self.connections {
+                            <tr>
+                                <td>{ connection.id }</td>
+                                <td><code>{ connection.view }</code></td>
+                                <td>{ &connection.uri }</td>
+                                <td>
+                                    { format_elapsed(now, connection.connected_at) }
+                                </td>
+                                <td>{ connection.messages }</td>
+                                <td>{ format!("{} bytes", connection.last_diff_bytes) }</td>
+                                <td>{ connection.mailbox_depth }</td>
+                                <td>
+                                    <button axm-click={ Msg::Disconnect(connection.id) }>
+                                        "Disconnect"
+                                    </button>
+                                </td>
+                            </tr>
+                        }
+                    </tbody>
+                </table>
+            }
+        }
+    }
+}
+
+fn format_elapsed(now: SystemTime, then: SystemTime) -> String {
+    let secs = now.duration_since(then).unwrap_or_default().as_secs();
+
+    let (hours, mins, secs) = (secs / 3600, secs / 60 % 60, secs % 60);
+
+    if hours > 0 {
+        format!("{}h {}m {}s ago", hours, mins, secs)
+    } else if mins > 0 {
+        format!("{}m {}s ago", mins, secs)
+    } else {
+        format!("{}s ago", secs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::{Body, HttpBody};
+    use http::{header, Request, StatusCode};
+    use tower::ServiceExt;
+
+    async fn get(uri: &str) -> (StatusCode, HeaderMap, String) {
+        let res = Router::new()
+            .nest("/admin/live-view", routes())
+            .oneshot(Request::get(uri).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+
+        let status = res.status();
+        let headers = res.headers().clone();
+        let mut body = res.into_body();
+        let mut text = String::new();
+        while let Some(chunk) = body.data().await {
+            text.push_str(std::str::from_utf8(&chunk.unwrap()).unwrap());
+        }
+        (status, headers, text)
+    }
+
+    #[tokio::test]
+    async fn loads_dashboard_and_its_client() {
+        let (handle, _rx) = ViewHandle::<()>::new();
+        let id = ConnectionId::next();
+        let _registration = register("MyView", id, Uri::from_static("/foo"), &handle);
+
+        let (status, _, page) = get("/admin/live-view").await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(page.contains("<title>axum-live-view dashboard</title>"));
+        assert!(page.contains("<code>MyView</code>"));
+        assert!(page.contains("<script src=/admin/live-view/live-view.js>"));
+
+        let (status, headers, js) = get("/admin/live-view/live-view.js").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(
+            headers[header::CONTENT_TYPE],
+            "application/javascript; charset=utf-8"
+        );
+        assert_eq!(js, crate::PRECOMPILED_JS);
+    }
+}
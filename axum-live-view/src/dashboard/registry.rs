@@ -0,0 +1,169 @@
+use crate::{instrument::ConnectionId, live_view::ViewHandle};
+use http::Uri;
+use std::{
+    collections::BTreeMap,
+    fmt,
+    sync::{
+        atomic::{AtomicU64, AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    time::SystemTime,
+};
+use tokio::sync::Notify;
+
+static CONNECTIONS: Mutex<BTreeMap<ConnectionId, Arc<Connection>>> = Mutex::new(BTreeMap::new());
+
+struct Connection {
+    id: ConnectionId,
+    view: &'static str,
+    uri: Uri,
+    connected_at: SystemTime,
+    messages: AtomicU64,
+    last_diff_bytes: AtomicUsize,
+    mailbox_depth: Box<dyn Fn() -> usize + Send + Sync>,
+    disconnect: Notify,
+}
+
+/// Register a new connection.
+///
+/// The connection is removed from the registry when the returned [`Registration`] is dropped.
+pub(crate) fn register<M>(
+    view: &'static str,
+    id: ConnectionId,
+    uri: Uri,
+    handle: &ViewHandle<M>,
+) -> Registration
+where
+    M: Send + 'static,
+{
+    let handle = handle.clone();
+
+    let connection = Arc::new(Connection {
+        id,
+        view,
+        uri,
+        connected_at: SystemTime::now(),
+        messages: AtomicU64::new(0),
+        last_diff_bytes: AtomicUsize::new(0),
+        mailbox_depth: Box::new(move || handle.mailbox_depth()),
+        disconnect: Notify::new(),
+    });
+
+    connections().insert(id, Arc::clone(&connection));
+
+    Registration { connection }
+}
+
+/// Get information about all the currently registered connections, ordered by when they connected.
+pub(crate) fn snapshot() -> Vec<ConnectionInfo> {
+    connections()
+        .values()
+        .map(|connection| ConnectionInfo {
+            id: connection.id,
+            view: connection.view,
+            uri: connection.uri.clone(),
+            connected_at: connection.connected_at,
+            messages: connection.messages.load(Ordering::Relaxed),
+            last_diff_bytes: connection.last_diff_bytes.load(Ordering::Relaxed),
+            mailbox_depth: (connection.mailbox_depth)(),
+        })
+        .collect()
+}
+
+/// Disconnect a connection.
+///
+/// Returns `false` if no connection with the given id exists.
+pub(crate) fn disconnect(id: ConnectionId) -> bool {
+    if let Some(connection) = connections().get(&id) {
+        // `notify_one` stores a permit so this works even if the connection isn't currently
+        // waiting
+        connection.disconnect.notify_one();
+        true
+    } else {
+        false
+    }
+}
+
+fn connections() -> std::sync::MutexGuard<'static, BTreeMap<ConnectionId, Arc<Connection>>> {
+    // the lock is never held while calling user code so it cannot be poisoned by a panicking view
+    CONNECTIONS.lock().unwrap_or_else(|err| err.into_inner())
+}
+
+pub(crate) struct Registration {
+    connection: Arc<Connection>,
+}
+
+impl Registration {
+    pub(crate) fn message_received(&self) {
+        self.connection.messages.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn diff_sent(&self, bytes: usize) {
+        self.connection
+            .last_diff_bytes
+            .store(bytes, Ordering::Relaxed);
+    }
+
+    pub(crate) async fn disconnect_requested(&self) {
+        self.connection.disconnect.notified().await;
+    }
+}
+
+impl Drop for Registration {
+    fn drop(&mut self) {
+        connections().remove(&self.connection.id);
+    }
+}
+
+impl fmt::Debug for Registration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Registration")
+            .field("id", &self.connection.id)
+            .field("view", &self.connection.view)
+            .finish()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct ConnectionInfo {
+    pub(crate) id: ConnectionId,
+    pub(crate) view: &'static str,
+    pub(crate) uri: Uri,
+    pub(crate) connected_at: SystemTime,
+    pub(crate) messages: u64,
+    pub(crate) last_diff_bytes: usize,
+    pub(crate) mailbox_depth: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn register_and_disconnect() {
+        let (handle, _rx) = ViewHandle::<()>::new();
+        let id = ConnectionId::next();
+
+        let registration = register("MyView", id, Uri::from_static("/foo"), &handle);
+        registration.message_received();
+        registration.diff_sent(123);
+        handle.send(()).await.unwrap();
+
+        let info = snapshot().into_iter().find(|info| info.id == id).unwrap();
+        assert_eq!(info.view, "MyView");
+        assert_eq!(info.uri, "/foo");
+        assert_eq!(info.messages, 1);
+        assert_eq!(info.last_diff_bytes, 123);
+        assert_eq!(info.mailbox_depth, 1);
+
+        assert!(disconnect(id));
+        tokio::time::timeout(Duration::from_secs(1), registration.disconnect_requested())
+            .await
+            .unwrap();
+
+        drop(registration);
+        assert!(snapshot().iter().all(|info| info.id != id));
+        assert!(!disconnect(id));
+    }
+}
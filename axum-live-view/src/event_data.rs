@@ -3,7 +3,8 @@
 mod inner {
     use crate::life_cycle::{self, EventMessageFromSocketData};
     use serde::{de::DeserializeOwned, Serialize};
-    use std::fmt;
+    use serde_json::{Map, Value};
+    use std::{borrow::Cow, fmt};
 
     /// The data for an event that happened on the client.
     ///
@@ -33,6 +34,50 @@ mod inner {
         ///
         /// See [`Scroll`] for more details.
         Scroll(Scroll),
+        /// A pointer event.
+        ///
+        /// See [`Pointer`] for more details.
+        Pointer(Pointer),
+        /// A touch event.
+        ///
+        /// See [`Touch`] for more details.
+        Touch(Touch),
+        /// A wheel event.
+        ///
+        /// See [`Wheel`] for more details.
+        Wheel(Wheel),
+        /// A drag and drop event.
+        ///
+        /// See [`Drag`] for more details.
+        Drag(Drag),
+        /// A window resize event.
+        ///
+        /// See [`Resize`] for more details.
+        Resize(Resize),
+        /// A visibility change event.
+        ///
+        /// See [`Visibility`] for more details.
+        Visibility(Visibility),
+        /// An online or offline event.
+        ///
+        /// See [`Network`] for more details.
+        Network(Network),
+        /// A viewport intersection event.
+        ///
+        /// See [`Intersection`] for more details.
+        Intersection(Intersection),
+        /// An event sent from an element with `axm-value-*` attributes.
+        ///
+        /// The `as_*` methods look through this variant, so [`EventData::as_form`] returns the
+        /// form whether or not it was sent with values.
+        ///
+        /// See [`Values`] for more details.
+        WithValues {
+            /// The event's own data, if it has any.
+            data: Option<Box<EventData>>,
+            /// The values of the `axm-value-*` attributes.
+            values: Values,
+        },
     }
 
     impl_from!(EventData::Form);
@@ -40,11 +85,65 @@ mod inner {
     impl_from!(EventData::Key);
     impl_from!(EventData::Mouse);
     impl_from!(EventData::Scroll);
+    impl_from!(EventData::Pointer);
+    impl_from!(EventData::Touch);
+    impl_from!(EventData::Wheel);
+    impl_from!(EventData::Drag);
+    impl_from!(EventData::Resize);
+    impl_from!(EventData::Visibility);
+    impl_from!(EventData::Network);
+    impl_from!(EventData::Intersection);
 
     impl EventData {
+        /// Get the values sent with `axm-value-*` attributes, if any.
+        ///
+        /// See [`Values`] for more details.
+        pub fn values(&self) -> Option<&Values> {
+            if let Self::WithValues { values, .. } = self {
+                Some(values)
+            } else {
+                None
+            }
+        }
+
+        /// Set the values sent with `axm-value-*` attributes.
+        ///
+        /// This allows creating events with values for example for use in tests.
+        pub fn with_values(self, values: Values) -> Self {
+            let data = match self {
+                Self::WithValues { data, .. } => data,
+                data => Some(Box::new(data)),
+            };
+            Self::WithValues { data, values }
+        }
+
+        pub(crate) fn from_socket(
+            data: EventMessageFromSocketData,
+            values: Map<String, Value>,
+        ) -> Option<Self> {
+            let data = Option::<Self>::from(data);
+            if values.is_empty() {
+                return data;
+            }
+            Some(Self::WithValues {
+                data: data.map(Box::new),
+                values: Values(values),
+            })
+        }
+
+        /// The event's own data, looking through [`EventData::WithValues`].
+        fn own_data(&self) -> &Self {
+            match self {
+                Self::WithValues {
+                    data: Some(data), ..
+                } => data,
+                _ => self,
+            }
+        }
+
         /// Get the inner [`Form`] if any.
         pub fn as_form(&self) -> Option<&Form> {
-            if let Self::Form(inner) = self {
+            if let Self::Form(inner) = self.own_data() {
                 Some(inner)
             } else {
                 None
@@ -53,7 +152,7 @@ mod inner {
 
         /// Get the inner [`Input`] if any.
         pub fn as_input(&self) -> Option<&Input> {
-            if let Self::Input(inner) = self {
+            if let Self::Input(inner) = self.own_data() {
                 Some(inner)
             } else {
                 None
@@ -62,7 +161,7 @@ mod inner {
 
         /// Get the inner [`Key`] if any.
         pub fn as_key(&self) -> Option<&Key> {
-            if let Self::Key(inner) = self {
+            if let Self::Key(inner) = self.own_data() {
                 Some(inner)
             } else {
                 None
@@ -71,7 +170,7 @@ mod inner {
 
         /// Get the inner [`Mouse`] if any.
         pub fn as_mouse(&self) -> Option<&Mouse> {
-            if let Self::Mouse(inner) = self {
+            if let Self::Mouse(inner) = self.own_data() {
                 Some(inner)
             } else {
                 None
@@ -80,7 +179,79 @@ mod inner {
 
         /// Get the inner [`Scroll`] if any.
         pub fn as_scroll(&self) -> Option<&Scroll> {
-            if let Self::Scroll(inner) = self {
+            if let Self::Scroll(inner) = self.own_data() {
+                Some(inner)
+            } else {
+                None
+            }
+        }
+
+        /// Get the inner [`Pointer`] if any.
+        pub fn as_pointer(&self) -> Option<&Pointer> {
+            if let Self::Pointer(inner) = self.own_data() {
+                Some(inner)
+            } else {
+                None
+            }
+        }
+
+        /// Get the inner [`Touch`] if any.
+        pub fn as_touch(&self) -> Option<&Touch> {
+            if let Self::Touch(inner) = self.own_data() {
+                Some(inner)
+            } else {
+                None
+            }
+        }
+
+        /// Get the inner [`Wheel`] if any.
+        pub fn as_wheel(&self) -> Option<&Wheel> {
+            if let Self::Wheel(inner) = self.own_data() {
+                Some(inner)
+            } else {
+                None
+            }
+        }
+
+        /// Get the inner [`Drag`] if any.
+        pub fn as_drag(&self) -> Option<&Drag> {
+            if let Self::Drag(inner) = self.own_data() {
+                Some(inner)
+            } else {
+                None
+            }
+        }
+
+        /// Get the inner [`Resize`] if any.
+        pub fn as_resize(&self) -> Option<&Resize> {
+            if let Self::Resize(inner) = self.own_data() {
+                Some(inner)
+            } else {
+                None
+            }
+        }
+
+        /// Get the inner [`Visibility`] if any.
+        pub fn as_visibility(&self) -> Option<&Visibility> {
+            if let Self::Visibility(inner) = self.own_data() {
+                Some(inner)
+            } else {
+                None
+            }
+        }
+
+        /// Get the inner [`Network`] if any.
+        pub fn as_network(&self) -> Option<&Network> {
+            if let Self::Network(inner) = self.own_data() {
+                Some(inner)
+            } else {
+                None
+            }
+        }
+
+        /// Get the inner [`Intersection`] if any.
+        pub fn as_intersection(&self) -> Option<&Intersection> {
+            if let Self::Intersection(inner) = self.own_data() {
                 Some(inner)
             } else {
                 None
@@ -94,8 +265,12 @@ mod inner {
                 EventMessageFromSocketData::Click
                 | EventMessageFromSocketData::WindowFocus
                 | EventMessageFromSocketData::WindowBlur
+                | EventMessageFromSocketData::WindowBeforeunload
+                | EventMessageFromSocketData::TransitionEnd
                 | EventMessageFromSocketData::None => None,
-                EventMessageFromSocketData::Form { query } => Some(EventData::Form(Form { query })),
+                EventMessageFromSocketData::Form { query, changed } => {
+                    Some(EventData::Form(Form { query, changed }))
+                }
                 EventMessageFromSocketData::Input { value } => {
                     let value = match value {
                         life_cycle::InputValue::Bool(x) => Input::Bool(x),
@@ -145,10 +320,490 @@ mod inner {
                 EventMessageFromSocketData::Scroll { scroll_x, scroll_y } => {
                     Some(EventData::Scroll(Scroll { scroll_x, scroll_y }))
                 }
+                EventMessageFromSocketData::Pointer {
+                    pointer_id,
+                    pointer_type,
+                    is_primary,
+                    client_x,
+                    client_y,
+                    page_x,
+                    page_y,
+                    offset_x,
+                    offset_y,
+                    width,
+                    height,
+                    pressure,
+                } => Some(EventData::Pointer(Pointer {
+                    pointer_id,
+                    pointer_type,
+                    is_primary,
+                    client_x,
+                    client_y,
+                    page_x,
+                    page_y,
+                    offset_x,
+                    offset_y,
+                    width,
+                    height,
+                    pressure,
+                })),
+                EventMessageFromSocketData::Touch {
+                    touches,
+                    changed_touches,
+                } => Some(EventData::Touch(Touch {
+                    touches: touches.into_iter().map(TouchPoint::from).collect(),
+                    changed_touches: changed_touches.into_iter().map(TouchPoint::from).collect(),
+                })),
+                EventMessageFromSocketData::Wheel {
+                    delta_x,
+                    delta_y,
+                    delta_z,
+                    client_x,
+                    client_y,
+                    ctrl,
+                } => Some(EventData::Wheel(Wheel {
+                    delta_x,
+                    delta_y,
+                    delta_z,
+                    client_x,
+                    client_y,
+                    ctrl,
+                })),
+                EventMessageFromSocketData::Drag {
+                    dragged_id,
+                    client_x,
+                    client_y,
+                } => Some(EventData::Drag(Drag {
+                    dragged_id,
+                    client_x,
+                    client_y,
+                })),
+                EventMessageFromSocketData::Resize { width, height } => {
+                    Some(EventData::Resize(Resize { width, height }))
+                }
+                EventMessageFromSocketData::Visibility { hidden } => {
+                    Some(EventData::Visibility(Visibility { hidden }))
+                }
+                EventMessageFromSocketData::Network { online } => {
+                    Some(EventData::Network(Network { online }))
+                }
+                EventMessageFromSocketData::Intersection {
+                    ratio,
+                    intersecting,
+                } => Some(EventData::Intersection(Intersection {
+                    ratio,
+                    intersecting,
+                })),
+            }
+        }
+    }
+
+    impl EventData {
+        fn name(&self) -> Option<&'static str> {
+            let name = match self.own_data() {
+                Self::Form(_) => "form",
+                Self::Input(_) => "input",
+                Self::Key(_) => "key",
+                Self::Mouse(_) => "mouse",
+                Self::Scroll(_) => "scroll",
+                Self::Pointer(_) => "pointer",
+                Self::Touch(_) => "touch",
+                Self::Wheel(_) => "wheel",
+                Self::Drag(_) => "drag",
+                Self::Resize(_) => "resize",
+                Self::Visibility(_) => "visibility",
+                Self::Network(_) => "network",
+                Self::Intersection(_) => "intersection",
+                Self::WithValues { .. } => return None,
+            };
+            Some(name)
+        }
+    }
+
+    /// Types that can be created from the data of an event.
+    ///
+    /// This allows binding message constructors directly, rather than unwrapping [`EventData`]
+    /// in [`LiveView::update`]:
+    ///
+    /// ```rust
+    /// use axum_live_view::{html, Html};
+    /// use serde::{Deserialize, Serialize};
+    ///
+    /// #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    /// enum Msg {
+    ///     TextChanged(String),
+    ///     Toggled(bool),
+    /// }
+    ///
+    /// fn render() -> Html<Msg> {
+    ///     html! {
+    ///         <input type="text" axm-input={ Msg::TextChanged } />
+    ///         <input type="checkbox" axm-change={ Msg::Toggled } />
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// The constructor must take a single argument that implements `FromEventData`. It is
+    /// called with the value from the event before the message is passed to `update`.
+    ///
+    /// [`FromEventData::Kind`] says which events the value can be created from and `html!` checks
+    /// it at compile time, so binding `axm-click={ Msg::TextChanged }` doesn't compile. If the
+    /// data still doesn't fit, such as an input containing `"abc"` bound to `Msg::AgeChanged(u32)`,
+    /// the event is ignored and the error is logged.
+    ///
+    /// `FromEventData` is implemented for:
+    ///
+    /// - `String`: The value of a text input, or the key of a key event.
+    /// - `bool`: Whether a checkbox is checked.
+    /// - `Vec<String>`: The selected options of a `<select multiple>`.
+    /// - Integers and floats: The value of an input, such as `<input type="number">`.
+    ///
+    /// Forms can be deserialized into your own types using [`deserialize_form`]:
+    ///
+    /// ```rust
+    /// use axum_live_view::event_data::{
+    ///     deserialize_form, kind, EventData, FromEventData, FromEventDataError,
+    /// };
+    /// use serde::{Deserialize, Serialize};
+    ///
+    /// #[derive(Serialize, Deserialize, Default, Debug, PartialEq)]
+    /// struct Signup {
+    ///     email: String,
+    ///     age: u32,
+    /// }
+    ///
+    /// impl FromEventData for Signup {
+    ///     type Kind = kind::Form;
+    ///
+    ///     fn from_event_data(data: Option<&EventData>) -> Result<Self, FromEventDataError> {
+    ///         deserialize_form(data)
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// [`LiveView::update`]: crate::live_view::LiveView::update
+    pub trait FromEventData: Default + Serialize + Sized {
+        /// The kind of events the value can be created from, such as [`kind::Form`].
+        ///
+        /// [`kind::Any`] allows binding the constructor to any event, for values created from
+        /// `axm-value-*` attributes.
+        ///
+        /// [`kind::Form`]: super::kind::Form
+        /// [`kind::Any`]: super::kind::Any
+        type Kind;
+
+        /// Create the value from the event's data.
+        fn from_event_data(data: Option<&EventData>) -> Result<Self, FromEventDataError>;
+
+        /// The value the constructor is called with when rendering the binding. It is never passed
+        /// to `update`.
+        ///
+        /// It's used to locate the argument in the message, so if the message contains other
+        /// values of the same type they must differ from the placeholder. Defaults to
+        /// [`Default::default`].
+        ///
+        /// If another value in the message equals the placeholder the argument can't be located
+        /// and rendering the binding fails, rather than the event data ending up in the wrong
+        /// field. Wrap such values in a different type or override this method to return a value
+        /// they can't have.
+        fn placeholder() -> Self {
+            Self::default()
+        }
+    }
+
+    /// The placeholder for text, chosen so it doesn't equal text from anywhere else.
+    const TEXT_PLACEHOLDER: &str = "\u{0}axm-placeholder";
+
+    /// Deserialize the data of a form event.
+    ///
+    /// See [`FromEventData`] for more details.
+    pub fn deserialize_form<T>(data: Option<&EventData>) -> Result<T, FromEventDataError>
+    where
+        T: DeserializeOwned,
+    {
+        let form = data
+            .and_then(EventData::as_form)
+            .ok_or_else(|| FromEventDataError::unexpected("form", data))?;
+        Ok(form.deserialize()?)
+    }
+
+    impl FromEventData for String {
+        type Kind = super::kind::Text;
+
+        fn from_event_data(data: Option<&EventData>) -> Result<Self, FromEventDataError> {
+            if let Some(value) = data.and_then(EventData::as_input).and_then(Input::as_str) {
+                return Ok(value.to_owned());
+            }
+            if let Some(key) = data.and_then(EventData::as_key) {
+                return Ok(key.key().to_owned());
+            }
+            Err(FromEventDataError::unexpected("text input or key", data))
+        }
+
+        fn placeholder() -> Self {
+            TEXT_PLACEHOLDER.to_owned()
+        }
+    }
+
+    impl FromEventData for bool {
+        type Kind = super::kind::Input;
+
+        fn from_event_data(data: Option<&EventData>) -> Result<Self, FromEventDataError> {
+            data.and_then(EventData::as_input)
+                .and_then(Input::as_bool)
+                .ok_or_else(|| FromEventDataError::unexpected("checkbox input", data))
+        }
+    }
+
+    impl FromEventData for Vec<String> {
+        type Kind = super::kind::Input;
+
+        fn from_event_data(data: Option<&EventData>) -> Result<Self, FromEventDataError> {
+            data.and_then(EventData::as_input)
+                .and_then(Input::as_strings)
+                .map(|values| values.to_vec())
+                .ok_or_else(|| FromEventDataError::unexpected("multiple select input", data))
+        }
+
+        fn placeholder() -> Self {
+            Vec::from([TEXT_PLACEHOLDER.to_owned()])
+        }
+    }
+
+    macro_rules! impl_from_event_data_parse {
+        ($($ty:ty => $placeholder:expr),*) => {
+            $(
+                impl FromEventData for $ty {
+                    type Kind = super::kind::Input;
+
+                    fn from_event_data(
+                        data: Option<&EventData>,
+                    ) -> Result<Self, FromEventDataError> {
+                        let value = data
+                            .and_then(EventData::as_input)
+                            .and_then(Input::as_str)
+                            .ok_or_else(|| FromEventDataError::unexpected("text input", data))?;
+                        value.trim().parse().map_err(|_| {
+                            FromEventDataError(FromEventDataErrorKind::Parse {
+                                value: value.to_owned(),
+                                ty: stringify!($ty),
+                            })
+                        })
+                    }
+
+                    fn placeholder() -> Self {
+                        $placeholder
+                    }
+                }
+            )*
+        };
+    }
+
+    // unlikely values, so the placeholder doesn't equal other numbers in the message
+    impl_from_event_data_parse!(
+        i32 => i32::MIN + 0x5eed,
+        i64 => i64::MIN + 0x5eed,
+        u32 => u32::MAX - 0x5eed,
+        u64 => u64::MAX - 0x5eed,
+        usize => usize::MAX - 0x5eed,
+        f32 => f32::MIN,
+        f64 => f64::MIN
+    );
+
+    /// The error returned if [`FromEventData`] fails.
+    #[derive(Debug)]
+    pub struct FromEventDataError(FromEventDataErrorKind);
+
+    #[derive(Debug)]
+    enum FromEventDataErrorKind {
+        UnexpectedData {
+            expected: &'static str,
+            found: Option<&'static str>,
+        },
+        Parse {
+            value: String,
+            ty: &'static str,
+        },
+        Form(FormSerializationError),
+    }
+
+    impl FromEventDataError {
+        /// Create an error for an event that doesn't have the `expected` kind of data.
+        ///
+        /// Useful when implementing [`FromEventData`].
+        pub fn unexpected(expected: &'static str, data: Option<&EventData>) -> Self {
+            Self(FromEventDataErrorKind::UnexpectedData {
+                expected,
+                found: data.and_then(EventData::name),
+            })
+        }
+    }
+
+    impl From<FormSerializationError> for FromEventDataError {
+        fn from(err: FormSerializationError) -> Self {
+            Self(FromEventDataErrorKind::Form(err))
+        }
+    }
+
+    impl fmt::Display for FromEventDataError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match &self.0 {
+                FromEventDataErrorKind::UnexpectedData {
+                    expected,
+                    found: Some(found),
+                } => write!(
+                    f,
+                    "expected {} event data, found {} event data",
+                    expected, found
+                ),
+                FromEventDataErrorKind::UnexpectedData {
+                    expected,
+                    found: None,
+                } => write!(
+                    f,
+                    "expected {} event data, found an event without data",
+                    expected
+                ),
+                FromEventDataErrorKind::Parse { value, ty } => {
+                    write!(f, "failed to parse {:?} as `{}`", value, ty)
+                }
+                FromEventDataErrorKind::Form(inner) => inner.fmt(f),
+            }
+        }
+    }
+
+    impl std::error::Error for FromEventDataError {
+        fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+            match &self.0 {
+                FromEventDataErrorKind::UnexpectedData { .. }
+                | FromEventDataErrorKind::Parse { .. } => None,
+                FromEventDataErrorKind::Form(inner) => Some(inner),
             }
         }
     }
 
+    /// Extra values sent with an event using `axm-value-*` attributes.
+    ///
+    /// Each `axm-value-{name}={ value }` attribute on the element that sent the event becomes a
+    /// value called `name`. Values are serialized as JSON, so `axm-value-id={ 1 }` is sent as a
+    /// number while `axm-value-id="1"` and `axm-value-id={ "1" }` are both sent as a string.
+    ///
+    /// ```rust
+    /// use axum_live_view::{event_data::EventData, html, Html};
+    /// use serde::{Deserialize, Serialize};
+    ///
+    /// #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    /// enum Msg {
+    ///     Delete,
+    /// }
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Item {
+    ///     id: u64,
+    /// }
+    ///
+    /// fn render(ids: &[u64]) -> Html<Msg> {
+    ///     html! {
+    ///         for id in ids {
+    ///             <button axm-click={ Msg::Delete } axm-value-id={ id }>"Delete"</button>
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// fn update(msg: Msg, data: Option<EventData>) {
+    ///     match msg {
+    ///         Msg::Delete => {
+    ///             let values = data.as_ref().and_then(EventData::values).unwrap();
+    ///             let item: Item = values.deserialize().unwrap();
+    ///             // delete the item with `item.id`
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    #[derive(Debug, Clone, Default)]
+    pub struct Values(Map<String, Value>);
+
+    impl Values {
+        /// Get a [`ValuesBuilder`] for `Values`.
+        ///
+        /// This allows creating `Values` for example for use in tests.
+        pub fn builder() -> ValuesBuilder {
+            ValuesBuilder::default()
+        }
+
+        /// Get a value by name.
+        pub fn get(&self, name: &str) -> Option<&Value> {
+            self.0.get(name)
+        }
+
+        /// Returns `true` if there are no values.
+        pub fn is_empty(&self) -> bool {
+            self.0.is_empty()
+        }
+
+        /// Iterate over the names and values.
+        pub fn iter(&self) -> impl Iterator<Item = (&str, &Value)> {
+            self.0.iter().map(|(name, value)| (&**name, value))
+        }
+
+        /// Deserialize the values into some type.
+        ///
+        /// The values are deserialized as a map from names to values.
+        pub fn deserialize<T>(&self) -> Result<T, ValuesDeserializationError>
+        where
+            T: DeserializeOwned,
+        {
+            T::deserialize(&self.0).map_err(ValuesDeserializationError)
+        }
+    }
+
+    /// The error returned if [`Values`] couldn't be deserialized.
+    #[derive(Debug)]
+    pub struct ValuesDeserializationError(serde_json::Error);
+
+    impl fmt::Display for ValuesDeserializationError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            self.0.fmt(f)
+        }
+    }
+
+    impl std::error::Error for ValuesDeserializationError {
+        fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+            Some(&self.0)
+        }
+    }
+
+    /// Builder for [`Values`].
+    ///
+    /// Created with [`Values::builder`].
+    #[derive(Clone, Debug, Default)]
+    pub struct ValuesBuilder {
+        values: Map<String, Value>,
+    }
+
+    impl ValuesBuilder {
+        /// Create a new `ValuesBuilder`.
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        /// Add a value.
+        ///
+        /// # Panics
+        ///
+        /// Panics if the value cannot be serialized to JSON.
+        pub fn value(mut self, name: impl Into<String>, value: impl Serialize) -> Self {
+            let value = serde_json::to_value(value).expect("failed to serialize value");
+            self.values.insert(name.into(), value);
+            self
+        }
+
+        /// Consume the builder and construct the `Values`.
+        pub fn build(self) -> Values {
+            Values(self.values)
+        }
+    }
+
     /// A form event.
     ///
     /// This event type is sent for these bindings:
@@ -158,6 +813,7 @@ mod inner {
     #[derive(Debug, Clone)]
     pub struct Form {
         query: String,
+        changed: Option<String>,
     }
 
     impl Form {
@@ -173,11 +829,7 @@ mod inner {
         where
             T: DeserializeOwned,
         {
-            let query = percent_encoding::percent_decode_str(&self.query)
-                .decode_utf8()
-                .map_err(|err| {
-                    FormSerializationError(QuerySerializationErrorKind::Utf8Error(err))
-                })?;
+            let query = self.decoded_query()?;
 
             let t = serde_qs::from_str(&*query).map_err(|err| {
                 FormSerializationError(QuerySerializationErrorKind::Serialization(err))
@@ -185,6 +837,19 @@ mod inner {
 
             Ok(t)
         }
+
+        /// Get the name of the field that changed, if any.
+        ///
+        /// This is only set for `axm-change` events triggered by a named field.
+        pub fn changed_field(&self) -> Option<&str> {
+            self.changed.as_deref()
+        }
+
+        pub(crate) fn decoded_query(&self) -> Result<Cow<'_, str>, FormSerializationError> {
+            percent_encoding::percent_decode_str(&self.query)
+                .decode_utf8()
+                .map_err(|err| FormSerializationError(QuerySerializationErrorKind::Utf8Error(err)))
+        }
     }
 
     /// The error returned if a form couldn't be serialized or deserialized.
@@ -218,6 +883,7 @@ mod inner {
     #[derive(Clone, Debug, Default)]
     pub struct FormBuilder {
         query: String,
+        changed: Option<String>,
     }
 
     impl FormBuilder {
@@ -239,8 +905,20 @@ mod inner {
             Ok(self)
         }
 
+        /// Set the name of the field that changed.
+        pub fn changed_field<S>(mut self, name: S) -> Self
+        where
+            S: Into<String>,
+        {
+            self.changed = Some(name.into());
+            self
+        }
+
         pub fn build(self) -> Form {
-            Form { query: self.query }
+            Form {
+                query: self.query,
+                changed: self.changed,
+            }
         }
     }
 
@@ -328,6 +1006,8 @@ mod inner {
         /// - `axm-mouseleave`
         /// - `axm-mouseout`
         /// - `axm-mousemove`
+        /// - `axm-dblclick`
+        /// - `axm-contextmenu`
         ///
         /// See [MDN] for more details about mouse events.
         ///
@@ -473,12 +1153,624 @@ mod inner {
             self.scroll_y
         }
     }
-}
-
-pub use self::inner::{EventData, Form, FormSerializationError, Input, Key, Mouse, Scroll};
 
-pub mod builders {
-    //! Event data builder types.
+    builder! {
+        #[builder_name = PointerBuilder]
+        #[derive(Debug, Clone)]
+        /// A pointer event.
+        ///
+        /// This event type is sent for these bindings:
+        ///
+        /// - `axm-pointerdown`
+        /// - `axm-pointermove`
+        /// - `axm-pointerup`
+        ///
+        /// Pointer events are sent for mouse, pen, and touch input. See [MDN] for more details.
+        ///
+        /// [MDN]: https://developer.mozilla.org/en-US/docs/Web/API/PointerEvent
+        pub struct Pointer {
+            pointer_id: i64,
+            pointer_type: String,
+            is_primary: bool,
+            client_x: f64,
+            client_y: f64,
+            page_x: f64,
+            page_y: f64,
+            offset_x: f64,
+            offset_y: f64,
+            width: f64,
+            height: f64,
+            pressure: f64,
+        }
+    }
+
+    impl Pointer {
+        /// A unique identifier for the pointer causing the event.
+        ///
+        /// See [MDN] for more details.
+        ///
+        /// [MDN]: https://developer.mozilla.org/en-US/docs/Web/API/PointerEvent/pointerId
+        pub fn pointer_id(&self) -> i64 {
+            self.pointer_id
+        }
+
+        /// The device type that caused the event, such as `mouse`, `pen`, or `touch`.
+        ///
+        /// See [MDN] for more details.
+        ///
+        /// [MDN]: https://developer.mozilla.org/en-US/docs/Web/API/PointerEvent/pointerType
+        pub fn pointer_type(&self) -> &str {
+            &self.pointer_type
+        }
+
+        /// Whether the pointer is the primary pointer of its type.
+        ///
+        /// See [MDN] for more details.
+        ///
+        /// [MDN]: https://developer.mozilla.org/en-US/docs/Web/API/PointerEvent/isPrimary
+        pub fn is_primary(&self) -> bool {
+            self.is_primary
+        }
+
+        /// Horizontal coordinate within the application's viewport at which the event occurred.
+        ///
+        /// See [MDN] for more details.
+        ///
+        /// [MDN]: https://developer.mozilla.org/en-US/docs/Web/API/MouseEvent/clientX
+        pub fn client_x(&self) -> f64 {
+            self.client_x
+        }
+
+        /// Vertical coordinate within the application's viewport at which the event occurred.
+        ///
+        /// See [MDN] for more details.
+        ///
+        /// [MDN]: https://developer.mozilla.org/en-US/docs/Web/API/MouseEvent/clientY
+        pub fn client_y(&self) -> f64 {
+            self.client_y
+        }
+
+        /// The horizontal coordinate of the pointer relative to the whole document.
+        ///
+        /// See [MDN] for more details.
+        ///
+        /// [MDN]: https://developer.mozilla.org/en-US/docs/Web/API/MouseEvent/pageX
+        pub fn page_x(&self) -> f64 {
+            self.page_x
+        }
+
+        /// The vertical coordinate of the pointer relative to the whole document.
+        ///
+        /// See [MDN] for more details.
+        ///
+        /// [MDN]: https://developer.mozilla.org/en-US/docs/Web/API/MouseEvent/pageY
+        pub fn page_y(&self) -> f64 {
+            self.page_y
+        }
+
+        /// The horizontal coordinate of the pointer relative to the position of the padding edge
+        /// of the target node.
+        ///
+        /// See [MDN] for more details.
+        ///
+        /// [MDN]: https://developer.mozilla.org/en-US/docs/Web/API/MouseEvent/offsetX
+        pub fn offset_x(&self) -> f64 {
+            self.offset_x
+        }
+
+        /// The vertical coordinate of the pointer relative to the position of the padding edge
+        /// of the target node.
+        ///
+        /// See [MDN] for more details.
+        ///
+        /// [MDN]: https://developer.mozilla.org/en-US/docs/Web/API/MouseEvent/offsetY
+        pub fn offset_y(&self) -> f64 {
+            self.offset_y
+        }
+
+        /// The width of the pointer's contact geometry, in CSS pixels.
+        ///
+        /// See [MDN] for more details.
+        ///
+        /// [MDN]: https://developer.mozilla.org/en-US/docs/Web/API/PointerEvent/width
+        pub fn width(&self) -> f64 {
+            self.width
+        }
+
+        /// The height of the pointer's contact geometry, in CSS pixels.
+        ///
+        /// See [MDN] for more details.
+        ///
+        /// [MDN]: https://developer.mozilla.org/en-US/docs/Web/API/PointerEvent/height
+        pub fn height(&self) -> f64 {
+            self.height
+        }
+
+        /// The normalized pressure of the pointer input, between `0.0` and `1.0`.
+        ///
+        /// See [MDN] for more details.
+        ///
+        /// [MDN]: https://developer.mozilla.org/en-US/docs/Web/API/PointerEvent/pressure
+        pub fn pressure(&self) -> f64 {
+            self.pressure
+        }
+    }
+
+    builder! {
+        #[builder_name = TouchBuilder]
+        #[derive(Debug, Clone)]
+        /// A touch event.
+        ///
+        /// This event type is sent for these bindings:
+        ///
+        /// - `axm-touchstart`
+        /// - `axm-touchend`
+        ///
+        /// See [MDN] for more details about touch events.
+        ///
+        /// [MDN]: https://developer.mozilla.org/en-US/docs/Web/API/TouchEvent
+        pub struct Touch {
+            touches: Vec<TouchPoint>,
+            changed_touches: Vec<TouchPoint>,
+        }
+    }
+
+    impl Touch {
+        /// All the points currently in contact with the surface.
+        ///
+        /// For `axm-touchend` this doesn't include the points that were removed.
+        ///
+        /// See [MDN] for more details.
+        ///
+        /// [MDN]: https://developer.mozilla.org/en-US/docs/Web/API/TouchEvent/touches
+        pub fn touches(&self) -> &[TouchPoint] {
+            &self.touches
+        }
+
+        /// The points that were added for `axm-touchstart`, or removed for `axm-touchend`.
+        ///
+        /// See [MDN] for more details.
+        ///
+        /// [MDN]: https://developer.mozilla.org/en-US/docs/Web/API/TouchEvent/changedTouches
+        pub fn changed_touches(&self) -> &[TouchPoint] {
+            &self.changed_touches
+        }
+    }
+
+    builder! {
+        #[builder_name = TouchPointBuilder]
+        #[derive(Debug, Clone)]
+        /// A single point of contact in a [`Touch`] event.
+        ///
+        /// See [MDN] for more details.
+        ///
+        /// [MDN]: https://developer.mozilla.org/en-US/docs/Web/API/Touch
+        pub struct TouchPoint {
+            identifier: i64,
+            client_x: f64,
+            client_y: f64,
+            page_x: f64,
+            page_y: f64,
+            screen_x: f64,
+            screen_y: f64,
+        }
+    }
+
+    impl TouchPoint {
+        /// A unique identifier for the point, for as long as it's in contact with the surface.
+        ///
+        /// See [MDN] for more details.
+        ///
+        /// [MDN]: https://developer.mozilla.org/en-US/docs/Web/API/Touch/identifier
+        pub fn identifier(&self) -> i64 {
+            self.identifier
+        }
+
+        /// Horizontal coordinate of the point relative to the viewport.
+        ///
+        /// See [MDN] for more details.
+        ///
+        /// [MDN]: https://developer.mozilla.org/en-US/docs/Web/API/Touch/clientX
+        pub fn client_x(&self) -> f64 {
+            self.client_x
+        }
+
+        /// Vertical coordinate of the point relative to the viewport.
+        ///
+        /// See [MDN] for more details.
+        ///
+        /// [MDN]: https://developer.mozilla.org/en-US/docs/Web/API/Touch/clientY
+        pub fn client_y(&self) -> f64 {
+            self.client_y
+        }
+
+        /// Horizontal coordinate of the point relative to the whole document.
+        ///
+        /// See [MDN] for more details.
+        ///
+        /// [MDN]: https://developer.mozilla.org/en-US/docs/Web/API/Touch/pageX
+        pub fn page_x(&self) -> f64 {
+            self.page_x
+        }
+
+        /// Vertical coordinate of the point relative to the whole document.
+        ///
+        /// See [MDN] for more details.
+        ///
+        /// [MDN]: https://developer.mozilla.org/en-US/docs/Web/API/Touch/pageY
+        pub fn page_y(&self) -> f64 {
+            self.page_y
+        }
+
+        /// Horizontal coordinate of the point relative to the screen.
+        ///
+        /// See [MDN] for more details.
+        ///
+        /// [MDN]: https://developer.mozilla.org/en-US/docs/Web/API/Touch/screenX
+        pub fn screen_x(&self) -> f64 {
+            self.screen_x
+        }
+
+        /// Vertical coordinate of the point relative to the screen.
+        ///
+        /// See [MDN] for more details.
+        ///
+        /// [MDN]: https://developer.mozilla.org/en-US/docs/Web/API/Touch/screenY
+        pub fn screen_y(&self) -> f64 {
+            self.screen_y
+        }
+    }
+
+    impl From<life_cycle::TouchPointData> for TouchPoint {
+        fn from(data: life_cycle::TouchPointData) -> Self {
+            Self {
+                identifier: data.identifier,
+                client_x: data.client_x,
+                client_y: data.client_y,
+                page_x: data.page_x,
+                page_y: data.page_y,
+                screen_x: data.screen_x,
+                screen_y: data.screen_y,
+            }
+        }
+    }
+
+    builder! {
+        #[builder_name = WheelBuilder]
+        #[derive(Debug, Clone)]
+        /// A wheel event.
+        ///
+        /// This event type is sent for `axm-wheel` bindings.
+        ///
+        /// The deltas are always in pixels, regardless of the [`deltaMode`] reported by the
+        /// browser.
+        ///
+        /// [`deltaMode`]: https://developer.mozilla.org/en-US/docs/Web/API/WheelEvent/deltaMode
+        pub struct Wheel {
+            delta_x: f64,
+            delta_y: f64,
+            delta_z: f64,
+            client_x: f64,
+            client_y: f64,
+            ctrl: bool,
+        }
+    }
+
+    impl Wheel {
+        /// The horizontal scroll amount.
+        ///
+        /// See [MDN] for more details.
+        ///
+        /// [MDN]: https://developer.mozilla.org/en-US/docs/Web/API/WheelEvent/deltaX
+        pub fn delta_x(&self) -> f64 {
+            self.delta_x
+        }
+
+        /// The vertical scroll amount.
+        ///
+        /// See [MDN] for more details.
+        ///
+        /// [MDN]: https://developer.mozilla.org/en-US/docs/Web/API/WheelEvent/deltaY
+        pub fn delta_y(&self) -> f64 {
+            self.delta_y
+        }
+
+        /// The scroll amount for the z-axis.
+        ///
+        /// See [MDN] for more details.
+        ///
+        /// [MDN]: https://developer.mozilla.org/en-US/docs/Web/API/WheelEvent/deltaZ
+        pub fn delta_z(&self) -> f64 {
+            self.delta_z
+        }
+
+        /// Horizontal coordinate within the application's viewport at which the event occurred.
+        ///
+        /// See [MDN] for more details.
+        ///
+        /// [MDN]: https://developer.mozilla.org/en-US/docs/Web/API/MouseEvent/clientX
+        pub fn client_x(&self) -> f64 {
+            self.client_x
+        }
+
+        /// Vertical coordinate within the application's viewport at which the event occurred.
+        ///
+        /// See [MDN] for more details.
+        ///
+        /// [MDN]: https://developer.mozilla.org/en-US/docs/Web/API/MouseEvent/clientY
+        pub fn client_y(&self) -> f64 {
+            self.client_y
+        }
+
+        /// Whether the control key was pressed. Browsers also set this for pinch-to-zoom
+        /// gestures on trackpads.
+        pub fn ctrl(&self) -> bool {
+            self.ctrl
+        }
+    }
+
+    builder! {
+        #[builder_name = DragBuilder]
+        #[derive(Debug, Clone)]
+        /// A drag and drop event.
+        ///
+        /// This event type is sent for these bindings:
+        ///
+        /// - `axm-dragstart`
+        /// - `axm-drop`
+        ///
+        /// Elements with `axm-dragstart` should also have `draggable="true"`. Elements with
+        /// `axm-drop` accept drops from any element.
+        pub struct Drag {
+            dragged_id: Option<String>,
+            client_x: f64,
+            client_y: f64,
+        }
+    }
+
+    impl Drag {
+        /// The `id` attribute of the element being dragged, if it has one.
+        ///
+        /// Only set when the element was dragged from the same page.
+        pub fn dragged_id(&self) -> Option<&str> {
+            self.dragged_id.as_deref()
+        }
+
+        /// Horizontal coordinate within the application's viewport at which the event occurred.
+        ///
+        /// See [MDN] for more details.
+        ///
+        /// [MDN]: https://developer.mozilla.org/en-US/docs/Web/API/MouseEvent/clientX
+        pub fn client_x(&self) -> f64 {
+            self.client_x
+        }
+
+        /// Vertical coordinate within the application's viewport at which the event occurred.
+        ///
+        /// See [MDN] for more details.
+        ///
+        /// [MDN]: https://developer.mozilla.org/en-US/docs/Web/API/MouseEvent/clientY
+        pub fn client_y(&self) -> f64 {
+            self.client_y
+        }
+    }
+
+    builder! {
+        #[builder_name = ResizeBuilder]
+        #[derive(Debug, Clone)]
+        /// A window resize event.
+        ///
+        /// This event type is sent for `axm-window-resize` bindings.
+        pub struct Resize {
+            width: f64,
+            height: f64,
+        }
+    }
+
+    impl Resize {
+        /// The width of the window's viewport in pixels.
+        ///
+        /// See [MDN] for more details.
+        ///
+        /// [MDN]: https://developer.mozilla.org/en-US/docs/Web/API/Window/innerWidth
+        pub fn width(&self) -> f64 {
+            self.width
+        }
+
+        /// The height of the window's viewport in pixels.
+        ///
+        /// See [MDN] for more details.
+        ///
+        /// [MDN]: https://developer.mozilla.org/en-US/docs/Web/API/Window/innerHeight
+        pub fn height(&self) -> f64 {
+            self.height
+        }
+    }
+
+    builder! {
+        #[builder_name = VisibilityBuilder]
+        #[derive(Debug, Clone)]
+        /// A visibility change event.
+        ///
+        /// This event type is sent for `axm-visibility-change` bindings, when the page becomes
+        /// hidden or visible. For example when the user switches tabs.
+        ///
+        /// See [MDN] for more details.
+        ///
+        /// [MDN]: https://developer.mozilla.org/en-US/docs/Web/API/Document/visibilitychange_event
+        pub struct Visibility {
+            hidden: bool,
+        }
+    }
+
+    impl Visibility {
+        /// Whether the page is hidden.
+        pub fn hidden(&self) -> bool {
+            self.hidden
+        }
+
+        /// Whether the page is visible.
+        pub fn visible(&self) -> bool {
+            !self.hidden
+        }
+    }
+
+    builder! {
+        #[builder_name = NetworkBuilder]
+        #[derive(Debug, Clone)]
+        /// An online or offline event.
+        ///
+        /// This event type is sent for these bindings:
+        ///
+        /// - `axm-online`
+        /// - `axm-offline`
+        ///
+        /// Since events are sent over the network, `axm-offline` is only received once the
+        /// browser is back online.
+        pub struct Network {
+            online: bool,
+        }
+    }
+
+    impl Network {
+        /// Whether the browser is online.
+        ///
+        /// See [MDN] for more details.
+        ///
+        /// [MDN]: https://developer.mozilla.org/en-US/docs/Web/API/Navigator/onLine
+        pub fn online(&self) -> bool {
+            self.online
+        }
+    }
+
+    builder! {
+        #[builder_name = IntersectionBuilder]
+        #[derive(Debug, Clone)]
+        /// A viewport intersection event.
+        ///
+        /// This event type is sent for these bindings:
+        ///
+        /// - `axm-viewport-enter`, when an element scrolls into view.
+        /// - `axm-viewport-leave`, when an element scrolls out of view.
+        ///
+        /// Uses an [`IntersectionObserver`] in the browser.
+        ///
+        /// [`IntersectionObserver`]: https://developer.mozilla.org/en-US/docs/Web/API/Intersection_Observer_API
+        pub struct Intersection {
+            ratio: f64,
+            intersecting: bool,
+        }
+    }
+
+    impl Intersection {
+        /// How much of the element is visible, between `0.0` and `1.0`.
+        ///
+        /// See [MDN] for more details.
+        ///
+        /// [MDN]: https://developer.mozilla.org/en-US/docs/Web/API/IntersectionObserverEntry/intersectionRatio
+        pub fn ratio(&self) -> f64 {
+            self.ratio
+        }
+
+        /// Whether the element is in view.
+        ///
+        /// See [MDN] for more details.
+        ///
+        /// [MDN]: https://developer.mozilla.org/en-US/docs/Web/API/IntersectionObserverEntry/isIntersecting
+        pub fn intersecting(&self) -> bool {
+            self.intersecting
+        }
+    }
+}
+
+pub use self::inner::{
+    deserialize_form, Drag, EventData, Form, FormSerializationError, FromEventData,
+    FromEventDataError, Input, Intersection, Key, Mouse, Network, Pointer, Resize, Scroll, Touch,
+    TouchPoint, Values, ValuesDeserializationError, Visibility, Wheel,
+};
+
+pub mod builders {
+    //! Event data builder types.
+
+    pub use super::inner::{
+        DragBuilder, FormBuilder, IntersectionBuilder, KeyBuilder, MouseBuilder, NetworkBuilder,
+        PointerBuilder, ResizeBuilder, ScrollBuilder, TouchBuilder, TouchPointBuilder,
+        ValuesBuilder, VisibilityBuilder, WheelBuilder,
+    };
+}
+
+pub mod kind {
+    //! The kinds of data events send, used by `html!` to check at compile time that a
+    //! constructor's argument can be created from the event it's bound to.
+    //!
+    //! See [`FromEventData::Kind`](super::FromEventData::Kind) for more details.
+
+    /// Whether values of a kind can be created from the data of events of kind `E`.
+    #[diagnostic::on_unimplemented(
+        message = "`{Self}` data can't be created from `{E}` events",
+        label = "constructor bound to an event it can't be created from",
+        note = "the constructor's argument is created from its `FromEventData::Kind`"
+    )]
+    pub trait Fits<E> {}
+
+    macro_rules! kinds {
+        ($($(#[$m:meta])* $name:ident,)*) => {
+            $(
+                $(#[$m])*
+                #[derive(Debug, Clone, Copy)]
+                #[non_exhaustive]
+                pub struct $name;
+
+                impl Fits<$name> for $name {}
+            )*
+        };
+    }
+
+    kinds! {
+        /// Form events, `axm-submit` and `axm-change` on forms.
+        Form,
+        /// Input events, `axm-input`, `axm-change`, `axm-focus`, and `axm-blur` on inputs,
+        /// textareas, and selects.
+        Input,
+        /// Key events, `axm-keydown`, `axm-keyup`, and their `axm-window-*` counterparts.
+        Key,
+        /// Mouse events such as `axm-mouseenter` and `axm-dblclick`.
+        Mouse,
+        /// Pointer events such as `axm-pointerdown`.
+        Pointer,
+        /// Touch events such as `axm-touchstart`.
+        Touch,
+        /// `axm-wheel` events.
+        Wheel,
+        /// `axm-dragstart` and `axm-drop` events.
+        Drag,
+        /// `axm-scroll` events.
+        Scroll,
+        /// `axm-window-resize` events.
+        Resize,
+        /// `axm-visibility-change` events.
+        Visibility,
+        /// `axm-online` and `axm-offline` events.
+        Network,
+        /// `axm-viewport-enter` and `axm-viewport-leave` events.
+        Intersection,
+        /// Events without data, such as `axm-click`.
+        NoData,
+    }
+
+    /// Text, from either an input or a key event.
+    #[derive(Debug, Clone, Copy)]
+    #[non_exhaustive]
+    pub struct Text;
+
+    impl Fits<Input> for Text {}
+    impl Fits<Key> for Text {}
+
+    /// Any event, for values created from the `axm-value-*` attributes sent along with every
+    /// event.
+    #[derive(Debug, Clone, Copy)]
+    #[non_exhaustive]
+    pub struct Any;
 
-    pub use super::inner::{FormBuilder, KeyBuilder, MouseBuilder, ScrollBuilder};
+    impl<E> Fits<E> for Any {}
 }
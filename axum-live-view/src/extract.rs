@@ -1,14 +1,23 @@
 //! Extractor for embedding live views in HTML templates.
 
-use crate::{html::Html, life_cycle::run_view, LiveView};
+use crate::{
+    html::Html,
+    life_cycle::{run_view, RunViewConfig, Stopped, Submission},
+    record::Recorder,
+    session::{Checkpoint, Session, Sessions},
+    shutdown::Shutdown,
+    LiveView,
+};
 use async_trait::async_trait;
 use axum::{
+    body::{Bytes, HttpBody},
     extract::{
-        ws::{self, WebSocket, WebSocketUpgrade},
-        FromRequestParts,
+        ws::{self, close_code, CloseFrame, WebSocket, WebSocketUpgrade},
+        FromRequest, FromRequestParts,
     },
-    http::{HeaderMap, Uri},
+    http::{header, HeaderMap, HeaderValue, Method, Request, StatusCode, Uri},
     response::{IntoResponse, Response},
+    BoxError,
 };
 use futures_util::{
     sink::SinkExt,
@@ -17,18 +26,22 @@ use futures_util::{
 use http::request::Parts;
 use std::{convert::Infallible, fmt::Debug};
 
-pub use crate::life_cycle::EmbedLiveView;
+pub use crate::life_cycle::{EmbedLiveView, RenderBatching};
 
 /// Extractor for embedding live views in HTML templates.
 #[derive(Debug)]
 pub struct LiveViewUpgrade {
     inner: LiveViewUpgradeInner,
+    config: RunViewConfig,
+    session: Option<Session>,
 }
 
 #[derive(Debug)]
 enum LiveViewUpgradeInner {
     Http,
     Ws(Box<(WebSocketUpgrade, Uri, HeaderMap)>),
+    /// Forms without JavaScript are handled, and one was posted if `Some`.
+    Forms(Option<Box<Submission>>),
 }
 
 #[async_trait]
@@ -39,22 +52,214 @@ where
     type Rejection = Infallible;
 
     async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let sessions = parts.extensions.get::<Sessions>().cloned();
+
         if let Ok(ws) = WebSocketUpgrade::from_request_parts(parts, state).await {
-            let uri = parts.uri.clone();
+            let (uri, token) = take_query_param(&parts.uri, "axm-session");
+            #[cfg(feature = "live-reload")]
+            let (uri, client_build_id) = take_query_param(&uri, "axm-build");
             let headers = parts.headers.clone();
+            let config = RunViewConfig {
+                recorder: parts.extensions.get::<Recorder>().cloned(),
+                shutdown: parts.extensions.get::<Shutdown>().cloned(),
+                #[cfg(feature = "live-reload")]
+                live_reload: parts
+                    .extensions
+                    .get::<crate::live_reload::LiveReload>()
+                    .cloned(),
+                #[cfg(feature = "live-reload")]
+                client_build_id,
+                ..Default::default()
+            };
+
+            let session = match (sessions, token) {
+                (Some(sessions), Some(token)) => {
+                    let session = sessions.resume(&token).await;
+                    if session.is_none() {
+                        tracing::debug!("invalid session token");
+                    }
+                    session
+                }
+                _ => None,
+            };
 
             Ok(Self {
                 inner: LiveViewUpgradeInner::Ws(Box::new((ws, uri, headers))),
+                config,
+                session,
             })
         } else {
             Ok(Self {
                 inner: LiveViewUpgradeInner::Http,
+                config: RunViewConfig::default(),
+                session: sessions.map(|sessions| sessions.start()),
             })
         }
     }
 }
 
+/// Extractor for forms posted to a live view without JavaScript.
+///
+/// Use it together with [`LiveViewUpgrade::forms`]. It rejects requests that aren't `POST`s, so
+/// extract it as `Option<FormPost>` to use the same handler for `GET` and `POST` requests.
+///
+/// # Cross-site requests
+///
+/// Browsers send cookies along with forms posted from other sites, so without a check any site
+/// could make a visitor's browser run a message through [`LiveView::update`] on their behalf.
+/// `FormPost` rejects posts that the `Sec-Fetch-Site` or `Origin` headers show came from another
+/// origin.
+///
+/// Those headers are sent by all current browsers but older browsers may omit them, in which case
+/// the post is accepted. If a form triggers something sensitive also use `SameSite` cookies or
+/// check a CSRF token of your own in `update`.
+#[derive(Debug)]
+pub struct FormPost(Box<Submission>);
+
+#[async_trait]
+impl<S, B> FromRequest<S, B> for FormPost
+where
+    B: HttpBody + Send + 'static,
+    B::Data: Send,
+    B::Error: Into<BoxError>,
+    S: Send + Sync,
+{
+    type Rejection = (StatusCode, &'static str);
+
+    async fn from_request(req: Request<B>, state: &S) -> Result<Self, Self::Rejection> {
+        if req.method() != Method::POST {
+            return Err((StatusCode::METHOD_NOT_ALLOWED, "expected a `POST` request"));
+        }
+
+        if is_cross_site(req.headers()) {
+            tracing::debug!("rejected cross-site form post");
+            return Err((
+                StatusCode::FORBIDDEN,
+                "cross-site form posts aren't allowed",
+            ));
+        }
+
+        let body = Bytes::from_request(req, state)
+            .await
+            .map_err(|_| (StatusCode::BAD_REQUEST, "failed to read form"))?;
+
+        std::str::from_utf8(&body)
+            .ok()
+            .and_then(Submission::from_body)
+            .map(|submission| Self(Box::new(submission)))
+            .ok_or((StatusCode::BAD_REQUEST, "form posted without a message"))
+    }
+}
+
+/// Check whether a request was sent from another origin, going by the headers browsers send.
+fn is_cross_site(headers: &HeaderMap) -> bool {
+    if let Some(site) = headers.get("sec-fetch-site") {
+        return site != "same-origin" && site != "none";
+    }
+
+    match (headers.get(header::ORIGIN), headers.get(header::HOST)) {
+        (Some(origin), Some(host)) => {
+            let origin_host = origin
+                .to_str()
+                .ok()
+                .and_then(|origin| origin.split_once("://"))
+                .map(|(_, host)| host);
+            origin_host != host.to_str().ok()
+        }
+        (Some(_), None) => true,
+        (None, _) => false,
+    }
+}
+
 impl LiveViewUpgrade {
+    /// Handle forms posted without JavaScript.
+    ///
+    /// By default forms bound with `axm-submit` only work once the JavaScript client has
+    /// connected. With this they're rendered with `method=post` and their message in a hidden
+    /// input, so they can also be submitted if JavaScript is disabled or hasn't loaded yet. The
+    /// handler must be routed for `POST` requests as well. The posted message is passed to
+    /// [`LiveView::update`] together with the form's values, before the page is rendered and
+    /// sent back:
+    ///
+    /// ```rust
+    /// use axum::{response::IntoResponse, routing::get, Router};
+    /// use axum_live_view::{extract::FormPost, html, LiveViewUpgrade};
+    /// # use axum_live_view::{event_data::EventData, live_view::Updated, Html, LiveView};
+    ///
+    /// async fn handler(live: LiveViewUpgrade, form: Option<FormPost>) -> impl IntoResponse {
+    ///     live.forms(form).response(|embed_live_view| {
+    ///         html! {
+    ///             { embed_live_view.embed(Signup::default()) }
+    ///             <script src="/assets/live-view.js"></script>
+    ///         }
+    ///     })
+    /// }
+    ///
+    /// let app = Router::new().route("/", get(handler).post(handler));
+    /// # let _: Router = app;
+    /// #
+    /// # #[derive(Default)]
+    /// # struct Signup;
+    /// # impl LiveView for Signup {
+    /// #     type Message = ();
+    /// #     fn update(self, _: (), _: Option<EventData>) -> Updated<Self> { todo!() }
+    /// #     fn render(&self) -> Html<()> { todo!() }
+    /// # }
+    /// ```
+    ///
+    /// Forms with a `method` attribute are left as is.
+    ///
+    /// The view is created by the handler as usual, so any state it needs must be kept somewhere
+    /// that outlives the request. [`LiveView::mount`] isn't called, spawned futures are dropped,
+    /// and [`js_command::navigate_to`](crate::js_command::navigate_to) becomes a `303 See Other`
+    /// redirect. Other JavaScript commands are ignored.
+    ///
+    /// See [`FormPost`] for how cross-site posts are handled.
+    pub fn forms(mut self, post: Option<FormPost>) -> Self {
+        if let LiveViewUpgradeInner::Http = self.inner {
+            self.inner = LiveViewUpgradeInner::Forms(post.map(|FormPost(submission)| submission));
+        }
+        self
+    }
+
+    /// Set how messages are batched before the view is rendered.
+    ///
+    /// Defaults to [`RenderBatching::Disabled`]. See [`RenderBatching`] for more details.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use axum::response::IntoResponse;
+    /// use axum_live_view::{extract::RenderBatching, html, LiveViewUpgrade};
+    /// use std::time::Duration;
+    /// # use axum_live_view::{event_data::EventData, live_view::Updated, Html, LiveView};
+    ///
+    /// async fn handler(live: LiveViewUpgrade) -> impl IntoResponse {
+    ///     let view = Clock::default();
+    ///
+    ///     // Render at most once per frame
+    ///     live.render_batching(RenderBatching::Frame(Duration::from_millis(16)))
+    ///         .response(|embed_live_view| {
+    ///             html! {
+    ///                 { embed_live_view.embed(view) }
+    ///                 <script src="/assets/live-view.js"></script>
+    ///             }
+    ///         })
+    /// }
+    /// #
+    /// # #[derive(Default)]
+    /// # struct Clock;
+    /// # impl LiveView for Clock {
+    /// #     type Message = ();
+    /// #     fn update(self, _: (), _: Option<EventData>) -> Updated<Self> { todo!() }
+    /// #     fn render(&self) -> Html<()> { todo!() }
+    /// # }
+    /// ```
+    pub fn render_batching(mut self, render_batching: RenderBatching) -> Self {
+        self.config.render_batching = render_batching;
+        self
+    }
+
     /// Return a response that contains an embedded live view.
     ///
     /// # Example
@@ -111,20 +316,41 @@ impl LiveViewUpgrade {
     {
         match self.inner {
             LiveViewUpgradeInner::Http => {
-                let embed = EmbedLiveView::noop();
+                let embed = EmbedLiveView::noop(self.session.as_ref());
                 gather_view(embed).into_response()
             }
+            LiveViewUpgradeInner::Forms(None) => {
+                let embed = EmbedLiveView::noop(self.session.as_ref());
+                axum::response::Html(gather_view(embed).render_with_form_posts()).into_response()
+            }
+            LiveViewUpgradeInner::Forms(Some(mut submission)) => {
+                let embed = EmbedLiveView::submit(&mut submission, self.session.as_ref());
+                let html = gather_view(embed);
+
+                let location = submission
+                    .redirect
+                    .as_deref()
+                    .and_then(|uri| HeaderValue::from_str(uri).ok());
+                if let Some(location) = location {
+                    (StatusCode::SEE_OTHER, [(header::LOCATION, location)]).into_response()
+                } else {
+                    axum::response::Html(html.render_with_form_posts()).into_response()
+                }
+            }
             LiveViewUpgradeInner::Ws(data) => {
                 let (ws, uri, headers) = *data;
+                let config = self.config;
                 let mut view = None;
 
-                let embed = EmbedLiveView::new(&mut view);
+                let embed = EmbedLiveView::new(&mut view, self.session.as_ref());
 
                 gather_view(embed);
 
-                if let Some(view) = view {
-                    ws.on_upgrade(|socket| run_view_on_socket(socket, view, uri, headers))
-                        .into_response()
+                if let Some((view, checkpoint)) = view {
+                    ws.on_upgrade(|socket| {
+                        run_view_on_socket(socket, view, uri, headers, config, checkpoint)
+                    })
+                    .into_response()
                 } else {
                     ws.on_upgrade(|_| async {}).into_response()
                 }
@@ -133,8 +359,14 @@ impl LiveViewUpgrade {
     }
 }
 
-async fn run_view_on_socket<L>(socket: WebSocket, view: L, uri: Uri, headers: HeaderMap)
-where
+async fn run_view_on_socket<L>(
+    socket: WebSocket,
+    view: L,
+    uri: Uri,
+    headers: HeaderMap,
+    config: RunViewConfig,
+    checkpoint: Option<Checkpoint<L>>,
+) where
     L: LiveView,
 {
     let (write, read) = socket.split();
@@ -156,7 +388,254 @@ where
         });
     futures_util::pin_mut!(read);
 
-    if let Err(err) = run_view(write, read, view, uri, headers).await {
-        tracing::error!(%err, "encountered while processing socket");
+    // `Shutdown::shutdown` returns once every signal is dropped, and the one in `config` is
+    // dropped when `run_view` returns, so hold on to another until the socket has been closed
+    let _shutdown_guard = config.shutdown.as_ref().map(Shutdown::subscribe);
+
+    match run_view(write.as_mut(), read, view, uri, headers, config, checkpoint).await {
+        Ok(Stopped::Shutdown { deadline }) => {
+            let close = ws::Message::Close(Some(CloseFrame {
+                code: close_code::RESTART,
+                reason: "server restarting".into(),
+            }));
+            let mut socket = write.get_pin_mut();
+            if let Ok(Err(err)) = tokio::time::timeout_at(deadline, socket.send(close)).await {
+                tracing::trace!(%err, "failed to close socket");
+            }
+        }
+        Ok(Stopped::Disconnected) => {}
+        Err(err) => {
+            tracing::error!(%err, "encountered while processing socket");
+        }
+    }
+}
+
+/// Remove a query param the client sends when connecting, such as the session token, from the
+/// URI so it isn't visible to views.
+fn take_query_param(uri: &Uri, name: &str) -> (Uri, Option<String>) {
+    let query = match uri.query() {
+        Some(query) => query,
+        None => return (uri.clone(), None),
+    };
+
+    let mut found = None;
+    let rest = query
+        .split('&')
+        .filter(|pair| match pair.split_once('=') {
+            Some((key, value)) if key == name => {
+                found = Some(value.to_owned());
+                false
+            }
+            _ => true,
+        })
+        .collect::<Vec<_>>()
+        .join("&");
+
+    if found.is_none() {
+        return (uri.clone(), None);
+    }
+
+    let path_and_query = if rest.is_empty() {
+        uri.path().to_owned()
+    } else {
+        format!("{}?{}", uri.path(), rest)
+    };
+    let mut parts = uri.clone().into_parts();
+    parts.path_and_query = path_and_query.parse().ok();
+
+    (
+        Uri::from_parts(parts).unwrap_or_else(|_| uri.clone()),
+        found,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate as axum_live_view;
+    use crate::{event_data::EventData, html, js_command, live_view::Updated};
+    use axum::{body::Body, routing::get, Router};
+    use serde::{Deserialize, Serialize};
+    use tower::ServiceExt;
+
+    #[derive(Default)]
+    struct Greeting {
+        name: String,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    enum Msg {
+        Greet,
+        Leave,
+    }
+
+    #[derive(Deserialize)]
+    struct GreetForm {
+        name: String,
+    }
+
+    impl LiveView for Greeting {
+        type Message = Msg;
+
+        fn update(mut self, msg: Msg, data: Option<EventData>) -> Updated<Self> {
+            match msg {
+                Msg::Greet => {
+                    let form: GreetForm = data.unwrap().as_form().unwrap().deserialize().unwrap();
+                    self.name = form.name;
+                    Updated::new(self)
+                }
+                Msg::Leave => {
+                    Updated::new(self).with(js_command::navigate_to(Uri::from_static("/goodbye")))
+                }
+            }
+        }
+
+        fn render(&self) -> Html<Msg> {
+            html! {
+                if !self.name.is_empty() {
+                    <p>"Hello " { &self.name }</p>
+                }
+                <form axm-submit={ Msg::Greet }>
+                    <input type="text" name="name" />
+                </form>
+            }
+        }
+    }
+
+    async fn send(request: Request<Body>) -> Response {
+        async fn handler(live: LiveViewUpgrade, form: Option<FormPost>) -> Response {
+            live.forms(form)
+                .response(|embed| embed.embed(Greeting::default()))
+        }
+
+        Router::new()
+            .route("/", get(handler).post(handler))
+            .oneshot(request)
+            .await
+            .unwrap()
+    }
+
+    async fn body_text(res: Response) -> String {
+        let mut body = res.into_body();
+        let mut text = String::new();
+        while let Some(chunk) = body.data().await {
+            text.push_str(std::str::from_utf8(&chunk.unwrap()).unwrap());
+        }
+        text
+    }
+
+    fn post(body: &'static str) -> Request<Body> {
+        Request::post("/")
+            .header(header::CONTENT_TYPE, "application/x-www-form-urlencoded")
+            .body(Body::from(body))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn get_renders_forms_that_post() {
+        let res = send(Request::get("/").body(Body::empty()).unwrap()).await;
+        assert_eq!(res.status(), StatusCode::OK);
+        let body = body_text(res).await;
+        assert!(body.contains("method=post"));
+        assert!(body.contains("<input type=hidden name=axm-submit value=%22Greet%22>"));
+    }
+
+    #[tokio::test]
+    async fn forms_only_post_when_enabled() {
+        async fn handler(live: LiveViewUpgrade) -> Response {
+            live.response(|embed| embed.embed(Greeting::default()))
+        }
+
+        let res = Router::new()
+            .route("/", get(handler))
+            .oneshot(Request::get("/").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let body = body_text(res).await;
+        assert!(!body.contains("method=post"));
+        assert!(!body.contains("type=hidden"));
+    }
+
+    #[tokio::test]
+    async fn post_updates_view_with_form() {
+        let res = send(post("axm-submit=%2522Greet%2522&name=Alice+Smith")).await;
+        assert_eq!(res.status(), StatusCode::OK);
+        assert!(body_text(res).await.contains("<p>Hello Alice Smith</p>"));
+    }
+
+    #[tokio::test]
+    async fn post_navigate_to_redirects() {
+        let res = send(post("axm-submit=%2522Leave%2522")).await;
+        assert_eq!(res.status(), StatusCode::SEE_OTHER);
+        assert_eq!(res.headers()[header::LOCATION], "/goodbye");
+    }
+
+    #[tokio::test]
+    async fn post_without_message_renders_view() {
+        let res = send(post("name=Alice")).await;
+        assert_eq!(res.status(), StatusCode::OK);
+        assert!(!body_text(res).await.contains("Hello"));
+    }
+
+    #[tokio::test]
+    async fn cross_site_post_is_ignored() {
+        let mut req = post("axm-submit=%2522Greet%2522&name=Mallory");
+        req.headers_mut()
+            .insert("sec-fetch-site", HeaderValue::from_static("cross-site"));
+        let res = send(req).await;
+        assert_eq!(res.status(), StatusCode::OK);
+        assert!(!body_text(res).await.contains("Hello"));
+    }
+
+    #[test]
+    fn cross_site_requests_are_detected() {
+        let headers = |pairs: &[(&'static str, &'static str)]| {
+            pairs
+                .iter()
+                .map(|(name, value)| {
+                    (
+                        header::HeaderName::from_static(name),
+                        HeaderValue::from_static(value),
+                    )
+                })
+                .collect::<HeaderMap>()
+        };
+
+        assert!(!is_cross_site(&headers(&[])));
+        assert!(!is_cross_site(&headers(&[(
+            "sec-fetch-site",
+            "same-origin"
+        )])));
+        assert!(is_cross_site(&headers(&[("sec-fetch-site", "same-site")])));
+        assert!(!is_cross_site(&headers(&[
+            ("origin", "https://example.com"),
+            ("host", "example.com"),
+        ])));
+        assert!(is_cross_site(&headers(&[
+            ("origin", "https://evil.example"),
+            ("host", "example.com"),
+        ])));
+        assert!(is_cross_site(&headers(&[
+            ("origin", "null"),
+            ("host", "example.com"),
+        ])));
+    }
+
+    #[test]
+    fn query_param_is_removed_from_uri() {
+        let uri = Uri::from_static("/counter?axm-session=abc.123");
+        let (uri, token) = take_query_param(&uri, "axm-session");
+        assert_eq!(uri, "/counter");
+        assert_eq!(token.unwrap(), "abc.123");
+
+        let uri = Uri::from_static("/counter?a=1&axm-session=abc&b=2");
+        let (uri, token) = take_query_param(&uri, "axm-session");
+        assert_eq!(uri, "/counter?a=1&b=2");
+        assert_eq!(token.unwrap(), "abc");
+
+        let uri = Uri::from_static("/counter?a=1");
+        let (uri, token) = take_query_param(&uri, "axm-session");
+        assert_eq!(uri, "/counter?a=1");
+        assert!(token.is_none());
     }
 }
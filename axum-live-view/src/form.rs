@@ -0,0 +1,1007 @@
+//! Forms with validation and per-field error messages.
+//!
+//! A [`Changeset`] holds the current value of a form along with the raw params submitted by the
+//! browser, the validation errors for each field, and which fields the user has touched. Errors
+//! are only shown for fields that have been touched, or once the form has been submitted, so users
+//! aren't greeted by a wall of errors before they've typed anything.
+//!
+//! The client sends the name of the field that changed with `axm-change` events, which
+//! [`Changeset::change`] uses to mark it as touched.
+//!
+//! # Example
+//!
+//! ```rust
+//! use axum_live_view::{
+//!     event_data::EventData,
+//!     form::{self, Changeset, Errors, Validate},
+//!     html,
+//!     live_view::Updated,
+//!     Html, LiveView,
+//! };
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize, Default, Clone)]
+//! struct Signup {
+//!     name: String,
+//!     email: String,
+//! }
+//!
+//! impl Validate for Signup {
+//!     fn validate(&self, errors: &mut Errors) {
+//!         if self.name.is_empty() {
+//!             errors.add("name", "can't be blank");
+//!         }
+//!         if !self.email.contains('@') {
+//!             errors.add("email", "must be an email address");
+//!         }
+//!     }
+//! }
+//!
+//! struct SignupView {
+//!     changeset: Changeset<Signup>,
+//! }
+//!
+//! impl LiveView for SignupView {
+//!     type Message = Msg;
+//!
+//!     fn update(mut self, msg: Msg, data: Option<EventData>) -> Updated<Self> {
+//!         match msg {
+//!             Msg::Validate => {
+//!                 let _ = self.changeset.change(data.as_ref());
+//!             }
+//!             Msg::Submit => {
+//!                 if self.changeset.submit(data.as_ref()).is_ok() && self.changeset.is_valid() {
+//!                     // save `self.changeset.data()`...
+//!                 }
+//!             }
+//!         }
+//!
+//!         Updated::new(self)
+//!     }
+//!
+//!     fn render(&self) -> Html<Msg> {
+//!         html! {
+//!             <form axm-change={ Msg::Validate } axm-submit={ Msg::Submit }>
+//!                 { form::input(&self.changeset, "text", "name") }
+//!                 { form::input(&self.changeset, "email", "email") }
+//!                 <input type="submit" value="Sign up" />
+//!             </form>
+//!         }
+//!     }
+//! }
+//!
+//! #[derive(Serialize, Deserialize, Debug, PartialEq)]
+//! enum Msg {
+//!     Validate,
+//!     Submit,
+//! }
+//! ```
+
+use crate::{
+    event_data::{EventData, FromEventDataError},
+    html, Html,
+};
+use serde::{de::DeserializeOwned, Deserialize, Deserializer, Serialize};
+use serde_path_to_error::Segment;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt,
+};
+
+// `html!` refers to the crate by name
+use crate as axum_live_view;
+
+/// Validation of form values.
+///
+/// See the [module docs](self) for an example.
+pub trait Validate {
+    /// Add errors for invalid fields to `errors`.
+    fn validate(&self, errors: &mut Errors);
+}
+
+/// Validation errors, grouped by field name.
+///
+/// Fields are named like their inputs, that is by their name as seen by serde. For a field with
+/// `#[serde(rename = "displayName")]` errors must be added for `"displayName"`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Errors(BTreeMap<String, Vec<String>>);
+
+impl Errors {
+    /// Add an error message for `field`, using its name as seen by serde.
+    pub fn add<K, V>(&mut self, field: K, message: V)
+    where
+        K: Into<String>,
+        V: Into<String>,
+    {
+        self.0.entry(field.into()).or_default().push(message.into());
+    }
+
+    /// Get the error messages for `field`.
+    pub fn get(&self, field: &str) -> &[String] {
+        self.0.get(field).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    /// Whether there are no errors.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Iterate over the fields with errors and their messages.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &[String])> {
+        self.0
+            .iter()
+            .map(|(field, messages)| (field.as_str(), messages.as_slice()))
+    }
+}
+
+/// The state of a form being edited.
+///
+/// See the [module docs](self) for more details.
+#[derive(Debug, Clone)]
+pub struct Changeset<T> {
+    data: T,
+    params: Vec<(String, String)>,
+    errors: Errors,
+    touched: BTreeSet<String>,
+    submitted: bool,
+}
+
+impl<T> Changeset<T>
+where
+    T: Serialize + DeserializeOwned + Validate,
+{
+    /// Create a new `Changeset` with an initial value.
+    ///
+    /// `data` is validated right away, but the errors aren't shown until fields are touched.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `data` cannot be serialized as a form.
+    pub fn new(data: T) -> Self {
+        let query = serde_qs::to_string(&data).expect("failed to serialize form");
+        let mut changeset = Self {
+            data,
+            params: parse_params(&query),
+            errors: Errors::default(),
+            touched: BTreeSet::new(),
+            submitted: false,
+        };
+        changeset.validate();
+        changeset
+    }
+
+    /// Apply the data of an `axm-change` event.
+    ///
+    /// The field that changed is marked as touched. If a field cannot be deserialized, such as
+    /// `"abc"` for a number, the params are still updated but the data is kept as is and the field
+    /// gets an `"is invalid"` error.
+    ///
+    /// Returns an error if `data` isn't a form or the form cannot be deserialized for reasons other
+    /// than an invalid field, such as a missing field.
+    pub fn change(&mut self, data: Option<&EventData>) -> Result<(), FromEventDataError> {
+        let form = data
+            .and_then(EventData::as_form)
+            .ok_or_else(|| FromEventDataError::unexpected("form", data))?;
+
+        if let Some(field) = form.changed_field() {
+            // multi-selects are named `field[]`
+            let field = field.strip_suffix("[]").unwrap_or(field);
+            self.touched.insert(field.to_owned());
+        }
+        self.params = parse_params(&form.decoded_query()?);
+        match form.deserialize::<Tracked<T>>()?.0 {
+            Ok(data) => {
+                self.data = data;
+                self.validate();
+            }
+            Err(field) => {
+                self.validate();
+                self.errors.0.insert(field, vec!["is invalid".to_owned()]);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Apply the data of an `axm-submit` event.
+    ///
+    /// This works like [`Changeset::change`] but marks the form as submitted, which shows the
+    /// errors for all fields.
+    pub fn submit(&mut self, data: Option<&EventData>) -> Result<(), FromEventDataError> {
+        self.submitted = true;
+        self.change(data)
+    }
+
+    fn validate(&mut self) {
+        let mut errors = Errors::default();
+        self.data.validate(&mut errors);
+        self.errors = errors;
+    }
+}
+
+impl<T> Changeset<T> {
+    /// Get the current value of the form.
+    ///
+    /// This is the last value that could be deserialized, which may not be valid.
+    pub fn data(&self) -> &T {
+        &self.data
+    }
+
+    /// Consume the changeset and return the current value of the form.
+    pub fn into_data(self) -> T {
+        self.data
+    }
+
+    /// Get the raw value of a field, as sent by the browser.
+    pub fn param(&self, name: &str) -> Option<&str> {
+        self.params
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Get all the raw values of a sequence field, such as a multi-select.
+    ///
+    /// This includes params named `name`, `name[]`, and `name[0]`, `name[1]`, etc.
+    pub fn param_values<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a str> + 'a {
+        self.params
+            .iter()
+            .filter(move |(key, _)| is_param_of(key, name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Get all validation errors, including those for untouched fields.
+    pub fn errors(&self) -> &Errors {
+        &self.errors
+    }
+
+    /// Get the errors to show for `field`.
+    ///
+    /// This is empty until the field has been touched or the form has been submitted.
+    pub fn field_errors(&self, field: &str) -> &[String] {
+        if self.submitted || self.is_touched(field) {
+            self.errors.get(field)
+        } else {
+            &[]
+        }
+    }
+
+    /// Whether the data passed validation.
+    pub fn is_valid(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// Whether `field` has been changed by the user.
+    pub fn is_touched(&self, field: &str) -> bool {
+        self.touched.contains(field)
+    }
+
+    /// Whether the form has been submitted.
+    pub fn is_submitted(&self) -> bool {
+        self.submitted
+    }
+}
+
+/// Derive [`LiveForm`](trait@LiveForm) for a struct, or [`FormField`] and [`FormOptions`] for an
+/// enum.
+///
+/// See [`LiveForm`](trait@LiveForm) for more details.
+pub use axum_live_view_macros::LiveForm;
+
+/// Forms whose inputs can be generated from their fields.
+///
+/// This is usually derived with `#[derive(LiveForm)]`, which renders a `<label>` for each field
+/// containing the input for its type, as determined by [`FormField`]:
+///
+/// - `String` renders a text input.
+/// - `bool` renders a checkbox.
+/// - Numbers render number inputs.
+/// - Enums with `#[derive(LiveForm)]` render a `<select>` with an option per variant.
+/// - `Vec`s of such enums render a `<select multiple>`.
+/// - `Option`s render like their inner type, with an empty value for `None`.
+///
+/// Inputs are named like the fields, taking `#[serde(rename = "...")]` and
+/// `#[serde(rename_all = "...")]` into account, so the form deserializes into the struct. Errors
+/// are looked up by the same names, see [`Errors`]. Fields
+/// with `#[serde(skip)]` or `#[serde(skip_deserializing)]` are left out. The label defaults to the
+/// field's name and can be changed with `#[live_form(label = "...")]`.
+///
+/// # Example
+///
+/// ```rust
+/// use axum_live_view::{
+///     form::{Changeset, Errors, LiveForm, Validate},
+///     html, Html,
+/// };
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Serialize, Deserialize, LiveForm)]
+/// struct Profile {
+///     #[live_form(label = "Display name")]
+///     name: String,
+///     age: u32,
+///     #[serde(default)]
+///     newsletter: bool,
+///     plan: Plan,
+///     #[serde(default)]
+///     addons: Vec<Addon>,
+/// }
+///
+/// #[derive(Serialize, Deserialize, LiveForm)]
+/// #[serde(rename_all = "lowercase")]
+/// enum Plan {
+///     Free,
+///     Pro,
+/// }
+///
+/// #[derive(Serialize, Deserialize, LiveForm)]
+/// enum Addon {
+///     Storage,
+///     Support,
+/// }
+///
+/// impl Validate for Profile {
+///     fn validate(&self, errors: &mut Errors) {
+///         if self.name.is_empty() {
+///             errors.add("name", "can't be blank");
+///         }
+///     }
+/// }
+///
+/// # #[derive(serde::Serialize, serde::Deserialize, Debug, PartialEq)]
+/// # enum Msg { Validate, Submit }
+/// fn render(changeset: &Changeset<Profile>) -> Html<Msg> {
+///     html! {
+///         <form axm-change={ Msg::Validate } axm-submit={ Msg::Submit }>
+///             { Profile::render_fields(changeset) }
+///             <input type="submit" value="Save" />
+///         </form>
+///     }
+/// }
+/// ```
+pub trait LiveForm: Sized {
+    /// Render the inputs for all fields, along with their errors.
+    fn render_fields<M>(changeset: &Changeset<Self>) -> Html<M>;
+}
+
+/// Types that can be edited with a form input.
+///
+/// See [`LiveForm`](trait@LiveForm) for more details.
+pub trait FormField {
+    /// Render the input for the field called `name`, along with its errors.
+    fn render_field<T, M>(changeset: &Changeset<T>, name: &str) -> Html<M>;
+
+    /// Render the input for an `Option` of this type.
+    ///
+    /// `None` isn't sent by the browser, so the input is rendered without a value, and an empty
+    /// value deserializes back into `None`. Defaults to [`FormField::render_field`]. Selects
+    /// should add an empty option.
+    fn render_optional_field<T, M>(changeset: &Changeset<T>, name: &str) -> Html<M> {
+        Self::render_field(changeset, name)
+    }
+}
+
+/// Types with a fixed set of values, such as fieldless enums.
+///
+/// This is usually derived with `#[derive(LiveForm)]`. See [`LiveForm`](trait@LiveForm) for
+/// more details.
+pub trait FormOptions {
+    /// The values as serialized by serde.
+    fn options() -> &'static [&'static str];
+}
+
+impl FormField for String {
+    fn render_field<T, M>(changeset: &Changeset<T>, name: &str) -> Html<M> {
+        input(changeset, "text", name)
+    }
+}
+
+impl FormField for bool {
+    fn render_field<T, M>(changeset: &Changeset<T>, name: &str) -> Html<M> {
+        checkbox(changeset, name)
+    }
+}
+
+macro_rules! impl_form_field_number {
+    ( $($ty:ty),* $(,)? ) => {
+        $(
+            impl FormField for $ty {
+                fn render_field<T, M>(changeset: &Changeset<T>, name: &str) -> Html<M> {
+                    input(changeset, "number", name)
+                }
+            }
+        )*
+    };
+}
+
+impl_form_field_number!(i8, i16, i32, i64, u8, u16, u32, u64, usize, isize, f32, f64);
+
+impl<F> FormField for Option<F>
+where
+    F: FormField,
+{
+    fn render_field<T, M>(changeset: &Changeset<T>, name: &str) -> Html<M> {
+        F::render_optional_field(changeset, name)
+    }
+}
+
+impl<O> FormField for Vec<O>
+where
+    O: FormOptions,
+{
+    fn render_field<T, M>(changeset: &Changeset<T>, name: &str) -> Html<M> {
+        multi_select(changeset, name, options::<O>())
+    }
+}
+
+/// The `(value, label)` pairs of `O` for use with [`select`] or [`multi_select`].
+pub fn options<O>() -> impl Iterator<Item = (&'static str, &'static str)>
+where
+    O: FormOptions,
+{
+    O::options().iter().map(|option| (*option, *option))
+}
+
+/// Render an `<input>` of the given type along with its errors.
+///
+/// The input is marked with `aria-invalid` while it has errors.
+pub fn input<T, M>(changeset: &Changeset<T>, input_type: &str, name: &str) -> Html<M> {
+    let value = changeset.param(name).filter(|value| !value.is_empty());
+    let invalid = !changeset.field_errors(name).is_empty();
+
+    html! {
+        <input
+            type={ Escaped(input_type) }
+            name={ Escaped(name) }
+            value=if let Some(value) = value { Some({ Escaped(value) }) } else { None }
+            aria-invalid=if invalid { "true" }
+        />
+        { error_tag(changeset, name) }
+    }
+}
+
+/// Render a `<textarea>` along with its errors.
+pub fn textarea<T, M>(changeset: &Changeset<T>, name: &str) -> Html<M> {
+    let value = changeset.param(name).unwrap_or_default();
+    let invalid = !changeset.field_errors(name).is_empty();
+
+    html! {
+        <textarea name={ Escaped(name) } aria-invalid=if invalid { "true" }>
+            { Escaped(value) }
+        </textarea>
+        { error_tag(changeset, name) }
+    }
+}
+
+/// Render a checkbox for a `bool` field along with its errors.
+///
+/// Browsers don't send unchecked checkboxes so the field should use `#[serde(default)]`.
+pub fn checkbox<T, M>(changeset: &Changeset<T>, name: &str) -> Html<M> {
+    let checked = changeset.param(name) == Some("true");
+    let invalid = !changeset.field_errors(name).is_empty();
+
+    html! {
+        <input
+            type="checkbox"
+            name={ Escaped(name) }
+            value="true"
+            checked=if checked { () }
+            aria-invalid=if invalid { "true" }
+        />
+        { error_tag(changeset, name) }
+    }
+}
+
+/// Render a `<select>` with the given `(value, label)` options along with its errors.
+pub fn select<T, M, I, V, L>(changeset: &Changeset<T>, name: &str, options: I) -> Html<M>
+where
+    I: IntoIterator<Item = (V, L)>,
+    V: fmt::Display,
+    L: fmt::Display,
+{
+    let current = changeset.param(name);
+    render_select(changeset, name, false, options, |value| {
+        current == Some(value)
+    })
+}
+
+/// Render a `<select multiple>` for a sequence field, such as a `Vec`, along with its errors.
+///
+/// The select is named `name[]` so the selected values deserialize into a sequence.
+pub fn multi_select<T, M, I, V, L>(changeset: &Changeset<T>, name: &str, options: I) -> Html<M>
+where
+    I: IntoIterator<Item = (V, L)>,
+    V: fmt::Display,
+    L: fmt::Display,
+{
+    let current = changeset.param_values(name).collect::<Vec<_>>();
+    render_select(changeset, name, true, options, |value| {
+        current.contains(&value)
+    })
+}
+
+fn render_select<T, M, I, V, L, F>(
+    changeset: &Changeset<T>,
+    name: &str,
+    multiple: bool,
+    options: I,
+    is_selected: F,
+) -> Html<M>
+where
+    I: IntoIterator<Item = (V, L)>,
+    V: fmt::Display,
+    L: fmt::Display,
+    F: Fn(&str) -> bool,
+{
+    let invalid = !changeset.field_errors(name).is_empty();
+    let options = options
+        .into_iter()
+        .map(|(value, label)| {
+            let value = value.to_string();
+            let selected = is_selected(&value);
+            (value, label.to_string(), selected)
+        })
+        .collect::<Vec<_>>();
+    let select_name = if multiple {
+        format!("{}[]", name)
+    } else {
+        name.to_owned()
+    };
+
+    html! {
+        <select
+            name={ Escaped(&select_name) }
+            multiple=if multiple { () }
+            aria-invalid=if invalid { "true" }
+        >
+            for (value, label, selected) in &options {
+                // options without a value use their label, which is empty for empty values
+                <option
+                    value=if !value.is_empty() { Some({ Escaped(value) }) } else { None }
+                    selected=if *selected { () }
+                >
+                    { Escaped(label) }
+                </option>
+            }
+        </select>
+        { error_tag(changeset, name) }
+    }
+}
+
+/// Render the errors to show for `field`.
+///
+/// Each error is rendered as `<span class="axm-error">`.
+pub fn error_tag<T, M>(changeset: &Changeset<T>, field: &str) -> Html<M> {
+    html! {
+        for error in changeset.field_errors(field) {
+            <span class="axm-error">{ Escaped(error) }</span>
+        }
+    }
+}
+
+/// Deserializes `T`, keeping track of which field failed to deserialize.
+///
+/// Errors that aren't caused by a specific field are returned as usual.
+struct Tracked<T>(Result<T, String>);
+
+impl<'de, T> Deserialize<'de> for Tracked<T>
+where
+    T: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        match serde_path_to_error::deserialize(deserializer) {
+            Ok(data) => Ok(Self(Ok(data))),
+            Err(err) => match err.path().iter().next() {
+                Some(Segment::Map { key }) => Ok(Self(Err(key.clone()))),
+                _ => Err(err.into_inner()),
+            },
+        }
+    }
+}
+
+fn is_param_of(key: &str, name: &str) -> bool {
+    if key == name {
+        return true;
+    }
+
+    key.strip_prefix(name)
+        .and_then(|rest| rest.strip_prefix('['))
+        .and_then(|rest| rest.strip_suffix(']'))
+        .is_some_and(|idx| idx.chars().all(|c| c.is_ascii_digit()))
+}
+
+/// Parse a decoded query string into its key-value pairs.
+fn parse_params(query: &str) -> Vec<(String, String)> {
+    query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            (decode_param(key), decode_param(value))
+        })
+        .collect()
+}
+
+fn decode_param(s: &str) -> String {
+    let s = s.replace('+', " ");
+    percent_encoding::percent_decode_str(&s)
+        .decode_utf8_lossy()
+        .into_owned()
+}
+
+/// Escapes text so it can be used in unquoted attribute values, as rendered by `html!`, and in
+/// element contents.
+struct Escaped<'a>(&'a str);
+
+impl fmt::Display for Escaped<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for c in self.0.chars() {
+            match c {
+                '&' => f.write_str("&amp;")?,
+                '<' => f.write_str("&lt;")?,
+                '>' => f.write_str("&gt;")?,
+                '"' | '\'' | '=' | '`' => write!(f, "&#{};", c as u32)?,
+                c if c.is_ascii_whitespace() => write!(f, "&#{};", c as u32)?,
+                c => write!(f, "{}", c)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{event_data::Form, life_cycle::EventMessageFromSocketData};
+
+    #[derive(Debug, Serialize, Deserialize, Default, PartialEq)]
+    struct Signup {
+        name: String,
+        email: String,
+        #[serde(default)]
+        terms: bool,
+    }
+
+    impl Validate for Signup {
+        fn validate(&self, errors: &mut Errors) {
+            if self.name.is_empty() {
+                errors.add("name", "can't be blank");
+            }
+            if !self.email.contains('@') {
+                errors.add("email", "must be an email address");
+            }
+        }
+    }
+
+    fn form_event(signup: &Signup, changed: Option<&str>) -> EventData {
+        let mut builder = Form::builder().serialize(signup).unwrap();
+        if let Some(changed) = changed {
+            builder = builder.changed_field(changed);
+        }
+        builder.build().into()
+    }
+
+    #[test]
+    fn errors_are_hidden_until_touched() {
+        let mut changeset = Changeset::new(Signup::default());
+        assert!(!changeset.is_valid());
+        assert!(changeset.field_errors("name").is_empty());
+        assert!(changeset.field_errors("email").is_empty());
+
+        let signup = Signup {
+            name: String::new(),
+            email: "bob".to_owned(),
+            terms: false,
+        };
+        changeset
+            .change(Some(&form_event(&signup, Some("email"))))
+            .unwrap();
+        assert_eq!(changeset.data(), &signup);
+        assert!(changeset.is_touched("email"));
+        assert!(!changeset.is_touched("name"));
+        assert_eq!(
+            changeset.field_errors("email"),
+            ["must be an email address"]
+        );
+        assert!(changeset.field_errors("name").is_empty());
+        assert_eq!(changeset.errors().get("name"), ["can't be blank"]);
+    }
+
+    #[test]
+    fn submit_shows_all_errors() {
+        let mut changeset = Changeset::new(Signup::default());
+        changeset
+            .submit(Some(&form_event(&Signup::default(), None)))
+            .unwrap();
+        assert!(changeset.is_submitted());
+        assert_eq!(changeset.field_errors("name"), ["can't be blank"]);
+        assert_eq!(
+            changeset.field_errors("email"),
+            ["must be an email address"]
+        );
+
+        let signup = Signup {
+            name: "Bob".to_owned(),
+            email: "bob@example.com".to_owned(),
+            terms: true,
+        };
+        changeset.submit(Some(&form_event(&signup, None))).unwrap();
+        assert!(changeset.is_valid());
+        assert_eq!(changeset.into_data(), signup);
+    }
+
+    #[test]
+    fn unexpected_event_data() {
+        let mut changeset = Changeset::new(Signup::default());
+        assert!(changeset.change(None).is_err());
+    }
+
+    #[test]
+    fn invalid_fields_get_an_error() {
+        #[derive(Debug, Serialize, Deserialize, Default)]
+        struct Age {
+            age: u32,
+        }
+
+        impl Validate for Age {
+            fn validate(&self, _: &mut Errors) {}
+        }
+
+        let mut changeset = Changeset::new(Age::default());
+        assert_eq!(changeset.param("age"), Some("0"));
+
+        #[derive(Serialize)]
+        struct RawAge {
+            age: &'static str,
+        }
+
+        let form = Form::builder()
+            .serialize(&RawAge { age: "abc" })
+            .unwrap()
+            .changed_field("age")
+            .build();
+        changeset.change(Some(&form.into())).unwrap();
+        assert_eq!(changeset.param("age"), Some("abc"));
+        assert_eq!(changeset.data().age, 0);
+        assert!(!changeset.is_valid());
+        assert_eq!(changeset.field_errors("age"), ["is invalid"]);
+
+        let form = Form::builder()
+            .serialize(&Age { age: 42 })
+            .unwrap()
+            .changed_field("age")
+            .build();
+        changeset.change(Some(&form.into())).unwrap();
+        assert_eq!(changeset.data().age, 42);
+        assert!(changeset.is_valid());
+    }
+
+    #[test]
+    fn render_input() {
+        let mut changeset = Changeset::new(Signup::default());
+        let view: Html<()> = input(&changeset, "text", "name");
+        assert_eq!(view.render(), "<input type=text name=name>");
+
+        let signup = Signup {
+            name: "Bob Smith".to_owned(),
+            email: "bob".to_owned(),
+            terms: false,
+        };
+        changeset
+            .change(Some(&form_event(&signup, Some("email"))))
+            .unwrap();
+
+        let view: Html<()> = input(&changeset, "text", "name");
+        assert_eq!(
+            view.render(),
+            "<input type=text name=name value=Bob&#32;Smith>"
+        );
+
+        let view: Html<()> = input(&changeset, "email", "email");
+        assert_eq!(
+            view.render(),
+            "<input type=email name=email value=bob aria-invalid=true><span class=axm-error>must&#32;be&#32;an&#32;email&#32;address</span>"
+        );
+    }
+
+    #[test]
+    fn render_checkbox_and_select() {
+        let signup = Signup {
+            name: "Bob".to_owned(),
+            email: "bob@example.com".to_owned(),
+            terms: true,
+        };
+        let changeset = Changeset::new(signup);
+
+        let view: Html<()> = checkbox(&changeset, "terms");
+        assert_eq!(
+            view.render(),
+            "<input type=checkbox name=terms value=true checked>"
+        );
+
+        let view: Html<()> = select(&changeset, "name", [("Alice", "Alice"), ("Bob", "Bob")]);
+        assert_eq!(
+            view.render(),
+            "<select name=name><option value=Alice>Alice</option><option value=Bob selected>Bob</option></select>"
+        );
+    }
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq, LiveForm)]
+    struct Profile {
+        #[serde(rename = "display_name")]
+        #[live_form(label = "Name")]
+        name: String,
+        #[serde(default)]
+        newsletter: bool,
+        plan: Plan,
+        #[serde(default)]
+        addons: Vec<Addon>,
+        #[serde(skip)]
+        #[allow(dead_code)]
+        internal: u32,
+    }
+
+    impl Validate for Profile {
+        fn validate(&self, errors: &mut Errors) {
+            if self.name.is_empty() {
+                errors.add("display_name", "can't be blank");
+            }
+        }
+    }
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq, LiveForm)]
+    #[serde(rename_all = "kebab-case")]
+    enum Plan {
+        Free,
+        TeamPro,
+    }
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq, LiveForm)]
+    enum Addon {
+        Storage,
+        Support,
+    }
+
+    #[test]
+    fn derive_live_form() {
+        let mut changeset = Changeset::new(Profile {
+            name: "Bob".to_owned(),
+            newsletter: false,
+            plan: Plan::TeamPro,
+            addons: vec![Addon::Support],
+            internal: 0,
+        });
+
+        let view: Html<()> = Profile::render_fields(&changeset);
+        assert_eq!(
+            view.render(),
+            "<label>Name<input type=text name=display_name value=Bob></label>\
+            <label>newsletter<input type=checkbox name=newsletter value=true></label>\
+            <label>plan<select name=plan><option value=free>free</option><option value=team-pro selected>team-pro</option></select></label>\
+            <label>addons<select name=addons[] multiple><option value=Storage>Storage</option><option value=Support selected>Support</option></select></label>"
+        );
+
+        // the query as sent by the browser
+        let data = EventData::from_socket(
+            EventMessageFromSocketData::Form {
+                query: "display_name=Alice&newsletter=true&plan=free&addons%5B%5D=Storage&addons%5B%5D=Support".to_owned(),
+                changed: Some("addons[]".to_owned()),
+            },
+            Default::default(),
+        );
+        changeset.change(data.as_ref()).unwrap();
+
+        assert!(changeset.is_touched("addons"));
+        assert_eq!(
+            changeset.data(),
+            &Profile {
+                name: "Alice".to_owned(),
+                newsletter: true,
+                plan: Plan::Free,
+                addons: vec![Addon::Storage, Addon::Support],
+                internal: 0,
+            }
+        );
+        assert_eq!(
+            changeset.param_values("addons").collect::<Vec<_>>(),
+            ["Storage", "Support"]
+        );
+    }
+
+    #[test]
+    fn optional_fields() {
+        #[derive(Debug, Serialize, Deserialize, PartialEq, LiveForm)]
+        struct Details {
+            age: Option<u32>,
+            plan: Option<Plan>,
+        }
+
+        impl Validate for Details {
+            fn validate(&self, _: &mut Errors) {}
+        }
+
+        let mut changeset = Changeset::new(Details {
+            age: None,
+            plan: None,
+        });
+
+        let view: Html<()> = Details::render_fields(&changeset);
+        assert_eq!(
+            view.render(),
+            "<label>age<input type=number name=age></label>\
+            <label>plan<select name=plan><option></option><option value=free>free</option><option value=team-pro>team-pro</option></select></label>"
+        );
+
+        let data = EventData::from_socket(
+            EventMessageFromSocketData::Form {
+                query: "age=42&plan=free".to_owned(),
+                changed: Some("plan".to_owned()),
+            },
+            Default::default(),
+        );
+        changeset.change(data.as_ref()).unwrap();
+        assert_eq!(
+            changeset.data(),
+            &Details {
+                age: Some(42),
+                plan: Some(Plan::Free),
+            }
+        );
+
+        // the browser sends empty values for empty inputs
+        let data = EventData::from_socket(
+            EventMessageFromSocketData::Form {
+                query: "age=&plan=".to_owned(),
+                changed: Some("age".to_owned()),
+            },
+            Default::default(),
+        );
+        changeset.change(data.as_ref()).unwrap();
+        assert_eq!(
+            changeset.data(),
+            &Details {
+                age: None,
+                plan: None,
+            }
+        );
+    }
+
+    #[test]
+    fn errors_of_renamed_fields() {
+        let mut changeset = Changeset::new(Profile {
+            name: "Bob".to_owned(),
+            newsletter: false,
+            plan: Plan::Free,
+            addons: Vec::new(),
+            internal: 0,
+        });
+
+        let data = EventData::from_socket(
+            EventMessageFromSocketData::Form {
+                query: "display_name=&plan=free".to_owned(),
+                changed: Some("display_name".to_owned()),
+            },
+            Default::default(),
+        );
+        changeset.change(data.as_ref()).unwrap();
+        assert_eq!(changeset.field_errors("display_name"), ["can't be blank"]);
+
+        let data = EventData::from_socket(
+            EventMessageFromSocketData::Form {
+                query: "display_name=Bob&plan=gold".to_owned(),
+                changed: Some("plan".to_owned()),
+            },
+            Default::default(),
+        );
+        changeset.change(data.as_ref()).unwrap();
+        assert_eq!(changeset.field_errors("plan"), ["is invalid"]);
+
+        let view: Html<()> = Profile::render_fields(&changeset);
+        assert!(view
+            .render()
+            .contains("<select name=plan aria-invalid=true>"));
+    }
+}
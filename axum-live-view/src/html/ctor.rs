@@ -0,0 +1,838 @@
+//! Message constructors bound in `html!`, such as `axm-input={ Msg::TextChanged }`.
+//!
+//! Constructors can't be serialized, so they're called with [`FromEventData::placeholder`] when
+//! rendering. The message is sent to the client along with the location of the placeholder in its
+//! JSON. When the event happens the placeholder is replaced with the value created from the
+//! event data and the message is deserialized as usual.
+//!
+//! The placeholder is located by type name, starting from the outermost message. That keeps
+//! working when the HTML is mapped, for example by `live_view::combine`, since each map records
+//! the type it was mapped from. If the message contains the argument's type more than once, the
+//! value that equals the placeholder is picked. Messages where that's still ambiguous fail to
+//! serialize, since the placeholder can't be told apart from the other values.
+//!
+//! How to create the argument from event data depends on its type, so each view keeps the
+//! [`Resolvers`] for the constructors it has rendered.
+
+use super::{Binding, DynamicFragment, Html, IndexMap};
+use crate::event_data::{EventData, FromEventData, FromEventDataError};
+use serde::{
+    de::{self, DeserializeOwned},
+    ser::{self, SerializeMap},
+    Deserialize, Serialize,
+};
+use serde_json::Value;
+use std::{
+    any::type_name,
+    collections::{hash_map::DefaultHasher, BTreeMap},
+    fmt,
+    hash::{Hash, Hasher},
+};
+
+/// Creates the placeholder's value from event data.
+type Resolver = fn(Option<&EventData>) -> Result<Value, CtorError>;
+
+/// A message created by a constructor with a placeholder argument.
+#[derive(Clone)]
+pub struct Ctor<T> {
+    msg: T,
+    /// Type names from the constructor's argument to the type `msg` was last mapped from.
+    types: Vec<&'static str>,
+    /// The placeholder as JSON, to tell it apart from other values of the same type.
+    placeholder: Option<Value>,
+    resolver: Resolver,
+}
+
+impl<T> Ctor<T> {
+    pub(crate) fn new<A>(f: impl FnOnce(A) -> T) -> Self
+    where
+        A: FromEventData,
+    {
+        let placeholder = A::placeholder();
+        Self {
+            placeholder: serde_json::to_value(&placeholder).ok(),
+            msg: f(placeholder),
+            types: Vec::from([type_name::<A>()]),
+            resolver: resolve::<A>,
+        }
+    }
+
+    pub(crate) fn map<F, K>(self, f: F) -> Ctor<K>
+    where
+        F: FnOnce(T) -> K,
+    {
+        let mut types = self.types;
+        types.push(type_name::<T>());
+        Ctor {
+            msg: f(self.msg),
+            types,
+            placeholder: self.placeholder,
+            resolver: self.resolver,
+        }
+    }
+}
+
+impl<T> PartialEq for Ctor<T>
+where
+    T: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        // the resolver is determined by the first type
+        self.msg == other.msg && self.types == other.types
+    }
+}
+
+impl<T> fmt::Debug for Ctor<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Ctor").field("types", &self.types).finish()
+    }
+}
+
+impl<T> Serialize for Ctor<T>
+where
+    T: Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        let mut matches = Vec::new();
+        self.msg
+            .serialize(FindPath {
+                targets: &self.types,
+                path: &mut Vec::new(),
+                matches: &mut matches,
+            })
+            .map_err(ser::Error::custom)?;
+
+        let mut template = serde_json::to_value(&self.msg).map_err(ser::Error::custom)?;
+
+        if matches.len() > 1 {
+            if let Some(placeholder) = &self.placeholder {
+                matches.retain(|path| {
+                    lookup(&mut template, path).is_some_and(|value| value == placeholder)
+                });
+            }
+        }
+
+        let path = match matches.len() {
+            1 => matches.remove(0),
+            0 => {
+                return Err(ser::Error::custom(format!(
+                    "`{}` not found in message",
+                    self.types[0]
+                )))
+            }
+            _ => {
+                return Err(ser::Error::custom(format!(
+                    "`{}` appears more than once in message with the same value as the \
+                     placeholder, so the constructor's argument can't be located. Wrap the other \
+                     values in a different type or override `FromEventData::placeholder`",
+                    self.types[0]
+                )))
+            }
+        };
+
+        // the placeholder is replaced anyway so don't send it
+        if let Some(placeholder) = lookup(&mut template, &path) {
+            *placeholder = Value::Null;
+        }
+
+        let mut map = serializer.serialize_map(Some(3))?;
+        map.serialize_entry("axm-ctor", &template)?;
+        map.serialize_entry("axm-path", &path)?;
+        map.serialize_entry("axm-kind", &kind_of(self.types[0]))?;
+        map.end()
+    }
+}
+
+/// A constructed message sent back from the client.
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub(crate) struct CtorMsg {
+    #[serde(rename = "axm-ctor")]
+    template: Value,
+    #[serde(rename = "axm-path")]
+    path: Vec<Segment>,
+    #[serde(rename = "axm-kind")]
+    kind: String,
+}
+
+impl CtorMsg {
+    /// Fill in the placeholder from the event data and deserialize the message.
+    pub(crate) fn build<M>(
+        self,
+        resolvers: &Resolvers,
+        data: Option<&EventData>,
+    ) -> Result<M, CtorError>
+    where
+        M: DeserializeOwned,
+    {
+        let resolver = resolvers.0.get(&self.kind).ok_or(CtorError::UnknownKind)?;
+        let value = resolver(data)?;
+
+        let mut msg = self.template;
+        *lookup(&mut msg, &self.path).ok_or(CtorError::InvalidPath)? = value;
+        serde_json::from_value(msg).map_err(CtorError::Json)
+    }
+}
+
+/// The resolvers of the constructors a view has rendered, by the kind sent to the client.
+#[derive(Debug, Default, Clone)]
+pub(crate) struct Resolvers(BTreeMap<String, Resolver>);
+
+impl Resolvers {
+    /// Collect the resolvers of the constructors in `html`.
+    pub(crate) fn of<T>(html: &Html<T>) -> Self {
+        fn go<T>(dynamic: &IndexMap<DynamicFragment<T>>, out: &mut Resolvers) {
+            for fragment in dynamic.values() {
+                match fragment {
+                    DynamicFragment::Message(Binding::Ctor(ctor))
+                    | DynamicFragment::Submit(Binding::Ctor(ctor)) => {
+                        out.0.entry(kind_of(ctor.types[0])).or_insert(ctor.resolver);
+                    }
+                    DynamicFragment::Html(html) => go(&html.dynamic, out),
+                    DynamicFragment::Loop { dynamic, .. } => {
+                        dynamic.values().for_each(|dynamic| go(dynamic, out))
+                    }
+                    DynamicFragment::Message(_)
+                    | DynamicFragment::Submit(_)
+                    | DynamicFragment::String(_) => {}
+                }
+            }
+        }
+
+        let mut resolvers = Self::default();
+        go(&html.dynamic, &mut resolvers);
+        resolvers
+    }
+
+    pub(crate) fn extend(&mut self, other: Self) {
+        self.0.extend(other.0);
+    }
+}
+
+pub(crate) fn deserialize_ctor<'de, D>(d: D) -> Result<CtorMsg, D::Error>
+where
+    D: de::Deserializer<'de>,
+{
+    let s = String::deserialize(d)?;
+    let s = percent_encoding::percent_decode_str(&s)
+        .decode_utf8()
+        .map_err(de::Error::custom)?;
+    serde_json::from_str(&s).map_err(de::Error::custom)
+}
+
+#[derive(Debug)]
+pub(crate) enum CtorError {
+    UnknownKind,
+    InvalidPath,
+    FromEventData(FromEventDataError),
+    Json(serde_json::Error),
+}
+
+impl fmt::Display for CtorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CtorError::UnknownKind => write!(f, "unknown constructor argument type"),
+            CtorError::InvalidPath => write!(f, "placeholder not found in message"),
+            CtorError::FromEventData(inner) => inner.fmt(f),
+            CtorError::Json(inner) => inner.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for CtorError {}
+
+fn resolve<A>(data: Option<&EventData>) -> Result<Value, CtorError>
+where
+    A: FromEventData,
+{
+    let value = A::from_event_data(data).map_err(CtorError::FromEventData)?;
+    serde_json::to_value(value).map_err(CtorError::Json)
+}
+
+// hashed so type names aren't exposed in the HTML. Resolvers are keyed by this 64-bit hash, so two
+// argument types whose names collide would share a resolver. `DefaultHasher` may change between
+// Rust releases, which is fine since kinds are only resolved by the process that rendered them.
+fn kind_of(type_name: &str) -> String {
+    let mut hasher = DefaultHasher::new();
+    type_name.hash(&mut hasher);
+    format!("{:x}", hasher.finish())
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(untagged)]
+enum Segment {
+    Index(usize),
+    Key(String),
+}
+
+fn lookup<'a>(mut value: &'a mut Value, path: &[Segment]) -> Option<&'a mut Value> {
+    for segment in path {
+        value = match segment {
+            Segment::Index(index) => value.get_mut(*index)?,
+            Segment::Key(key) => value.get_mut(key)?,
+        };
+    }
+    Some(value)
+}
+
+/// A serializer that finds the paths to every value of the placeholder's type, as they would be
+/// in serialized JSON.
+struct FindPath<'a> {
+    /// The type names to look for, outermost last.
+    targets: &'a [&'static str],
+    path: &'a mut Vec<Segment>,
+    matches: &'a mut Vec<Vec<Segment>>,
+}
+
+impl<'a> FindPath<'a> {
+    fn reborrow(&mut self) -> FindPath<'_> {
+        FindPath {
+            targets: self.targets,
+            path: self.path,
+            matches: self.matches,
+        }
+    }
+
+    fn visit<T>(&mut self, segment: Option<Segment>, value: &T) -> Result<(), serde_json::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        let pushed = segment.is_some();
+        self.path.extend(segment);
+
+        match self.targets.split_last() {
+            Some((target, [])) if type_name::<T>() == *target => {
+                self.matches.push(self.path.clone());
+            }
+            Some((target, rest)) if type_name::<T>() == *target => {
+                value.serialize(FindPath {
+                    targets: rest,
+                    path: self.path,
+                    matches: self.matches,
+                })?;
+            }
+            _ => value.serialize(self.reborrow())?,
+        }
+
+        if pushed {
+            self.path.pop();
+        }
+        Ok(())
+    }
+
+    fn compound(self, variant: Option<&'static str>) -> Compound<'a> {
+        let pushed = variant.is_some();
+        self.path
+            .extend(variant.map(|variant| Segment::Key(variant.to_owned())));
+        Compound {
+            finder: self,
+            pushed,
+            index: 0,
+            key: None,
+        }
+    }
+}
+
+struct Compound<'a> {
+    finder: FindPath<'a>,
+    pushed: bool,
+    index: usize,
+    key: Option<String>,
+}
+
+impl<'a> Compound<'a> {
+    fn element<T>(&mut self, value: &T) -> Result<(), serde_json::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        let index = self.index;
+        self.index += 1;
+        self.visit(Segment::Index(index), value)
+    }
+
+    fn field<T>(&mut self, key: &str, value: &T) -> Result<(), serde_json::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.visit(Segment::Key(key.to_owned()), value)
+    }
+
+    fn visit<T>(&mut self, segment: Segment, value: &T) -> Result<(), serde_json::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.finder.visit(Some(segment), value)
+    }
+
+    fn finish(self) -> Result<(), serde_json::Error> {
+        if self.pushed {
+            self.finder.path.pop();
+        }
+        Ok(())
+    }
+}
+
+impl<'a> ser::Serializer for FindPath<'a> {
+    type Ok = ();
+    type Error = serde_json::Error;
+    type SerializeSeq = Compound<'a>;
+    type SerializeTuple = Compound<'a>;
+    type SerializeTupleStruct = Compound<'a>;
+    type SerializeTupleVariant = Compound<'a>;
+    type SerializeMap = Compound<'a>;
+    type SerializeStruct = Compound<'a>;
+    type SerializeStructVariant = Compound<'a>;
+
+    fn serialize_bool(self, _: bool) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn serialize_i8(self, _: i8) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn serialize_i16(self, _: i16) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn serialize_i32(self, _: i32) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn serialize_i64(self, _: i64) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn serialize_u8(self, _: u8) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn serialize_u16(self, _: u16) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn serialize_u32(self, _: u32) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn serialize_u64(self, _: u64) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn serialize_f32(self, _: f32) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn serialize_f64(self, _: f64) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn serialize_char(self, _: char) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn serialize_str(self, _: &str) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn serialize_bytes(self, _: &[u8]) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn serialize_none(self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn serialize_some<T>(mut self, value: &T) -> Result<(), Self::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.visit(None, value)
+    }
+
+    fn serialize_unit(self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn serialize_unit_struct(self, _: &'static str) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn serialize_newtype_struct<T>(mut self, _: &'static str, value: &T) -> Result<(), Self::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.visit(None, value)
+    }
+
+    fn serialize_newtype_variant<T>(
+        mut self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<(), Self::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.visit(Some(Segment::Key(variant.to_owned())), value)
+    }
+
+    fn serialize_seq(self, _: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
+        Ok(self.compound(None))
+    }
+
+    fn serialize_tuple(self, _: usize) -> Result<Self::SerializeTuple, Self::Error> {
+        Ok(self.compound(None))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeTupleStruct, Self::Error> {
+        Ok(self.compound(None))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeTupleVariant, Self::Error> {
+        Ok(self.compound(Some(variant)))
+    }
+
+    fn serialize_map(self, _: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
+        Ok(self.compound(None))
+    }
+
+    fn serialize_struct(
+        self,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeStruct, Self::Error> {
+        Ok(self.compound(None))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeStructVariant, Self::Error> {
+        Ok(self.compound(Some(variant)))
+    }
+}
+
+impl ser::SerializeSeq for Compound<'_> {
+    type Ok = ();
+    type Error = serde_json::Error;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), Self::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), Self::Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTuple for Compound<'_> {
+    type Ok = ();
+    type Error = serde_json::Error;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), Self::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), Self::Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTupleStruct for Compound<'_> {
+    type Ok = ();
+    type Error = serde_json::Error;
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<(), Self::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), Self::Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTupleVariant for Compound<'_> {
+    type Ok = ();
+    type Error = serde_json::Error;
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<(), Self::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), Self::Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeMap for Compound<'_> {
+    type Ok = ();
+    type Error = serde_json::Error;
+
+    fn serialize_key<T>(&mut self, key: &T) -> Result<(), Self::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        // JSON object keys are always strings
+        self.key = Some(match serde_json::to_value(key)? {
+            Value::String(key) => key,
+            key => key.to_string(),
+        });
+        Ok(())
+    }
+
+    fn serialize_value<T>(&mut self, value: &T) -> Result<(), Self::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        let key = self.key.take().unwrap_or_default();
+        self.visit(Segment::Key(key), value)
+    }
+
+    fn end(self) -> Result<(), Self::Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeStruct for Compound<'_> {
+    type Ok = ();
+    type Error = serde_json::Error;
+
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), Self::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.field(key, value)
+    }
+
+    fn end(self) -> Result<(), Self::Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeStructVariant for Compound<'_> {
+    type Ok = ();
+    type Error = serde_json::Error;
+
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<(), Self::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        self.field(key, value)
+    }
+
+    fn end(self) -> Result<(), Self::Error> {
+        self.finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate as axum_live_view;
+    use crate::event_data::Input;
+    use serde_json::json;
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    enum Msg {
+        TextChanged(String),
+        Renamed { id: u64, name: String },
+        Relabeled { label: String, name: String },
+        Resized { width: u32, height: u32 },
+        Toggled { enabled: bool, checked: bool },
+    }
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    enum Outer {
+        Other(String),
+        Inner(Msg),
+    }
+
+    fn round_trip<T>(ctor: &Ctor<T>, data: EventData) -> T
+    where
+        T: Serialize + DeserializeOwned,
+    {
+        let json = serde_json::to_value(ctor).unwrap();
+        let ctor_msg = serde_json::from_value::<CtorMsg>(json).unwrap();
+        ctor_msg.build(&resolvers(ctor), Some(&data)).unwrap()
+    }
+
+    fn resolvers<T>(ctor: &Ctor<T>) -> Resolvers {
+        Resolvers(BTreeMap::from([(kind_of(ctor.types[0]), ctor.resolver)]))
+    }
+
+    fn input(value: &str) -> EventData {
+        EventData::from(Input::String(value.to_owned()))
+    }
+
+    #[test]
+    fn tuple_variant() {
+        let ctor = Ctor::new(Msg::TextChanged);
+        assert_eq!(
+            serde_json::to_value(&ctor).unwrap(),
+            json!({
+                "axm-ctor": { "TextChanged": null },
+                "axm-path": ["TextChanged"],
+                "axm-kind": kind_of(type_name::<String>()),
+            })
+        );
+        assert_eq!(
+            round_trip(&ctor, input("hi")),
+            Msg::TextChanged("hi".to_owned())
+        );
+    }
+
+    #[test]
+    fn mapped() {
+        let ctor = Ctor::new(|name| Msg::Renamed { id: 1, name }).map(Outer::Inner);
+        assert_eq!(
+            round_trip(&ctor, input("bob")),
+            Outer::Inner(Msg::Renamed {
+                id: 1,
+                name: "bob".to_owned()
+            })
+        );
+    }
+
+    #[test]
+    fn argument_type_appearing_more_than_once() {
+        let label = "Name".to_owned();
+        let ctor = Ctor::new(|name| Msg::Relabeled { label, name });
+        assert_eq!(
+            round_trip(&ctor, input("bob")),
+            Msg::Relabeled {
+                label: "Name".to_owned(),
+                name: "bob".to_owned()
+            }
+        );
+
+        let ctor = Ctor::new(|height| Msg::Resized { width: 0, height }).map(Outer::Inner);
+        assert_eq!(
+            round_trip(&ctor, input("10")),
+            Outer::Inner(Msg::Resized {
+                width: 0,
+                height: 10
+            })
+        );
+    }
+
+    #[test]
+    fn argument_indistinguishable_from_other_values() {
+        let ctor = Ctor::new(|checked| Msg::Toggled {
+            enabled: false,
+            checked,
+        });
+        let err = serde_json::to_value(&ctor).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("`bool` appears more than once in message"));
+
+        // rendering fails rather than panics
+        let html: Html<Msg> = crate::html! {
+            <input type="checkbox" axm-change={ |checked| Msg::Toggled { enabled: false, checked } } />
+        };
+        assert!(serde_json::to_value(&html).is_err());
+    }
+
+    #[test]
+    fn other_value_equal_to_placeholder() {
+        // the other value can't be told apart from the argument, so rendering fails instead of
+        // filling in the wrong field
+        let width = u32::placeholder();
+        let ctor = Ctor::new(move |height| Msg::Resized { width, height });
+        let err = serde_json::to_value(&ctor).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("`u32` appears more than once in message"));
+
+        let label = String::placeholder();
+        let ctor = Ctor::new(move |name| Msg::Relabeled {
+            label: label.clone(),
+            name,
+        });
+        let err = serde_json::to_value(&ctor).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("`alloc::string::String` appears more than once in message"));
+    }
+
+    #[test]
+    fn unknown_kind() {
+        let ctor = Ctor::new(Msg::TextChanged);
+        let json = serde_json::to_value(&ctor).unwrap();
+        let ctor_msg = serde_json::from_value::<CtorMsg>(json).unwrap();
+        let err = ctor_msg
+            .build::<Msg>(&Resolvers::default(), Some(&input("hi")))
+            .unwrap_err();
+        assert_eq!(err.to_string(), "unknown constructor argument type");
+    }
+
+    #[test]
+    fn resolvers_of_html() {
+        let ctor = Ctor::new(Msg::TextChanged);
+        let html: Html<Msg> = crate::html! {
+            <div>
+                for _ in 0..2 {
+                    <input axm-input={ Msg::TextChanged } />
+                }
+            </div>
+        };
+        let json = serde_json::to_value(&ctor).unwrap();
+        let ctor_msg = serde_json::from_value::<CtorMsg>(json).unwrap();
+        assert_eq!(
+            ctor_msg
+                .build::<Msg>(&Resolvers::of(&html), Some(&input("hi")))
+                .unwrap(),
+            Msg::TextChanged("hi".to_owned())
+        );
+    }
+
+    #[test]
+    fn unexpected_event_data() {
+        let ctor = Ctor::new(Msg::TextChanged);
+        let json = serde_json::to_value(&ctor).unwrap();
+        let ctor_msg = serde_json::from_value::<CtorMsg>(json).unwrap();
+        let err = ctor_msg.build::<Msg>(&resolvers(&ctor), None).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "expected text input or key event data, found an event without data"
+        );
+    }
+}
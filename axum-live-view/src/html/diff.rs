@@ -1,10 +1,16 @@
-use super::{empty_slice, serialize_msg, DynamicFragment, Html, IndexMap};
+use super::{
+    empty_slice, serialize_msg, statics, Binding, DynamicFragment, Html, IndexMap, Reused,
+};
 use serde::Serialize;
 use std::collections::BTreeMap;
 
 #[derive(Serialize)]
 pub(crate) struct HtmlDiff<'a, T> {
-    #[serde(rename = "f", skip_serializing_if = "Option::is_none")]
+    #[serde(
+        rename = "f",
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "statics::serialize_optional_fixed"
+    )]
     fixed: Option<&'static [&'static str]>,
     #[serde(rename = "d", skip_serializing_if = "BTreeMap::is_empty")]
     dynamic: IndexMap<Option<DynamicFragmentDiff<'a, T>>>,
@@ -15,10 +21,14 @@ pub(crate) struct HtmlDiff<'a, T> {
 pub(crate) enum DynamicFragmentDiff<'a, T> {
     String(&'a str),
     #[serde(serialize_with = "serialize_msg")]
-    Message(&'a T),
+    Message(&'a Binding<T>),
     HtmlDiff(HtmlDiff<'a, T>),
     Loop {
-        #[serde(rename = "f", skip_serializing_if = "empty_slice")]
+        #[serde(
+            rename = "f",
+            skip_serializing_if = "empty_slice",
+            serialize_with = "statics::serialize_fixed"
+        )]
         fixed: &'static [&'static str],
         #[serde(rename = "b", skip_serializing_if = "BTreeMap::is_empty")]
         dynamic: IndexMap<Option<IndexMap<DynamicFragmentDiff<'a, T>>>>,
@@ -42,7 +52,7 @@ impl<'a, T> From<&'a DynamicFragment<T>> for DynamicFragmentDiff<'a, T> {
     fn from(other: &'a DynamicFragment<T>) -> Self {
         match other {
             DynamicFragment::String(s) => Self::String(s),
-            DynamicFragment::Message(msg) => Self::Message(msg),
+            DynamicFragment::Message(msg) | DynamicFragment::Submit(msg) => Self::Message(msg),
             DynamicFragment::Html(html) => Self::HtmlDiff(html.into()),
             DynamicFragment::Loop { fixed, dynamic } => Self::Loop {
                 fixed,
@@ -65,16 +75,34 @@ impl<'a, T> From<&'a DynamicFragment<T>> for DynamicFragmentDiff<'a, T> {
 }
 
 impl<T> Html<T> {
+    #[cfg(test)]
     pub(crate) fn diff<'a>(&self, other: &'a Self) -> Option<HtmlDiff<'a, T>>
+    where
+        T: PartialEq + Serialize,
+    {
+        self.diff_reusing(other, &Reused::default())
+    }
+
+    /// Diff two templates, skipping parts that were reused from `self` when rendering `other`.
+    pub(crate) fn diff_reusing<'a>(
+        &self,
+        other: &'a Self,
+        reused: &Reused,
+    ) -> Option<HtmlDiff<'a, T>>
     where
         T: PartialEq + Serialize,
     {
         let dynamic = zip(self.dynamic.iter(), other.dynamic.iter())
             .filter_map(|pair| match pair {
+                Zipped::Both((self_idx, _), _)
+                    if reused.contains(self.fixed, other.fixed, *self_idx) =>
+                {
+                    None
+                }
                 Zipped::Both((self_idx, self_value), (other_idx, other_value)) => {
                     debug_assert_eq!(self_idx, other_idx);
                     self_value
-                        .diff(other_value)
+                        .diff(other_value, reused)
                         .map(|diff| (*self_idx, Some(diff)))
                 }
                 Zipped::Left((other_idx, _)) => Some((*other_idx, None)),
@@ -104,7 +132,11 @@ impl<T> Html<T> {
 }
 
 impl<T> DynamicFragment<T> {
-    pub(crate) fn diff<'a>(&self, other: &'a Self) -> Option<DynamicFragmentDiff<'a, T>>
+    pub(crate) fn diff<'a>(
+        &self,
+        other: &'a Self,
+        reused: &Reused,
+    ) -> Option<DynamicFragmentDiff<'a, T>>
     where
         T: PartialEq + Serialize,
     {
@@ -116,7 +148,8 @@ impl<T> DynamicFragment<T> {
                     Some(DynamicFragmentDiff::String(other_value))
                 }
             }
-            (Self::Message(from_self), Self::Message(from_other)) => {
+            (Self::Message(from_self), Self::Message(from_other))
+            | (Self::Submit(from_self), Self::Submit(from_other)) => {
                 if from_self == from_other {
                     None
                 } else {
@@ -124,7 +157,7 @@ impl<T> DynamicFragment<T> {
                 }
             }
             (Self::Html(self_value), Self::Html(other_value)) => self_value
-                .diff(other_value)
+                .diff_reusing(other_value, reused)
                 .map(DynamicFragmentDiff::HtmlDiff),
             (
                 Self::Loop {
@@ -164,12 +197,23 @@ impl<T> DynamicFragment<T> {
                                     Zipped::Right(_) => {
                                         unreachable!("unable to find a way to hit this yolo")
                                     }
+                                    Zipped::Both((self_idx, _), _)
+                                        if reused.contains(
+                                            self_fixed,
+                                            dynamic_fixed,
+                                            *self_idx,
+                                        ) =>
+                                    {
+                                        None
+                                    }
                                     Zipped::Both(
                                         (self_idx, self_value),
                                         (other_idx, other_value),
                                     ) => {
                                         debug_assert_eq!(self_idx, other_idx);
-                                        self_value.diff(other_value).map(|diff| (*self_idx, diff))
+                                        self_value
+                                            .diff(other_value, reused)
+                                            .map(|diff| (*self_idx, diff))
                                     }
                                 })
                                 .collect::<BTreeMap<_, _>>();
@@ -2,11 +2,20 @@ use axum::response::IntoResponse;
 use serde::Serialize;
 use std::{collections::BTreeMap, fmt};
 
-pub(crate) use self::private::*;
-
+pub(crate) use self::{
+    ctor::{deserialize_ctor, CtorMsg, Resolvers},
+    private::*,
+    render::SUBMIT_INPUT,
+    reuse::{render_reusing, Reused},
+    statics::{Statics, StaticsCache},
+};
+
+mod ctor;
 mod diff;
 pub(crate) mod private;
 mod render;
+mod reuse;
+mod statics;
 
 #[cfg(test)]
 mod tests;
@@ -20,7 +29,7 @@ type IndexMap<T> = BTreeMap<usize, T>;
 /// [`html!`]: crate::html!
 #[derive(Clone, Serialize, PartialEq)]
 pub struct Html<T> {
-    #[serde(rename = "f")]
+    #[serde(rename = "f", serialize_with = "statics::serialize_fixed")]
     fixed: &'static [&'static str],
     #[serde(rename = "d", skip_serializing_if = "BTreeMap::is_empty")]
     dynamic: IndexMap<DynamicFragment<T>>,
@@ -44,6 +53,7 @@ impl<T> std::fmt::Debug for DynamicFragment<T> {
         match self {
             Self::String(inner) => f.debug_tuple("String").field(inner).finish(),
             Self::Message(_) => f.debug_tuple("Message").finish(),
+            Self::Submit(_) => f.debug_tuple("Submit").finish(),
             Self::Html(inner) => f.debug_tuple("Html").field(inner).finish(),
             Self::Loop { fixed, dynamic } => f
                 .debug_struct("Loop")
@@ -54,6 +64,16 @@ impl<T> std::fmt::Debug for DynamicFragment<T> {
     }
 }
 
+impl<T> std::fmt::Debug for Binding<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Message(_) => f.debug_tuple("Message").finish(),
+            Self::Js(_) => f.debug_tuple("Js").finish(),
+            Self::Ctor(_) => f.debug_tuple("Ctor").finish(),
+        }
+    }
+}
+
 impl<S, T> From<S> for DynamicFragment<T>
 where
     S: fmt::Display,
@@ -74,7 +94,7 @@ where
     S: serde::Serializer,
     T: Serialize,
 {
-    let encoded = serde_json::to_string(msg).unwrap();
+    let encoded = serde_json::to_string(msg).map_err(serde::ser::Error::custom)?;
     let encoded = std::borrow::Cow::from(percent_encoding::utf8_percent_encode(
         &encoded,
         ENCODE_FRAGMENT,
@@ -89,7 +109,8 @@ impl<T> DynamicFragment<T> {
     {
         match self {
             DynamicFragment::String(s) => DynamicFragment::String(s),
-            DynamicFragment::Message(msg) => DynamicFragment::Message(f(msg)),
+            DynamicFragment::Message(binding) => DynamicFragment::Message(binding.map(f)),
+            DynamicFragment::Submit(binding) => DynamicFragment::Submit(binding.map(f)),
             DynamicFragment::Html(inner) => DynamicFragment::Html(inner.map_with_mut(f)),
             DynamicFragment::Loop { fixed, dynamic } => DynamicFragment::Loop {
                 fixed,
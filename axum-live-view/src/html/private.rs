@@ -2,23 +2,153 @@
 
 #![allow(missing_docs)]
 
+pub use super::ctor::Ctor;
 use super::*;
+use crate::{
+    event_data::{kind::Fits, FromEventData},
+    js_command::{JsChain, JsCommand},
+};
+use std::{cell::Cell, marker::PhantomData};
 
 #[derive(Clone, Serialize, PartialEq)]
 #[serde(untagged)]
 pub enum DynamicFragment<T> {
     String(String),
     #[serde(serialize_with = "serialize_msg")]
-    Message(T),
+    Message(Binding<T>),
+    /// The `axm-submit` binding of a form without a `method`. `html!` places it last so the
+    /// form's opening tag closes right after it.
+    #[serde(serialize_with = "serialize_msg")]
+    Submit(Binding<T>),
     Html(Html<T>),
     Loop {
-        #[serde(rename = "f")]
+        #[serde(rename = "f", serialize_with = "super::statics::serialize_fixed")]
         fixed: &'static [&'static str],
         #[serde(rename = "b", skip_serializing_if = "BTreeMap::is_empty")]
         dynamic: IndexMap<IndexMap<DynamicFragment<T>>>,
     },
 }
 
+/// What an `axm-*` attribute is bound to.
+#[derive(Clone, Serialize, PartialEq)]
+#[serde(untagged)]
+pub enum Binding<T> {
+    Message(T),
+    Js(JsChain<T>),
+    Ctor(Ctor<T>),
+}
+
+impl<T> Binding<T> {
+    pub(super) fn map<F, K>(self, f: F) -> Binding<K>
+    where
+        F: FnOnce(T) -> K,
+    {
+        match self {
+            Binding::Message(msg) => Binding::Message(f(msg)),
+            Binding::Js(chain) => Binding::Js(chain.map(f)),
+            Binding::Ctor(ctor) => Binding::Ctor(ctor.map(f)),
+        }
+    }
+}
+
+pub trait IntoBinding<T> {
+    fn into_binding(self) -> Binding<T>;
+}
+
+impl<T> IntoBinding<T> for T {
+    #[inline]
+    fn into_binding(self) -> Binding<T> {
+        Binding::Message(self)
+    }
+}
+
+impl<T> IntoBinding<T> for JsChain<T> {
+    #[inline]
+    fn into_binding(self) -> Binding<T> {
+        Binding::Js(self)
+    }
+}
+
+impl<T> IntoBinding<T> for Ctor<T> {
+    #[inline]
+    fn into_binding(self) -> Binding<T> {
+        Binding::Ctor(self)
+    }
+}
+
+/// Wraps the value of an `axm-*` attribute so constructors like `Msg::TextChanged` can be told
+/// apart from messages using autoref specialization. `html!` calls `(&&spec).binding_spec()` which
+/// resolves to [`CtorBindingSpec`] if the value is a function, [`JsBindingSpec`] if it's a single
+/// [`JsCommand`], and [`MsgBindingSpec`] otherwise.
+///
+/// `E` is the kind of event the attribute binds, from [`event_data::kind`], which the
+/// constructor's argument must fit.
+///
+/// [`event_data::kind`]: crate::event_data::kind
+pub struct BindingSpec<F, E>(Cell<Option<F>>, PhantomData<E>);
+
+impl<F, E> BindingSpec<F, E> {
+    #[inline]
+    pub fn new(value: F) -> Self {
+        Self(Cell::new(Some(value)), PhantomData)
+    }
+
+    fn take(&self) -> F {
+        self.0.take().expect("binding spec used twice")
+    }
+}
+
+impl<F, E> fmt::Debug for BindingSpec<F, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("BindingSpec").finish()
+    }
+}
+
+pub trait CtorBindingSpec<T, A, E> {
+    fn binding_spec(&self) -> Ctor<T>
+    where
+        A: FromEventData,
+        A::Kind: Fits<E>;
+}
+
+impl<F, A, T, E> CtorBindingSpec<T, A, E> for &BindingSpec<F, E>
+where
+    F: FnOnce(A) -> T,
+{
+    #[inline]
+    fn binding_spec(&self) -> Ctor<T>
+    where
+        A: FromEventData,
+        A::Kind: Fits<E>,
+    {
+        Ctor::new(self.take())
+    }
+}
+
+/// A bare [`JsCommand`] can't implement [`IntoBinding`] next to the blanket impl for messages, so
+/// it's turned into a [`JsChain`] that doesn't push anything here instead.
+pub trait JsBindingSpec<T> {
+    fn binding_spec(&self) -> JsChain<T>;
+}
+
+impl<T, E> JsBindingSpec<T> for &BindingSpec<JsCommand, E> {
+    #[inline]
+    fn binding_spec(&self) -> JsChain<T> {
+        JsChain::from(self.take())
+    }
+}
+
+pub trait MsgBindingSpec<B> {
+    fn binding_spec(&self) -> B;
+}
+
+impl<B, E> MsgBindingSpec<B> for BindingSpec<B, E> {
+    #[inline]
+    fn binding_spec(&self) -> B {
+        self.take()
+    }
+}
+
 pub trait DynamicFragmentVecExt<T> {
     fn push_fragment(&mut self, part: impl Into<DynamicFragment<T>>);
 
@@ -28,7 +158,18 @@ pub trait DynamicFragmentVecExt<T> {
         dynamic: Vec<Vec<DynamicFragment<T>>>,
     );
 
-    fn push_message(&mut self, msg: T);
+    fn push_message(&mut self, msg: impl IntoBinding<T>);
+
+    fn push_submit(&mut self, msg: impl IntoBinding<T>);
+
+    fn reuse_fragment(
+        &mut self,
+        fixed: &'static [&'static str],
+        owner: &'static str,
+        fields: &[&'static str],
+    ) -> bool
+    where
+        T: 'static;
 }
 
 impl<T> DynamicFragmentVecExt<T> for Vec<DynamicFragment<T>> {
@@ -52,8 +193,32 @@ impl<T> DynamicFragmentVecExt<T> for Vec<DynamicFragment<T>> {
     }
 
     #[inline]
-    fn push_message(&mut self, msg: T) {
-        self.push(DynamicFragment::Message(msg))
+    fn push_message(&mut self, msg: impl IntoBinding<T>) {
+        self.push(DynamicFragment::Message(msg.into_binding()))
+    }
+
+    #[inline]
+    fn push_submit(&mut self, msg: impl IntoBinding<T>) {
+        self.push(DynamicFragment::Submit(msg.into_binding()))
+    }
+
+    #[inline]
+    fn reuse_fragment(
+        &mut self,
+        fixed: &'static [&'static str],
+        owner: &'static str,
+        fields: &[&'static str],
+    ) -> bool
+    where
+        T: 'static,
+    {
+        match super::reuse::take(fixed, self.len(), owner, fields) {
+            Some(fragment) => {
+                self.push(fragment);
+                true
+            }
+            None => false,
+        }
     }
 }
 
@@ -71,3 +236,20 @@ impl<T> HtmlBuilder<T> {
         }
     }
 }
+
+/// The value of an `axm-value-*` attribute, rendered as percent encoded JSON.
+#[derive(Debug)]
+pub struct ValueAttr<T>(pub T);
+
+impl<T> fmt::Display for ValueAttr<T>
+where
+    T: Serialize,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let encoded = serde_json::to_string(&self.0).map_err(|_| fmt::Error)?;
+        for part in percent_encoding::utf8_percent_encode(&encoded, ENCODE_FRAGMENT) {
+            f.write_str(part)?;
+        }
+        Ok(())
+    }
+}
@@ -7,39 +7,99 @@ impl<T> Html<T> {
         T: Serialize,
     {
         let mut out = String::new();
-        let _ = render_to(self.fixed, &self.dynamic, &mut out);
+        if let Err(err) = render_to(self.fixed, &self.dynamic, &mut out, None) {
+            tracing::error!(%err, "failed to render HTML");
+        }
+        out
+    }
+
+    /// Render the HTML for the initial response when forms without JavaScript are handled.
+    ///
+    /// Forms bound with `axm-submit` are made to post their message along with the form's values
+    /// so they still work if JavaScript is disabled or fails to load. The JavaScript client
+    /// replaces the page with the regular rendering once connected.
+    pub(crate) fn render_with_form_posts(&self) -> String
+    where
+        T: Serialize,
+    {
+        let mut out = String::new();
+        if let Err(err) = render_to(self.fixed, &self.dynamic, &mut out, Some(&mut None)) {
+            tracing::error!(%err, "failed to render HTML");
+        }
         out
     }
 }
 
+/// The name of the hidden input that holds the message of a form submitted without JavaScript.
+pub(crate) const SUBMIT_INPUT: &str = "axm-submit";
+
+/// `pending_submit` is `None` if forms aren't posted, otherwise it holds the message of a form
+/// whose opening tag hasn't been closed yet.
 fn render_to<T>(
     fixed: &'static [&'static str],
     dynamic: &IndexMap<DynamicFragment<T>>,
     out: &mut String,
-) -> Result<(), ()>
+    mut pending_submit: Option<&mut Option<String>>,
+) -> Result<(), serde_json::Error>
 where
     T: Serialize,
 {
     let mut dynamic_iter = dynamic.iter();
 
     for f in fixed {
-        out.push_str(f);
+        match pending_submit.as_deref_mut().and_then(Option::take) {
+            Some(msg) => match f.strip_prefix('>') {
+                Some(rest) => {
+                    out.push_str(" method=post><input type=hidden name=");
+                    out.push_str(SUBMIT_INPUT);
+                    out.push_str(" value=");
+                    out.push_str(&msg);
+                    out.push('>');
+                    out.push_str(rest);
+                }
+                None => {
+                    // the binding is the last attribute so only the end of an `if` can be between
+                    // it and the end of the opening tag
+                    debug_assert!(f.is_empty());
+                    out.push_str(f);
+                    if let Some(pending) = pending_submit.as_deref_mut() {
+                        *pending = Some(msg);
+                    }
+                }
+            },
+            None => out.push_str(f),
+        }
 
         match dynamic_iter.next() {
             Some((_, DynamicFragment::Html(html))) => {
-                let _ = render_to(html.fixed, &html.dynamic, out);
+                render_to(
+                    html.fixed,
+                    &html.dynamic,
+                    out,
+                    pending_submit.as_deref_mut(),
+                )?;
             }
             Some((_, DynamicFragment::String(s))) => {
                 out.push_str(&*s);
             }
             Some((_, DynamicFragment::Message(msg))) => {
-                let encoded_msg = serde_json::to_string(msg).unwrap();
+                let encoded_msg = serde_json::to_string(msg)?;
                 for el in
                     percent_encoding::utf8_percent_encode(&encoded_msg, super::ENCODE_FRAGMENT)
                 {
                     out.push_str(el);
                 }
             }
+            Some((_, DynamicFragment::Submit(msg))) => {
+                let encoded_msg = serde_json::to_string(msg)?;
+                let encoded_msg =
+                    percent_encoding::utf8_percent_encode(&encoded_msg, super::ENCODE_FRAGMENT)
+                        .to_string();
+                out.push_str(&encoded_msg);
+                if let Some(pending) = pending_submit.as_deref_mut() {
+                    *pending = Some(encoded_msg);
+                }
+            }
             Some((
                 _,
                 DynamicFragment::Loop {
@@ -48,7 +108,7 @@ where
                 },
             )) => {
                 for d in dynamic.values() {
-                    let _ = render_to(loop_fixed, d, out);
+                    render_to(loop_fixed, d, out, pending_submit.as_deref_mut())?;
                 }
             }
             None => {}
@@ -0,0 +1,220 @@
+//! Reuse the dynamic parts of the previous render whose fields haven't changed.
+//!
+//! `html!` wraps every dynamic part that only reads fields of `self` in a call to
+//! [`DynamicFragmentVecExt::reuse_fragment`]. While rendering with [`render_reusing`] that moves
+//! the corresponding part out of the previous render, if none of its fields have changed, instead
+//! of evaluating it again. Since the part only depends on those fields, all occurrences of it have
+//! the same value so any occurrence from the previous render will do.
+//!
+//! Field names are only meaningful for the type whose fields were compared, so parts rendered by
+//! other types, such as child views with fields of the same name, are never reused.
+//!
+//! [`DynamicFragmentVecExt::reuse_fragment`]: super::DynamicFragmentVecExt::reuse_fragment
+
+use super::{DynamicFragment, Html, IndexMap};
+use std::{
+    any::Any,
+    cell::RefCell,
+    collections::{HashMap, HashSet},
+};
+
+thread_local! {
+    static ACTIVE: RefCell<Option<ReuseContext>> = const { RefCell::new(None) };
+}
+
+/// Templates are identified by the address and length of their fixed parts.
+///
+/// Fixed parts are `static`s generated by `html!` so their address is unique.
+type TemplateId = (usize, usize);
+
+fn template_id(fixed: &'static [&'static str]) -> TemplateId {
+    (fixed.as_ptr() as usize, fixed.len())
+}
+
+/// The dynamic parts that were reused, identified by their template and index.
+#[derive(Debug, Default)]
+pub(crate) struct Reused(HashSet<(TemplateId, usize)>);
+
+impl Reused {
+    /// Whether the part at `idx` was reused and therefore doesn't need to be diffed.
+    pub(crate) fn contains(
+        &self,
+        old_fixed: &'static [&'static str],
+        new_fixed: &'static [&'static str],
+        idx: usize,
+    ) -> bool {
+        let id = template_id(new_fixed);
+        template_id(old_fixed) == id && self.0.contains(&(id, idx))
+    }
+}
+
+struct ReuseContext {
+    /// The name of the type whose fields `unchanged` refers to.
+    owner: &'static str,
+    unchanged: HashSet<&'static str>,
+    previous: Box<dyn Any>,
+    /// The paths to every occurrence of each template in `previous`. Built on first use.
+    occurrences: Option<HashMap<TemplateId, Vec<Vec<usize>>>>,
+    /// How many occurrences of each part have been reused so far.
+    taken: HashMap<(TemplateId, usize), usize>,
+    reused: Reused,
+}
+
+impl ReuseContext {
+    fn take<T>(
+        &mut self,
+        fixed: &'static [&'static str],
+        idx: usize,
+        owner: &'static str,
+        fields: &[&'static str],
+    ) -> Option<DynamicFragment<T>>
+    where
+        T: 'static,
+    {
+        if owner != self.owner || !fields.iter().all(|field| self.unchanged.contains(field)) {
+            return None;
+        }
+
+        let previous = self.previous.downcast_mut::<Html<T>>()?;
+        let occurrences = self
+            .occurrences
+            .get_or_insert_with(|| index_templates(previous));
+
+        let id = template_id(fixed);
+        let taken = self.taken.entry((id, idx)).or_default();
+        let path = occurrences.get(&id)?.get(*taken)?;
+        *taken += 1;
+
+        // the part might already have been moved out as part of a reused parent
+        let fragment = match dynamic_at_path(&mut previous.dynamic, path)?.get_mut(&idx)? {
+            DynamicFragment::String(s) if s.is_empty() => return None,
+            fragment => std::mem::replace(fragment, DynamicFragment::String(String::new())),
+        };
+
+        self.reused.0.insert((id, idx));
+        Some(fragment)
+    }
+}
+
+/// Render `html` while reusing parts of `previous` that only read fields of `owner` in
+/// `unchanged`.
+///
+/// `owner` is the [`std::any::type_name`] of the type that was compared.
+///
+/// Returns the new HTML, what remains of `previous`, and which parts were reused.
+pub(crate) fn render_reusing<T, F>(
+    previous: Html<T>,
+    owner: &'static str,
+    unchanged: HashSet<&'static str>,
+    render: F,
+) -> (Html<T>, Html<T>, Reused)
+where
+    T: 'static,
+    F: FnOnce() -> Html<T>,
+{
+    struct Guard;
+
+    impl Drop for Guard {
+        fn drop(&mut self) {
+            // make sure a panicking render doesn't leave the context around
+            ACTIVE.with(|active| active.borrow_mut().take());
+        }
+    }
+
+    ACTIVE.with(|active| {
+        *active.borrow_mut() = Some(ReuseContext {
+            owner,
+            unchanged,
+            previous: Box::new(previous),
+            occurrences: None,
+            taken: Default::default(),
+            reused: Default::default(),
+        })
+    });
+    let guard = Guard;
+
+    let html = render();
+
+    let context = ACTIVE
+        .with(|active| active.borrow_mut().take())
+        .expect("reuse context removed during render");
+    drop(guard);
+
+    let previous = *context
+        .previous
+        .downcast::<Html<T>>()
+        .unwrap_or_else(|_| unreachable!());
+
+    (html, previous, context.reused)
+}
+
+/// Move the part at `idx` of the template `fixed` out of the previous render, if none of the
+/// `fields` of `owner` have changed.
+pub(super) fn take<T>(
+    fixed: &'static [&'static str],
+    idx: usize,
+    owner: &'static str,
+    fields: &[&'static str],
+) -> Option<DynamicFragment<T>>
+where
+    T: 'static,
+{
+    ACTIVE.with(|active| {
+        active
+            .borrow_mut()
+            .as_mut()
+            .and_then(|context| context.take(fixed, idx, owner, fields))
+    })
+}
+
+fn index_templates<T>(html: &Html<T>) -> HashMap<TemplateId, Vec<Vec<usize>>> {
+    fn go<T>(
+        fixed: &'static [&'static str],
+        dynamic: &IndexMap<DynamicFragment<T>>,
+        path: &mut Vec<usize>,
+        out: &mut HashMap<TemplateId, Vec<Vec<usize>>>,
+    ) {
+        out.entry(template_id(fixed))
+            .or_default()
+            .push(path.clone());
+
+        for (idx, fragment) in dynamic {
+            path.push(*idx);
+            match fragment {
+                DynamicFragment::Html(html) => go(html.fixed, &html.dynamic, path, out),
+                DynamicFragment::Loop { fixed, dynamic } => {
+                    for (iteration, dynamic) in dynamic {
+                        path.push(*iteration);
+                        go(fixed, dynamic, path, out);
+                        path.pop();
+                    }
+                }
+                DynamicFragment::String(_)
+                | DynamicFragment::Message(_)
+                | DynamicFragment::Submit(_) => {}
+            }
+            path.pop();
+        }
+    }
+
+    let mut out = HashMap::new();
+    go(html.fixed, &html.dynamic, &mut Vec::new(), &mut out);
+    out
+}
+
+fn dynamic_at_path<'a, T>(
+    mut dynamic: &'a mut IndexMap<DynamicFragment<T>>,
+    path: &[usize],
+) -> Option<&'a mut IndexMap<DynamicFragment<T>>> {
+    let mut path = path.iter();
+    while let Some(idx) = path.next() {
+        dynamic = match dynamic.get_mut(idx)? {
+            DynamicFragment::Html(html) => &mut html.dynamic,
+            DynamicFragment::Loop { dynamic, .. } => dynamic.get_mut(path.next()?)?,
+            DynamicFragment::String(_)
+            | DynamicFragment::Message(_)
+            | DynamicFragment::Submit(_) => return None,
+        };
+    }
+    Some(dynamic)
+}
@@ -0,0 +1,89 @@
+//! Fingerprinting of the fixed parts of templates, also known as statics.
+//!
+//! Statics never change for a given template so there is no reason to send them to the client more
+//! than once per connection. While serializing with a [`StaticsCache`] each static is replaced by
+//! a numeric id and the statics the client hasn't seen yet are returned separately.
+
+use serde::{Serialize, Serializer};
+use serde_json::Value;
+use std::{cell::RefCell, collections::BTreeMap, collections::HashMap};
+
+pub(crate) type Statics = BTreeMap<u32, &'static [&'static str]>;
+
+thread_local! {
+    static ACTIVE: RefCell<Option<StaticsCache>> = const { RefCell::new(None) };
+}
+
+/// The statics sent to a client, keyed by the address and length of the slice.
+///
+/// Statics are always `&'static` slices generated by `html!` so the address is enough to identify
+/// them.
+#[derive(Debug, Default)]
+pub(crate) struct StaticsCache {
+    ids: HashMap<(usize, usize), u32>,
+    new: Statics,
+}
+
+impl StaticsCache {
+    /// Serialize `value` with statics replaced by ids.
+    ///
+    /// Returns the serialized value and the statics that weren't previously sent.
+    pub(crate) fn serialize<T>(&mut self, value: &T) -> serde_json::Result<(Value, Statics)>
+    where
+        T: Serialize + ?Sized,
+    {
+        ACTIVE.with(|active| *active.borrow_mut() = Some(std::mem::take(self)));
+
+        let result = serde_json::to_value(value);
+
+        let mut cache = ACTIVE
+            .with(|active| active.borrow_mut().take())
+            .expect("statics cache removed during serialization");
+        let new = std::mem::take(&mut cache.new);
+        *self = cache;
+
+        result.map(|value| (value, new))
+    }
+
+    fn id(&mut self, fixed: &'static [&'static str]) -> u32 {
+        let key = (fixed.as_ptr() as usize, fixed.len());
+        let next_id = self.ids.len() as u32;
+        let new = &mut self.new;
+        *self.ids.entry(key).or_insert_with(|| {
+            new.insert(next_id, fixed);
+            next_id
+        })
+    }
+}
+
+/// Serialize the fixed parts of a template.
+///
+/// Uses the id from the active [`StaticsCache`], if any, otherwise the fixed parts are serialized
+/// as is.
+pub(crate) fn serialize_fixed<S>(
+    fixed: &&'static [&'static str],
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    let id = ACTIVE.with(|active| active.borrow_mut().as_mut().map(|cache| cache.id(fixed)));
+
+    match id {
+        Some(id) => serializer.serialize_u32(id),
+        None => fixed.serialize(serializer),
+    }
+}
+
+pub(crate) fn serialize_optional_fixed<S>(
+    fixed: &Option<&'static [&'static str]>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    match fixed {
+        Some(fixed) => serialize_fixed(fixed, serializer),
+        None => serializer.serialize_none(),
+    }
+}
@@ -446,6 +446,174 @@ fn axm_attribute() {
     );
 }
 
+#[test]
+fn axm_window_attributes() {
+    let view: Html<&str> = html! {
+        <div
+            axm-window-resize={ "resized" }
+            axm-window-beforeunload={ "leaving" }
+            axm-visibility-change={ "visibility" }
+            axm-online={ "online" }
+            axm-offline={ "offline" }
+        ></div>
+    };
+    assert_eq!(
+        view.render(),
+        "<div axm-window-resize=%22resized%22 axm-window-beforeunload=%22leaving%22 \
+         axm-visibility-change=%22visibility%22 axm-online=%22online%22 \
+         axm-offline=%22offline%22></div>"
+    );
+}
+
+#[test]
+fn axm_viewport_attributes() {
+    let view: Html<&str> = html! {
+        <div axm-viewport-enter={ "entered" } axm-viewport-leave={ "left" }></div>
+    };
+    assert_eq!(
+        view.render(),
+        "<div axm-viewport-enter=%22entered%22 axm-viewport-leave=%22left%22></div>"
+    );
+}
+
+#[test]
+fn axm_transition_attributes() {
+    let view: Html<&str> = html! {
+        <div
+            axm-transition-enter="fade-in"
+            axm-transition-leave="fade-out"
+            axm-transition-end={ "gone" }
+        ></div>
+    };
+    assert_eq!(
+        view.render(),
+        "<div axm-transition-enter=fade-in axm-transition-leave=fade-out axm-transition-end=%22gone%22></div>"
+    );
+}
+
+#[test]
+fn axm_key_combos_are_normalized() {
+    let view: Html<&str> = html! {
+        <div
+            axm-window-keydown={ "search" }
+            axm-key="Meta+K, ctrl + k"
+            axm-prevent-default
+        ></div>
+    };
+    assert_eq!(
+        view.render(),
+        "<div axm-window-keydown=%22search%22 axm-key=meta+k,ctrl+k axm-prevent-default></div>"
+    );
+}
+
+#[test]
+fn axm_trigger_action() {
+    let render = |valid: bool| -> String {
+        let view: Html<&str> = html! {
+            <form action="/login" axm-submit={ "submit" } axm-trigger-action=if valid { () }></form>
+        };
+        view.render()
+    };
+    assert_eq!(
+        render(false),
+        "<form action=/login axm-submit=%22submit%22></form>"
+    );
+    assert_eq!(
+        render(true),
+        "<form action=/login axm-trigger-action axm-submit=%22submit%22></form>"
+    );
+}
+
+#[test]
+fn render_with_form_posts() {
+    let view: Html<&str> = html! {
+        <form axm-submit={ "save" } class="signup" title="a > b">
+            <input type="text" name="email" />
+        </form>
+        <button axm-click={ "reset" }>"Reset"</button>
+    };
+    assert_eq!(
+        view.render(),
+        "<form class=signup title=a > b axm-submit=%22save%22><input type=text name=email></form>\
+        <button axm-click=%22reset%22>Reset</button>"
+    );
+    assert_eq!(
+        view.render_with_form_posts(),
+        "<form class=signup title=a > b axm-submit=%22save%22 method=post>\
+        <input type=hidden name=axm-submit value=%22save%22>\
+        <input type=text name=email></form>\
+        <button axm-click=%22reset%22>Reset</button>"
+    );
+
+    for editing in [true, false] {
+        let view: Html<&str> = html! {
+            <form axm-submit=if editing { "save" } else { "create" } class="signup"></form>
+        };
+        let msg = if editing { "save" } else { "create" };
+        assert_eq!(
+            view.render_with_form_posts(),
+            format!(
+                "<form class=signup axm-submit=%22{msg}%22 method=post>\
+                <input type=hidden name=axm-submit value=%22{msg}%22></form>"
+            )
+        );
+    }
+
+    let editing = false;
+    let view: Html<&str> = html! {
+        <form axm-submit=if editing { "save" } class="signup"></form>
+    };
+    assert_eq!(view.render_with_form_posts(), "<form class=signup></form>");
+
+    let view: Html<&str> = html! {
+        <form action="/login" method="post" axm-submit={ "login" }></form>
+    };
+    assert_eq!(view.render_with_form_posts(), view.render());
+}
+
+#[test]
+fn axm_value_attributes() {
+    let name = "Bob Smith";
+    let admin = true;
+    let view: Html<&str> = html! {
+        <button
+            axm-click={ "select" }
+            axm-value-id={ 1 }
+            axm-value-name={ name }
+            axm-value-admin=if admin { { true } }
+            axm-value-kind="user"
+        ></button>
+    };
+    assert_eq!(
+        view.render(),
+        "<button axm-click=%22select%22 axm-value-id=1 axm-value-name=%22Bob%20Smith%22 axm-value-admin=true axm-value-kind=%22user%22></button>"
+    );
+
+    // literals are strings, like string blocks
+    let literal: Html<&str> = html! { <button axm-value-id="123"></button> };
+    let block: Html<&str> = html! { <button axm-value-id={ "123" }></button> };
+    assert_eq!(literal.render(), "<button axm-value-id=%22123%22></button>");
+    assert_eq!(literal.render(), block.render());
+}
+
+#[test]
+fn axm_constructor_binding() {
+    #[derive(Serialize)]
+    enum Msg {
+        Clicked,
+        TextChanged(String),
+    }
+
+    let view: Html<Msg> = html! {
+        <input axm-input={ Msg::TextChanged } axm-click={ Msg::Clicked } />
+    };
+    let rendered = view.render();
+    assert!(rendered.starts_with(
+        "<input axm-input={%22axm-ctor%22:{%22TextChanged%22:null},%22axm-path%22:[%22TextChanged%22],%22axm-kind%22:"
+    ));
+    assert!(rendered.ends_with(" axm-click=%22Clicked%22>"));
+}
+
 #[test]
 fn axm_enum_update_attribute() {
     #[derive(Serialize)]
@@ -470,6 +638,83 @@ fn axm_enum_update_attribute() {
     );
 }
 
+#[test]
+fn axm_js_attribute() {
+    use crate::js_command as js;
+
+    let view: Html<&str> = html! {
+        <button axm-click={ js::toggle_class("#menu", "open").then(js::push("opened")) } />
+    };
+    assert_eq!(
+        view.render(),
+        "<button axm-click={%22axm-js%22:[{%22kind%22:{%22t%22:%22toggle_class%22,%22selector%22:%22#menu%22,%22klass%22:%22open%22},%22delay_ms%22:null}],%22axm-push%22:%22opened%22}>"
+    );
+
+    let view: Html<&str> = html! {
+        <button axm-click={ js::JsChain::from(js::set_title("hi")) } />
+    };
+    assert_eq!(
+        view.render(),
+        "<button axm-click={%22axm-js%22:[{%22kind%22:{%22t%22:%22set_title%22,%22title%22:%22hi%22},%22delay_ms%22:null}]}>"
+    );
+
+    let view = html! { <button axm-click={ js::push(1) } /> }.map(|n: i32| n + 1);
+    assert_eq!(
+        view.render(),
+        "<button axm-click={%22axm-js%22:[],%22axm-push%22:2}>"
+    );
+}
+
+#[test]
+fn axm_js_attribute_bare_command() {
+    use crate::js_command as js;
+
+    let view: Html<&str> = html! {
+        <button axm-click={ js::toggle_class("#m", "open") } />
+    };
+    assert_eq!(
+        view.render(),
+        "<button axm-click={%22axm-js%22:[{%22kind%22:{%22t%22:%22toggle_class%22,%22selector%22:%22#m%22,%22klass%22:%22open%22},%22delay_ms%22:null}]}>"
+    );
+
+    let view: Html<i32> = html! {
+        <button axm-click={ js::toggle_class("#m", "open") }></button>
+        <button axm-click={ 1 }></button>
+    };
+    assert_eq!(
+        view.render(),
+        "<button axm-click={%22axm-js%22:[{%22kind%22:{%22t%22:%22toggle_class%22,%22selector%22:%22#m%22,%22klass%22:%22open%22},%22delay_ms%22:null}]}></button><button axm-click=1></button>"
+    );
+}
+
+#[test]
+fn axm_js_attribute_is_percent_encoded() {
+    use crate::js_command as js;
+
+    let view: Html<&str> = html! {
+        <button axm-click={ js::set_title("a b").then(js::push("opened")) } />
+    };
+    let rendered = view.render();
+    let encoded = rendered
+        .strip_prefix("<button axm-click=")
+        .and_then(|rest| rest.strip_suffix('>'))
+        .unwrap();
+    assert!(!encoded.contains(' ') && !encoded.contains('"'));
+
+    // the client decodes bindings the same way before checking for JS commands
+    let decoded = percent_encoding::percent_decode_str(encoded)
+        .decode_utf8()
+        .unwrap();
+    let chain: serde_json::Value = serde_json::from_str(&decoded).unwrap();
+    assert_eq!(
+        chain,
+        json!({
+            "axm-js": [{ "kind": { "t": "set_title", "title": "a b" }, "delay_ms": null }],
+            "axm-push": "opened",
+        })
+    );
+}
+
 #[test]
 fn diffing_fixed() {
     let old: Html<()> = html! { <div>"old"</div> };
@@ -880,3 +1125,254 @@ fn if_toggle() {
         })
     );
 }
+
+#[test]
+fn statics_are_only_sent_once() {
+    fn render(items: &[i32], flag: bool) -> Html<()> {
+        html! {
+            <ul>
+                for item in items {
+                    <li>{ item }</li>
+                }
+            </ul>
+            if flag {
+                <p>"on"</p>
+            } else {
+                <p>"off"</p>
+            }
+        }
+    }
+
+    let mut cache = StaticsCache::default();
+
+    let a = render(&[1], false);
+    let (json, statics) = cache.serialize(&a).unwrap();
+    assert_json_diff::assert_json_eq!(
+        json,
+        json!({
+            "f": 0,
+            "d": {
+                "0": {
+                    "f": 1,
+                    "b": {
+                        "0": { "0": "1" },
+                    },
+                },
+                "1": { "f": 2 },
+            },
+        })
+    );
+    assert_json_diff::assert_json_eq!(
+        statics,
+        json!({
+            "0": ["<ul>", "</ul>", ""],
+            "1": ["<li>", "</li>"],
+            "2": ["<p>off</p>"],
+        })
+    );
+
+    // the loop and the outer template are already known
+    let b = render(&[1, 2], true);
+    let (json, statics) = cache.serialize(&a.diff(&b)).unwrap();
+    assert_json_diff::assert_json_eq!(
+        json,
+        json!({
+            "d": {
+                "0": {
+                    "b": {
+                        "1": { "0": "2" },
+                    },
+                },
+                "1": { "f": 3 },
+            },
+        })
+    );
+    assert_json_diff::assert_json_eq!(statics, json!({ "3": ["<p>on</p>"] }));
+
+    // toggling back reuses the id
+    let c = render(&[1, 2], false);
+    let (json, statics) = cache.serialize(&b.diff(&c)).unwrap();
+    assert_json_diff::assert_json_eq!(json, json!({ "d": { "1": { "f": 2 } } }));
+    assert!(statics.is_empty());
+
+    // without a cache statics are serialized as is
+    assert_json_diff::assert_json_eq!(json!(c)["f"], json!(["<ul>", "</ul>", ""]));
+}
+
+#[test]
+fn unchanged_parts_are_reused() {
+    use std::{any::type_name, collections::HashSet};
+
+    struct View {
+        a: Tracked<u32>,
+        b: Tracked<u32>,
+        class: Tracked<&'static str>,
+        items: Vec<u32>,
+    }
+
+    impl View {
+        fn render(&self) -> Html<()> {
+            html! {
+                <p>{ self.a.get() }</p>
+                <p>{ self.b.get() }</p>
+                <ul>
+                    for item in &self.items {
+                        <li class={ self.class.get() }>{ item }</li>
+                    }
+                </ul>
+            }
+        }
+    }
+
+    let mut view = View {
+        a: Tracked("a", 1),
+        b: Tracked("b", 1),
+        class: Tracked("class", "item"),
+        items: Vec::from([1, 2]),
+    };
+    let html = view.render();
+    take_evaluated();
+
+    // only `b` changed
+    view.b.1 = 2;
+    let (new_html, previous, reused) = render_reusing(
+        html,
+        type_name::<View>(),
+        HashSet::from(["a", "class", "items"]),
+        || view.render(),
+    );
+    assert_eq!(take_evaluated(), ["b"]);
+    assert_eq!(
+        new_html.render(),
+        "<p>1</p><p>2</p><ul><li class=item>1</li><li class=item>2</li></ul>"
+    );
+    assert_eq!(
+        json!(previous.diff_reusing(&new_html, &reused)),
+        json!({ "d": { "1": "2" } })
+    );
+
+    // the loop is rendered again but the class is reused for the items that already existed
+    view.items.push(3);
+    let (newer_html, previous, reused) = render_reusing(
+        new_html,
+        type_name::<View>(),
+        HashSet::from(["a", "b", "class"]),
+        || view.render(),
+    );
+    assert_eq!(take_evaluated(), ["class"]);
+    assert_eq!(
+        newer_html.render(),
+        "<p>1</p><p>2</p><ul><li class=item>1</li><li class=item>2</li><li class=item>3</li></ul>"
+    );
+    assert_eq!(
+        json!(previous.diff_reusing(&newer_html, &reused)),
+        json!({ "d": { "2": { "b": { "2": { "0": "item", "1": "3" } } } } })
+    );
+}
+
+#[test]
+fn parts_with_macros_are_not_reused() {
+    use std::{any::type_name, collections::HashSet};
+
+    struct View {
+        a: Tracked<u32>,
+        b: Tracked<u32>,
+    }
+
+    // only `a` appears in the template but `b` is read as well
+    macro_rules! with_b {
+        ($view:ident . $field:ident) => {
+            $view.$field.get() + $view.b.get()
+        };
+    }
+
+    impl View {
+        fn render(&self) -> Html<()> {
+            html! { <p>{ with_b!(self.a) }</p> }
+        }
+    }
+
+    let mut view = View {
+        a: Tracked("a", 1),
+        b: Tracked("b", 1),
+    };
+    let html = view.render();
+    take_evaluated();
+
+    view.b.1 = 2;
+    let (new_html, _, _) = render_reusing(html, type_name::<View>(), HashSet::from(["a"]), || {
+        view.render()
+    });
+    assert_eq!(take_evaluated(), ["a", "b"]);
+    assert_eq!(new_html.render(), "<p>3</p>");
+}
+
+#[test]
+fn nested_views_with_same_field_names_are_not_reused() {
+    use std::{any::type_name, collections::HashSet};
+
+    struct Page {
+        title: Tracked<&'static str>,
+        items: Vec<Item>,
+    }
+
+    struct Item {
+        title: Tracked<&'static str>,
+    }
+
+    impl Page {
+        fn render(&self) -> Html<()> {
+            html! {
+                <h1>{ self.title.get() }</h1>
+                <ul>
+                    for item in &self.items {
+                        { item.render() }
+                    }
+                </ul>
+            }
+        }
+    }
+
+    impl Item {
+        fn render(&self) -> Html<()> {
+            html! { <li>{ self.title.get() }</li> }
+        }
+    }
+
+    let mut page = Page {
+        title: Tracked("page", "Page"),
+        items: Vec::from([Item {
+            title: Tracked("item", "a"),
+        }]),
+    };
+    let html = page.render();
+    take_evaluated();
+
+    // the page's `title` is unchanged but the item's is not
+    page.items[0].title.1 = "renamed";
+    let (new_html, _, _) =
+        render_reusing(html, type_name::<Page>(), HashSet::from(["title"]), || {
+            page.render()
+        });
+    assert_eq!(take_evaluated(), ["item"]);
+    assert_eq!(new_html.render(), "<h1>Page</h1><ul><li>renamed</li></ul>");
+}
+
+thread_local! {
+    static EVALUATED: std::cell::RefCell<Vec<&'static str>> =
+        const { std::cell::RefCell::new(Vec::new()) };
+}
+
+/// A field that records when it's read, to check which parts of a template are evaluated.
+struct Tracked<T>(&'static str, T);
+
+impl<T> Tracked<T> {
+    fn get(&self) -> &T {
+        EVALUATED.with(|evaluated| evaluated.borrow_mut().push(self.0));
+        &self.1
+    }
+}
+
+fn take_evaluated() -> Vec<&'static str> {
+    EVALUATED.with(|evaluated| std::mem::take(&mut *evaluated.borrow_mut()))
+}
@@ -0,0 +1,376 @@
+//! Tracing and metrics instrumentation for running views.
+//!
+//! Metrics are only recorded if the `metrics` feature is enabled and connections are only
+//! registered with the dashboard if the `dashboard` feature is enabled. Without them all the
+//! functions in this module that deal with metrics or the dashboard compile to nothing.
+
+use crate::live_view::ViewHandle;
+use http::Uri;
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt, io,
+    sync::atomic::{AtomicU64, Ordering},
+    time::Duration,
+};
+use tracing::Span;
+
+/// Identifier for a single WebSocket connection running a view.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(transparent)]
+pub(crate) struct ConnectionId(u64);
+
+impl ConnectionId {
+    pub(crate) fn next() -> Self {
+        static NEXT: AtomicU64 = AtomicU64::new(0);
+        Self(NEXT.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
+impl fmt::Display for ConnectionId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// Create the span that wraps everything happening on a connection.
+pub(crate) fn connection_span(view: &'static str, connection_id: ConnectionId) -> Span {
+    tracing::info_span!("live_view", view, connection_id = %connection_id)
+}
+
+/// Create the span for updating a view with a batch of `messages`, including the following render
+/// and diff.
+///
+/// The timings are recorded with [`UpdateTimings::record`] once they're known.
+pub(crate) fn update_span(messages: usize) -> Span {
+    tracing::debug_span!(
+        "update",
+        messages,
+        update_duration = tracing::field::Empty,
+        render_duration = tracing::field::Empty,
+        diff_duration = tracing::field::Empty,
+        diff_bytes = tracing::field::Empty,
+    )
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+pub(crate) struct UpdateTimings {
+    pub(crate) update: Duration,
+    pub(crate) render: Duration,
+    pub(crate) diff: Duration,
+    pub(crate) diff_bytes: usize,
+}
+
+impl UpdateTimings {
+    pub(crate) fn record(&self, view: &'static str, span: &Span) {
+        span.record("update_duration", tracing::field::debug(self.update));
+        span.record("render_duration", tracing::field::debug(self.render));
+        span.record("diff_duration", tracing::field::debug(self.diff));
+        span.record("diff_bytes", self.diff_bytes);
+
+        #[cfg(feature = "metrics")]
+        {
+            ::metrics::histogram!(
+                "axum_live_view_update_duration_seconds",
+                self.update.as_secs_f64(),
+                "view" => view,
+            );
+            ::metrics::histogram!(
+                "axum_live_view_render_duration_seconds",
+                self.render.as_secs_f64(),
+                "view" => view,
+            );
+            ::metrics::histogram!(
+                "axum_live_view_diff_duration_seconds",
+                self.diff.as_secs_f64(),
+                "view" => view,
+            );
+            ::metrics::histogram!(
+                "axum_live_view_diff_bytes",
+                self.diff_bytes as f64,
+                "view" => view,
+            );
+        }
+        #[cfg(not(feature = "metrics"))]
+        let _ = view;
+    }
+}
+
+/// Calculate the number of bytes `value` takes up when serialized as JSON, without allocating
+/// the JSON.
+pub(crate) fn serialized_len<T>(value: &T) -> usize
+where
+    T: Serialize + ?Sized,
+{
+    struct Counter(usize);
+
+    impl io::Write for Counter {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0 += buf.len();
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let mut counter = Counter(0);
+    let _ = serde_json::to_writer(&mut counter, value);
+    counter.0
+}
+
+/// Guard that tracks a view as active for as long as its alive.
+///
+/// If the `dashboard` feature is enabled this also registers the connection so it shows up in the
+/// dashboard.
+#[derive(Debug)]
+pub(crate) struct ActiveView {
+    #[cfg_attr(not(feature = "metrics"), allow(dead_code))]
+    view: &'static str,
+    #[cfg(feature = "dashboard")]
+    registration: crate::dashboard::Registration,
+}
+
+#[cfg_attr(not(feature = "metrics"), allow(clippy::unused_self))]
+impl ActiveView {
+    pub(crate) fn new<M>(
+        view: &'static str,
+        connection_id: ConnectionId,
+        uri: &Uri,
+        handle: &ViewHandle<M>,
+    ) -> Self
+    where
+        M: Send + 'static,
+    {
+        #[cfg(feature = "metrics")]
+        ::metrics::increment_gauge!("axum_live_view_active_views", 1.0, "view" => view);
+
+        #[cfg(not(feature = "dashboard"))]
+        let _ = (connection_id, uri, handle);
+
+        Self {
+            view,
+            #[cfg(feature = "dashboard")]
+            registration: crate::dashboard::register(view, connection_id, uri.clone(), handle),
+        }
+    }
+
+    pub(crate) fn message_received(&self) {
+        #[cfg(feature = "metrics")]
+        ::metrics::increment_counter!("axum_live_view_messages_total", "view" => self.view);
+
+        #[cfg(feature = "dashboard")]
+        self.registration.message_received();
+    }
+
+    #[cfg_attr(not(feature = "dashboard"), allow(clippy::unused_self))]
+    pub(crate) fn diff_sent<T>(&self, diff: &T)
+    where
+        T: Serialize + ?Sized,
+    {
+        #[cfg(feature = "dashboard")]
+        self.registration.diff_sent(serialized_len(diff));
+
+        #[cfg(not(feature = "dashboard"))]
+        let _ = diff;
+    }
+
+    pub(crate) fn socket_error(&self) {
+        #[cfg(feature = "metrics")]
+        ::metrics::increment_counter!("axum_live_view_socket_errors_total", "view" => self.view);
+    }
+
+    pub(crate) fn client_reconnected(&self) {
+        #[cfg(feature = "metrics")]
+        ::metrics::increment_counter!("axum_live_view_reconnects_total", "view" => self.view);
+    }
+
+    /// Wait until the connection has been disconnected from the dashboard.
+    ///
+    /// Never completes if the `dashboard` feature is disabled.
+    pub(crate) async fn disconnect_requested(&self) {
+        #[cfg(feature = "dashboard")]
+        self.registration.disconnect_requested().await;
+
+        #[cfg(not(feature = "dashboard"))]
+        futures_util::future::pending::<()>().await;
+    }
+}
+
+impl Drop for ActiveView {
+    fn drop(&mut self) {
+        #[cfg(feature = "metrics")]
+        ::metrics::decrement_gauge!("axum_live_view_active_views", 1.0, "view" => self.view);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{
+        collections::BTreeMap,
+        sync::{Arc, Mutex},
+    };
+    use tracing::{
+        field::{Field, Visit},
+        span::{Attributes, Id, Record},
+        Subscriber,
+    };
+    use tracing_subscriber::{
+        layer::{Context, SubscriberExt},
+        registry::LookupSpan,
+        Layer,
+    };
+
+    /// Layer that records the fields of all spans as `span.field => value`.
+    #[derive(Default, Clone)]
+    struct SpanFields(Arc<Mutex<BTreeMap<String, String>>>);
+
+    struct Visitor<'a> {
+        span: &'static str,
+        fields: &'a mut BTreeMap<String, String>,
+    }
+
+    impl Visit for Visitor<'_> {
+        fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+            self.fields
+                .insert(format!("{}.{}", self.span, field), format!("{:?}", value));
+        }
+    }
+
+    impl<S> Layer<S> for SpanFields
+    where
+        S: Subscriber + for<'a> LookupSpan<'a>,
+    {
+        fn on_new_span(&self, attrs: &Attributes<'_>, _id: &Id, _ctx: Context<'_, S>) {
+            attrs.record(&mut Visitor {
+                span: attrs.metadata().name(),
+                fields: &mut self.0.lock().unwrap(),
+            });
+        }
+
+        fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+            values.record(&mut Visitor {
+                span: ctx.span(id).unwrap().name(),
+                fields: &mut self.0.lock().unwrap(),
+            });
+        }
+    }
+
+    #[test]
+    fn span_fields() {
+        let fields = SpanFields::default();
+        let subscriber = tracing_subscriber::registry().with(fields.clone());
+
+        tracing::subscriber::with_default(subscriber, || {
+            let span = connection_span("Counter", ConnectionId(3));
+            let _guard = span.enter();
+
+            let span = update_span(2);
+            UpdateTimings {
+                update: Duration::from_millis(1),
+                render: Duration::from_millis(2),
+                diff: Duration::from_millis(3),
+                diff_bytes: 42,
+            }
+            .record("Counter", &span);
+        });
+
+        let fields = fields.0.lock().unwrap();
+        let fields = fields
+            .iter()
+            .map(|(field, value)| (field.as_str(), value.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            fields,
+            [
+                ("live_view.connection_id", "3"),
+                ("live_view.view", "\"Counter\""),
+                ("update.diff_bytes", "42"),
+                ("update.diff_duration", "3ms"),
+                ("update.messages", "2"),
+                ("update.render_duration", "2ms"),
+                ("update.update_duration", "1ms"),
+            ]
+        );
+    }
+
+    #[cfg(feature = "metrics")]
+    #[test]
+    fn metrics() {
+        use ::metrics::{Counter, Gauge, Histogram, Key, KeyName, Recorder, SharedString, Unit};
+        use std::sync::atomic::AtomicU64;
+
+        /// Recorder that keeps counters and gauges of the test view, ignoring other tests running
+        /// at the same time.
+        struct TestRecorder(Mutex<BTreeMap<String, Arc<AtomicU64>>>);
+
+        impl TestRecorder {
+            fn metric(&self, key: &Key) -> Option<Arc<AtomicU64>> {
+                let is_test_view = key
+                    .labels()
+                    .any(|label| label.key() == "view" && label.value() == "instrument::tests");
+                if !is_test_view {
+                    return None;
+                }
+
+                let mut metrics = self.0.lock().unwrap();
+                Some(metrics.entry(key.name().to_owned()).or_default().clone())
+            }
+
+            fn counter(&self, name: &str) -> u64 {
+                self.0
+                    .lock()
+                    .unwrap()
+                    .get(name)
+                    .map_or(0, |value| value.load(Ordering::SeqCst))
+            }
+
+            fn gauge(&self, name: &str) -> f64 {
+                f64::from_bits(self.counter(name))
+            }
+        }
+
+        impl Recorder for TestRecorder {
+            fn describe_counter(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+            fn describe_gauge(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+            fn describe_histogram(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+
+            fn register_counter(&self, key: &Key) -> Counter {
+                self.metric(key)
+                    .map_or_else(Counter::noop, Counter::from_arc)
+            }
+
+            fn register_gauge(&self, key: &Key) -> Gauge {
+                self.metric(key).map_or_else(Gauge::noop, Gauge::from_arc)
+            }
+
+            fn register_histogram(&self, _: &Key) -> Histogram {
+                Histogram::noop()
+            }
+        }
+
+        static RECORDER: TestRecorder = TestRecorder(Mutex::new(BTreeMap::new()));
+        ::metrics::set_recorder(&RECORDER).unwrap();
+
+        let (handle, _rx) = ViewHandle::<()>::new();
+        let active = ActiveView::new(
+            "instrument::tests",
+            ConnectionId::next(),
+            &Uri::from_static("/"),
+            &handle,
+        );
+        assert_eq!(RECORDER.gauge("axum_live_view_active_views"), 1.0);
+
+        active.message_received();
+        active.message_received();
+        active.socket_error();
+        active.client_reconnected();
+        assert_eq!(RECORDER.counter("axum_live_view_messages_total"), 2);
+        assert_eq!(RECORDER.counter("axum_live_view_socket_errors_total"), 1);
+        assert_eq!(RECORDER.counter("axum_live_view_reconnects_total"), 1);
+
+        drop(active);
+        assert_eq!(RECORDER.gauge("axum_live_view_active_views"), 0.0);
+    }
+}
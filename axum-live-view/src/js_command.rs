@@ -1,4 +1,30 @@
 //! JavaScript commands for performing additional kinds of actions directly in the browser.
+//!
+//! Commands can either be sent from [`LiveView::update`] using [`Updated::with`] or bound directly
+//! to events in [`html!`], in which case they run in the browser without a round-trip to the
+//! server:
+//!
+//! ```
+//! use axum_live_view::{html, js_command as js, Html};
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize, PartialEq, Clone)]
+//! enum Msg {
+//!     Opened,
+//! }
+//!
+//! let html: Html<Msg> = html! {
+//!     <button axm-click={ js::toggle_class("#menu", "open").then(js::push(Msg::Opened)) }>
+//!         "Menu"
+//!     </button>
+//! };
+//! ```
+//!
+//! A single command can be bound on its own, like `axm-click={ js::focus("#search") }`.
+//!
+//! [`LiveView::update`]: crate::live_view::LiveView::update
+//! [`Updated::with`]: crate::live_view::Updated::with
+//! [`html!`]: crate::html!
 
 use axum::http::Uri;
 use serde::{Deserialize, Serialize};
@@ -34,19 +60,157 @@ impl JsCommand {
         self.delay_ms = Some(duration.as_millis() as _);
         self
     }
+
+    /// Run another command, or send a message to the view, after this command.
+    ///
+    /// This is used to bind commands to events in [`html!`]. See the [module docs](self) for an
+    /// example.
+    ///
+    /// [`html!`]: crate::html!
+    pub fn then<T>(self, next: impl Into<JsChain<T>>) -> JsChain<T> {
+        JsChain::from(self).then(next)
+    }
+}
+
+/// A chain of [`JsCommand`]s, optionally followed by a message, bound to an event in [`html!`].
+///
+/// The commands run in the browser, in order, without a round-trip to the server. If the chain
+/// contains a message it's sent to the view after the commands have run.
+///
+/// Created with [`JsCommand::then`], [`push`], or `JsChain::from` for a single command.
+///
+/// [`html!`]: crate::html!
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct JsChain<T> {
+    #[serde(rename = "axm-js")]
+    commands: Vec<JsCommand>,
+    #[serde(rename = "axm-push", skip_serializing_if = "Option::is_none")]
+    push: Option<T>,
+}
+
+impl<T> JsChain<T> {
+    /// Run another command, or send a message to the view, after this chain.
+    ///
+    /// If both chains contain a message only the message from `next` is sent.
+    pub fn then(mut self, next: impl Into<JsChain<T>>) -> Self {
+        let next = next.into();
+        self.commands.extend(next.commands);
+        if next.push.is_some() {
+            self.push = next.push;
+        }
+        self
+    }
+
+    pub(crate) fn into_push(self) -> Option<T> {
+        self.push
+    }
+
+    pub(crate) fn map<F, K>(self, f: F) -> JsChain<K>
+    where
+        F: FnOnce(T) -> K,
+    {
+        JsChain {
+            commands: self.commands,
+            push: self.push.map(f),
+        }
+    }
+}
+
+impl<T> From<JsCommand> for JsChain<T> {
+    fn from(cmd: JsCommand) -> Self {
+        Self {
+            commands: vec![cmd],
+            push: None,
+        }
+    }
+}
+
+/// Send a message to the view after the other commands in a [`JsChain`] have run.
+///
+/// # Example
+///
+/// ```
+/// use axum_live_view::js_command as js;
+///
+/// # #[derive(serde::Serialize)] enum Msg { Opened }
+/// js::add_class("#menu", "open").then(js::push(Msg::Opened));
+/// ```
+pub fn push<T>(msg: T) -> JsChain<T> {
+    JsChain {
+        commands: Vec::new(),
+        push: Some(msg),
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
 #[serde(tag = "t")]
 pub(crate) enum JsCommandKind {
-    NavigateTo { uri: String },
-    AddClass { selector: String, klass: String },
-    RemoveClass { selector: String, klass: String },
-    ToggleClass { selector: String, klass: String },
-    ClearValue { selector: String },
-    SetTitle { title: String },
-    HistoryPushState { uri: String },
+    NavigateTo {
+        uri: String,
+    },
+    AddClass {
+        selector: String,
+        klass: String,
+    },
+    RemoveClass {
+        selector: String,
+        klass: String,
+    },
+    ToggleClass {
+        selector: String,
+        klass: String,
+    },
+    ClearValue {
+        selector: String,
+    },
+    SetTitle {
+        title: String,
+    },
+    HistoryPushState {
+        uri: String,
+    },
+    Reload,
+    Focus {
+        selector: String,
+    },
+    Blur {
+        selector: String,
+    },
+    FocusFirst {
+        selector: String,
+    },
+    SetAttribute {
+        selector: String,
+        name: String,
+        value: String,
+    },
+    RemoveAttribute {
+        selector: String,
+        name: String,
+    },
+    Show {
+        selector: String,
+        options: VisibilityOptions,
+    },
+    Hide {
+        selector: String,
+        options: VisibilityOptions,
+    },
+    ScrollIntoView {
+        selector: String,
+    },
+    DispatchEvent {
+        selector: String,
+        event: String,
+        detail: serde_json::Value,
+    },
+    Announce {
+        text: String,
+    },
+    TriggerAction {
+        selector: String,
+    },
 }
 
 impl From<JsCommandKind> for JsCommand {
@@ -123,6 +287,8 @@ pub fn toggle_class(selector: impl Into<String>, klass: impl Into<String>) -> Js
 
 /// Clear the value of input fields matching a CSS selector.
 ///
+/// For inputs with `axm-controlled` rendering an empty value does the same.
+///
 /// # Example
 ///
 /// ```
@@ -166,3 +332,272 @@ pub fn history_push_state(uri: Uri) -> JsCommand {
     }
     .into()
 }
+
+/// Reload the page.
+///
+/// This calls [`Location.reload`].
+///
+/// # Example
+///
+/// ```
+/// axum_live_view::js_command::reload();
+/// ```
+///
+/// [`Location.reload`]: https://developer.mozilla.org/en-US/docs/Web/API/Location/reload
+pub fn reload() -> JsCommand {
+    JsCommandKind::Reload.into()
+}
+
+/// Focus the first element matching a CSS selector.
+///
+/// # Example
+///
+/// ```
+/// axum_live_view::js_command::focus("#search");
+/// ```
+pub fn focus(selector: impl Into<String>) -> JsCommand {
+    JsCommandKind::Focus {
+        selector: selector.into(),
+    }
+    .into()
+}
+
+/// Remove focus from the first element matching a CSS selector.
+///
+/// # Example
+///
+/// ```
+/// axum_live_view::js_command::blur("#search");
+/// ```
+pub fn blur(selector: impl Into<String>) -> JsCommand {
+    JsCommandKind::Blur {
+        selector: selector.into(),
+    }
+    .into()
+}
+
+/// Focus the first focusable element inside the first element matching a CSS selector.
+///
+/// Useful for moving focus into a modal when it opens.
+///
+/// # Example
+///
+/// ```
+/// axum_live_view::js_command::focus_first("#modal");
+/// ```
+pub fn focus_first(selector: impl Into<String>) -> JsCommand {
+    JsCommandKind::FocusFirst {
+        selector: selector.into(),
+    }
+    .into()
+}
+
+/// Set an attribute on elements matching a CSS selector.
+///
+/// # Example
+///
+/// ```
+/// axum_live_view::js_command::set_attribute("#menu-button", "aria-expanded", "true");
+/// ```
+pub fn set_attribute(
+    selector: impl Into<String>,
+    name: impl Into<String>,
+    value: impl Into<String>,
+) -> JsCommand {
+    JsCommandKind::SetAttribute {
+        selector: selector.into(),
+        name: name.into(),
+        value: value.into(),
+    }
+    .into()
+}
+
+/// Remove an attribute from elements matching a CSS selector.
+///
+/// # Example
+///
+/// ```
+/// axum_live_view::js_command::remove_attribute("#submit", "disabled");
+/// ```
+pub fn remove_attribute(selector: impl Into<String>, name: impl Into<String>) -> JsCommand {
+    JsCommandKind::RemoveAttribute {
+        selector: selector.into(),
+        name: name.into(),
+    }
+    .into()
+}
+
+/// Show elements matching a CSS selector.
+///
+/// This sets `style.display`, to `block` unless another value is set with
+/// [`VisibilityOptions::display`].
+///
+/// # Example
+///
+/// ```
+/// use axum_live_view::js_command::{self, VisibilityOptions};
+/// use std::time::Duration;
+///
+/// js_command::show(
+///     "#modal",
+///     VisibilityOptions::new()
+///         .display("flex")
+///         .transition("fade-in", Duration::from_millis(200)),
+/// );
+/// ```
+pub fn show(selector: impl Into<String>, options: VisibilityOptions) -> JsCommand {
+    JsCommandKind::Show {
+        selector: selector.into(),
+        options,
+    }
+    .into()
+}
+
+/// Hide elements matching a CSS selector.
+///
+/// This sets `style.display` to `none`. If a transition is set the elements are hidden once it
+/// has finished.
+///
+/// # Example
+///
+/// ```
+/// use axum_live_view::js_command::{self, VisibilityOptions};
+/// use std::time::Duration;
+///
+/// js_command::hide(
+///     "#modal",
+///     VisibilityOptions::new().transition("fade-out", Duration::from_millis(200)),
+/// );
+/// ```
+pub fn hide(selector: impl Into<String>, options: VisibilityOptions) -> JsCommand {
+    JsCommandKind::Hide {
+        selector: selector.into(),
+        options,
+    }
+    .into()
+}
+
+/// Options for [`show`] and [`hide`].
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct VisibilityOptions {
+    display: Option<String>,
+    transition: Option<Transition>,
+}
+
+impl VisibilityOptions {
+    /// Create a new `VisibilityOptions` with the default settings.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the `display` value used when showing elements.
+    ///
+    /// Defaults to `block`. Has no effect on [`hide`].
+    pub fn display(mut self, display: impl Into<String>) -> Self {
+        self.display = Some(display.into());
+        self
+    }
+
+    /// Add classes to the elements while they're being shown or hidden.
+    ///
+    /// `classes` is a space separated list of classes, which are removed again after `duration`.
+    /// The duration will be rounded the nearest millisecond.
+    pub fn transition(mut self, classes: impl Into<String>, duration: Duration) -> Self {
+        self.transition = Some(Transition {
+            classes: classes.into(),
+            duration_ms: duration.as_millis() as _,
+        });
+        self
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+struct Transition {
+    classes: String,
+    duration_ms: u64,
+}
+
+/// Scroll the first element matching a CSS selector into view.
+///
+/// This calls [`Element.scrollIntoView`].
+///
+/// # Example
+///
+/// ```
+/// axum_live_view::js_command::scroll_into_view("#latest-message");
+/// ```
+///
+/// [`Element.scrollIntoView`]: https://developer.mozilla.org/en-US/docs/Web/API/Element/scrollIntoView
+pub fn scroll_into_view(selector: impl Into<String>) -> JsCommand {
+    JsCommandKind::ScrollIntoView {
+        selector: selector.into(),
+    }
+    .into()
+}
+
+/// Dispatch a [`CustomEvent`] on elements matching a CSS selector.
+///
+/// The event bubbles and `detail` is available as `event.detail`. This is useful for integrating
+/// with other JavaScript on the page. Use [`serde_json::to_value`] to create `detail` from your
+/// own types.
+///
+/// # Example
+///
+/// ```
+/// use serde_json::json;
+///
+/// axum_live_view::js_command::dispatch_event("#chart", "chart:update", json!({ "points": [1, 2, 3] }));
+/// ```
+///
+/// [`CustomEvent`]: https://developer.mozilla.org/en-US/docs/Web/API/CustomEvent
+pub fn dispatch_event(
+    selector: impl Into<String>,
+    event: impl Into<String>,
+    detail: serde_json::Value,
+) -> JsCommand {
+    JsCommandKind::DispatchEvent {
+        selector: selector.into(),
+        event: event.into(),
+        detail,
+    }
+    .into()
+}
+
+/// Announce a message to screen readers.
+///
+/// The text is placed in a visually hidden [`aria-live`] region that's added to the page.
+///
+/// # Example
+///
+/// ```
+/// axum_live_view::js_command::announce("3 results found");
+/// ```
+///
+/// [`aria-live`]: https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Attributes/aria-live
+pub fn announce(text: impl Into<String>) -> JsCommand {
+    JsCommandKind::Announce { text: text.into() }.into()
+}
+
+/// Submit a form as a regular HTTP request.
+///
+/// The first form matching the CSS selector is submitted to its `action`, using its `method`,
+/// without going through `axm-submit`. This is useful for things that can't be done over the
+/// WebSocket, such as setting a session cookie when logging in. Validate the form in
+/// [`LiveView::update`] and send this command once it's valid. A regular axum handler can then
+/// process the request and redirect.
+///
+/// Forms can also be submitted by rendering them with the `axm-trigger-action` attribute.
+///
+/// # Example
+///
+/// ```
+/// axum_live_view::js_command::trigger_action("#login");
+/// ```
+///
+/// [`LiveView::update`]: crate::live_view::LiveView::update
+pub fn trigger_action(selector: impl Into<String>) -> JsCommand {
+    JsCommandKind::TriggerAction {
+        selector: selector.into(),
+    }
+    .into()
+}
@@ -200,6 +200,244 @@
 //!
 //! See [`html`](macro.html.html) for details on all the support bindings.
 //!
+//! ## Values
+//!
+//! Extra data can be sent along with an event using `axm-value-*` attributes, instead of adding
+//! it to the message. The values are available through [`EventData::values`](event_data::EventData::values):
+//!
+//! ```rust
+//! # use axum_live_view::html;
+//! # #[derive(serde::Serialize, serde::Deserialize, Debug, PartialEq)]
+//! # enum Msg { Delete }
+//! # let id = 1;
+//! #
+//! html! {
+//!     <button axm-click={ Msg::Delete } axm-value-id={ id }>"Delete"</button>
+//! };
+//! ```
+//!
+//! See [`Values`](event_data::Values) for more details.
+//!
+//! ## Message constructors
+//!
+//! Bindings also accept a function, such as a tuple variant, whose argument is filled in from the
+//! event data. This avoids having to dig the value out of [`EventData`](event_data::EventData) in `update`:
+//!
+//! ```rust
+//! # use axum_live_view::html;
+//! # #[derive(serde::Serialize, serde::Deserialize, Debug, PartialEq)]
+//! # enum Msg { NameChanged(String) }
+//! #
+//! # let _: axum_live_view::Html<Msg> =
+//! html! {
+//!     <input type="text" axm-input={ Msg::NameChanged } />
+//! };
+//! ```
+//!
+//! The argument must implement [`FromEventData`](event_data::FromEventData), whose
+//! [`Kind`](event_data::FromEventData::Kind) says which events it can be created from. Binding a
+//! constructor to an event that doesn't send that kind of data fails to compile:
+//!
+//! ```rust,compile_fail
+//! # use axum_live_view::html;
+//! # #[derive(serde::Serialize, serde::Deserialize, Debug, PartialEq)]
+//! # enum Msg { NameChanged(String) }
+//! #
+//! # let _: axum_live_view::Html<Msg> =
+//! html! {
+//!     // clicks don't send any text
+//!     <button axm-click={ Msg::NameChanged }>"Save"</button>
+//! };
+//! ```
+//!
+//! If the value still doesn't fit when the event happens, for example binding
+//! `Msg::AgeChanged(u32)` to an input that contains `"abc"`, a warning is logged and the event is
+//! ignored.
+//!
+//! The argument is located in the message by rendering it with a placeholder. If the message
+//! contains other values of the argument's type that equal the placeholder, such as another
+//! `bool`, the argument can't be located and the view is stopped with an error.
+//!
+//! ## Key bindings
+//!
+//! `axm-keydown`, `axm-keyup`, and their `axm-window-*` counterparts can be limited to specific
+//! keys with `axm-key`. It accepts a key, optionally prefixed with `ctrl`, `alt`, `shift`, or `meta`
+//! modifiers, and alternatives separated by commas. Only matching keystrokes are sent to the
+//! server and `axm-prevent-default` stops the browser's default behavior for those keystrokes:
+//!
+//! ```rust
+//! # use axum_live_view::html;
+//! # #[derive(serde::Serialize, serde::Deserialize, Debug, PartialEq)]
+//! # enum Msg { OpenSearch, Submit }
+//! #
+//! html! {
+//!     <div axm-window-keydown={ Msg::OpenSearch } axm-key="ctrl+k, meta+k" axm-prevent-default></div>
+//!     <textarea axm-keydown={ Msg::Submit } axm-key="shift+Enter"></textarea>
+//! };
+//! ```
+//!
+//! Keys are either single characters or names of keys such as `Enter`, `Escape`, `ArrowUp`,
+//! `Space`, `Plus`, `CapsLock`, or `F1` through `F24`, compared case insensitively. Literal
+//! combos are checked at compile time so something like `axm-key="ctl+k"` is a compile error.
+//! Combos from blocks, such as `axm-key={ shortcut }`, aren't checked but are otherwise handled
+//! the same way.
+//!
+//! Pointer, touch, and wheel events, along with `axm-dblclick` and `axm-contextmenu`, also only
+//! stop the browser's default behavior with `axm-prevent-default`, so binding them doesn't
+//! prevent scrolling or the context menu.
+//!
+//! ## Loading states
+//!
+//! While an event is being processed by the server the element that sent it gets the
+//! `axm-loading` class. Elements that send `axm-click` or `axm-submit` events are also disabled,
+//! along with a form's submit buttons, which prevents double submits. `axm-disable-with` sets the
+//! text of a button while it's disabled:
+//!
+//! ```rust
+//! # use axum_live_view::html;
+//! # #[derive(serde::Serialize, serde::Deserialize, Debug, PartialEq)]
+//! # enum Msg { Save }
+//! #
+//! html! {
+//!     <form axm-submit={ Msg::Save }>
+//!         <button type="submit" axm-disable-with="Saving...">"Save"</button>
+//!     </form>
+//! };
+//! ```
+//!
+//! The loading state is cleared once the server has sent the resulting update.
+//!
+//! ## Submitting forms over HTTP
+//!
+//! Some things, like setting a session cookie, can't be done over the WebSocket. Forms rendered
+//! with `axm-trigger-action` are submitted as regular HTTP requests to their `action`, so a view
+//! can validate a form in `update` and then hand it off to a normal axum handler:
+//!
+//! ```rust
+//! # use axum_live_view::html;
+//! # #[derive(serde::Serialize, serde::Deserialize, Debug, PartialEq)]
+//! # enum Msg { Validate, Submit }
+//! # let (submitted, valid) = (true, true);
+//! #
+//! // only submit once the user has submitted the form and `update` has found it to be valid
+//! html! {
+//!     <form
+//!         action="/login"
+//!         method="post"
+//!         axm-change={ Msg::Validate }
+//!         axm-submit={ Msg::Submit }
+//!         axm-trigger-action=if submitted && valid { () }
+//!     >
+//!         <input type="email" name="email" />
+//!         <input type="password" name="password" />
+//!     </form>
+//! };
+//! ```
+//!
+//! [`js_command::trigger_action`] does the same from `update`.
+//!
+//! ## Forms without JavaScript
+//!
+//! Forms bound with `axm-submit` can also be made to work before the JavaScript client has
+//! connected, or without JavaScript at all, by rendering them as regular forms that post their
+//! message and values back to the page. Opt in with [`LiveViewUpgrade::forms`] and route the
+//! handler for `POST` as well:
+//!
+//! ```rust
+//! # use axum::{response::IntoResponse, routing::get, Router};
+//! use axum_live_view::{extract::FormPost, LiveViewUpgrade};
+//!
+//! async fn handler(live: LiveViewUpgrade, form: Option<FormPost>) -> impl IntoResponse {
+//!     live.forms(form).response(|embed_live_view| {
+//!         // ...
+//! #       embed_live_view.embed(View)
+//!     })
+//! }
+//!
+//! let app = Router::new().route("/", get(handler).post(handler));
+//! # let _: Router = app;
+//! #
+//! # struct View;
+//! # impl axum_live_view::LiveView for View {
+//! #     type Message = ();
+//! #     fn update(self, _: (), _: Option<axum_live_view::event_data::EventData>) -> axum_live_view::live_view::Updated<Self> { todo!() }
+//! #     fn render(&self) -> axum_live_view::Html<()> { todo!() }
+//! # }
+//! ```
+//!
+//! See [`LiveViewUpgrade::forms`] for the details.
+//!
+//! ## Controlled inputs
+//!
+//! By default the value of an input, textarea, or select is owned by the browser once the page
+//! has loaded, so the values the server renders afterwards are ignored. Adding `axm-controlled`
+//! makes the rendered value win instead, which allows resetting or normalizing what was typed:
+//!
+//! ```rust
+//! # use axum_live_view::html;
+//! # #[derive(serde::Serialize, serde::Deserialize, Debug, PartialEq)]
+//! # enum Msg { CodeChanged }
+//! # let code = String::new();
+//! #
+//! html! {
+//!     <input
+//!         type="text"
+//!         value={ code.to_uppercase() }
+//!         axm-input={ Msg::CodeChanged }
+//!         axm-controlled
+//!     />
+//! };
+//! ```
+//!
+//! The value of a focused input is kept if the user has typed something that hasn't been sent to
+//! the server yet, so slow updates don't undo their edits.
+//!
+//! ## Transitions
+//!
+//! Elements can be animated when they're added or removed by a view update using
+//! `axm-transition-enter` and `axm-transition-leave`. The classes are added to the element and,
+//! for `axm-transition-leave`, the element is only removed once its CSS animation or transition
+//! has finished. `axm-transition-end` sends a message when the animation has finished:
+//!
+//! ```rust
+//! # use axum_live_view::html;
+//! # #[derive(serde::Serialize, serde::Deserialize, Debug, PartialEq)]
+//! # enum Msg { ToastHidden }
+//! #
+//! html! {
+//!     <div
+//!         class="toast"
+//!         axm-transition-enter="fade-in"
+//!         axm-transition-leave="fade-out"
+//!         axm-transition-end={ Msg::ToastHidden }
+//!     >
+//!         "Saved!"
+//!     </div>
+//! };
+//! ```
+//!
+//! # Instrumentation
+//!
+//! Each WebSocket connection runs inside a `live_view` [`tracing`] span with the view's type name
+//! and a connection id. Every call to `update` gets a child `update` span that records how long
+//! updating, rendering, and diffing took, as well as the size of the diff sent to the client.
+//!
+//! If the `metrics` cargo feature is enabled axum-live-view will also record the following
+//! metrics using the [`metrics`](https://crates.io/crates/metrics) crate. All metrics are
+//! labeled with `view`.
+//!
+//! - `axum_live_view_active_views` (gauge): The number of connected views.
+//! - `axum_live_view_messages_total` (counter): The number of messages received.
+//! - `axum_live_view_socket_errors_total` (counter): The number of WebSocket errors.
+//! - `axum_live_view_reconnects_total` (counter): The number of times clients have reconnected.
+//! - `axum_live_view_update_duration_seconds`, `axum_live_view_render_duration_seconds`,
+//!   `axum_live_view_diff_duration_seconds` (histograms): Time spent in each step of processing
+//!   a message.
+//! - `axum_live_view_diff_bytes` (histogram): The size of the diffs sent to clients.
+//!
+//! The `dashboard` cargo feature enables the `dashboard` module which provides routes for a
+//! dashboard that shows all connected views in real time.
+//!
 //! # Pros and cons
 //!
 //! Some pros and cons to keep in mind when deciding whether axum-live-view is right for your use
@@ -266,13 +504,24 @@
 #[macro_use]
 mod macros;
 
+#[cfg(feature = "dashboard")]
+#[cfg_attr(docsrs, doc(cfg(feature = "dashboard")))]
+pub mod dashboard;
 pub mod event_data;
 pub mod extract;
+pub mod form;
 pub mod js_command;
+#[cfg(feature = "live-reload")]
+#[cfg_attr(docsrs, doc(cfg(feature = "live-reload")))]
+pub mod live_reload;
 pub mod live_view;
+pub mod record;
+pub mod session;
+pub mod shutdown;
 pub mod test;
 
 mod html;
+mod instrument;
 mod life_cycle;
 mod util;
 
@@ -1,14 +1,19 @@
 use crate::{
     event_data::EventData,
-    html::Html,
-    js_command::JsCommand,
+    html::{self, CtorMsg, Html, Resolvers, Reused, Statics, StaticsCache},
+    instrument::{self, ActiveView, ConnectionId, UpdateTimings},
+    js_command::{self, JsCommand},
     live_view::{Updated, ViewHandle},
+    record::Recorder,
+    session::{Checkpoint, Session},
+    shutdown::{Shutdown, ShutdownSignal},
     util::ReceiverStream,
     LiveView,
 };
 use futures_util::{
+    future::FutureExt,
     sink::{Sink, SinkExt},
-    stream::StreamExt,
+    stream::{Stream, StreamExt},
     TryStream, TryStreamExt,
 };
 use http::{HeaderMap, Uri};
@@ -17,37 +22,120 @@ use serde::{
     Deserialize, Serialize,
 };
 use serde_json::Value;
-use std::{fmt, marker::PhantomData};
+use std::{
+    any::Any,
+    collections::BTreeMap,
+    fmt,
+    marker::PhantomData,
+    time::{Duration, Instant},
+};
 use tokio::sync::{mpsc, oneshot};
+use tracing::{Instrument, Span};
 
 /// Type used to embed live views in HTML templates.
 pub struct EmbedLiveView<'a, L> {
-    view: Option<&'a mut Option<L>>,
+    view: Option<&'a mut Option<(L, Option<Checkpoint<L>>)>>,
+    session: Option<&'a Session>,
+    submission: Option<&'a mut Submission>,
 }
 
 impl<'a, L> EmbedLiveView<'a, L> {
-    pub(crate) fn noop() -> Self {
-        Self { view: None }
+    pub(crate) fn noop(session: Option<&'a Session>) -> Self {
+        Self {
+            view: None,
+            session,
+            submission: None,
+        }
+    }
+
+    pub(crate) fn new(
+        view: &'a mut Option<(L, Option<Checkpoint<L>>)>,
+        session: Option<&'a Session>,
+    ) -> Self {
+        Self {
+            view: Some(view),
+            session,
+            submission: None,
+        }
     }
 
-    pub(crate) fn new(view: &'a mut Option<L>) -> Self {
-        Self { view: Some(view) }
+    pub(crate) fn submit(submission: &'a mut Submission, session: Option<&'a Session>) -> Self {
+        Self {
+            view: None,
+            session,
+            submission: Some(submission),
+        }
     }
 
     /// Embed a live view in a HTML template.
-    pub fn embed(self, view: L) -> Html<L::Message>
+    pub fn embed(mut self, view: L) -> Html<L::Message>
     where
         L: LiveView,
     {
+        let view = self.apply_submission(view);
         let html = wrap_in_live_view_container(view.render());
 
         if let Some(view_handle) = self.view {
-            *view_handle = Some(view);
+            *view_handle = Some((view, None));
         }
 
         html
     }
 
+    /// Embed a live view that can be resumed after reconnecting, even on another server instance.
+    ///
+    /// If the client is reconnecting to a session that has been checkpointed `view` is ignored
+    /// and the view is rebuilt from the checkpoint instead.
+    ///
+    /// Behaves like [`embed`](Self::embed) if [`Sessions`] haven't been enabled. See the
+    /// [`session`] module for more details.
+    ///
+    /// [`Sessions`]: crate::session::Sessions
+    /// [`session`]: crate::session
+    pub fn embed_resumable(mut self, view: L) -> Html<L::Message>
+    where
+        L: LiveView + Serialize + DeserializeOwned,
+    {
+        let session = match self.session {
+            Some(session) => session,
+            None => return self.embed(view),
+        };
+        let view = self.apply_submission(view);
+
+        match self.view {
+            Some(view_handle) => {
+                let view = match session.state.as_deref().map(serde_json::from_slice::<L>) {
+                    Some(Ok(resumed)) => {
+                        tracing::debug!("view resumed from session");
+                        resumed
+                    }
+                    Some(Err(err)) => {
+                        tracing::warn!(%err, "failed to resume view from session");
+                        view
+                    }
+                    None => view,
+                };
+
+                let html = wrap_in_live_view_container(view.render());
+                let checkpoint = session.checkpoint(|view: &L| serde_json::to_vec(view));
+                *view_handle = Some((view, Some(checkpoint)));
+                html
+            }
+            None => crate::html::private::HtmlBuilder {
+                dynamic: Vec::from([
+                    crate::html::DynamicFragment::String(session.token.clone()),
+                    crate::html::DynamicFragment::Html(view.render()),
+                ]),
+                fixed: &[
+                    "<div id=\"live-view-container\" data-axm-session=\"",
+                    "\">",
+                    "</div>",
+                ],
+            }
+            .into_html(),
+        }
+    }
+
     /// Check whether the request was a WebSocket upgrade request.
     ///
     /// This can be used to initialize the view differently depending on which part of the live
@@ -56,6 +144,117 @@ impl<'a, L> EmbedLiveView<'a, L> {
     pub fn connected(&self) -> bool {
         self.view.is_some()
     }
+
+    fn apply_submission(&mut self, view: L) -> L
+    where
+        L: LiveView,
+    {
+        match &mut self.submission {
+            Some(submission) => submission.apply(view),
+            None => view,
+        }
+    }
+}
+
+/// A form submitted without JavaScript.
+///
+/// The form's message is rendered in a hidden input by [`Html::render_with_form_posts`] and sent along
+/// with the form's values. The message is applied to the view when it's embedded, so the page
+/// can be rendered with the updated view.
+#[derive(Debug)]
+pub(crate) struct Submission {
+    msg: String,
+    data: Option<EventData>,
+    /// Set if the view sent a [`js_command::navigate_to`] command.
+    pub(crate) redirect: Option<String>,
+}
+
+impl Submission {
+    /// Parse a `application/x-www-form-urlencoded` body.
+    ///
+    /// Returns `None` if the body doesn't contain a message.
+    pub(crate) fn from_body(body: &str) -> Option<Self> {
+        let mut msg = None;
+        let query = body
+            .split('&')
+            .filter(|pair| match pair.split_once('=') {
+                Some((key, value)) if key == html::SUBMIT_INPUT => {
+                    let value = value.replace('+', " ");
+                    msg = Some(
+                        percent_encoding::percent_decode_str(&value)
+                            .decode_utf8_lossy()
+                            .into_owned(),
+                    );
+                    false
+                }
+                _ => true,
+            })
+            .collect::<Vec<_>>()
+            .join("&");
+
+        Some(Self {
+            msg: msg?,
+            data: EventData::from_socket(
+                EventMessageFromSocketData::Form {
+                    query,
+                    changed: None,
+                },
+                Default::default(),
+            ),
+            redirect: None,
+        })
+    }
+
+    fn apply<L>(&mut self, view: L) -> L
+    where
+        L: LiveView,
+    {
+        // constructors are resolved by the view that rendered them
+        let resolvers = Resolvers::of(&view.render());
+        let msg = match self.decode_msg::<L::Message>(&resolvers) {
+            Ok(msg) => msg,
+            Err(err) => {
+                tracing::debug!(%err, "failed to decode submitted message");
+                return view;
+            }
+        };
+
+        // JavaScript commands and spawned futures can't run without a connection
+        let Updated {
+            live_view,
+            js_commands,
+            spawns: _,
+        } = view.update(msg, self.data.clone());
+
+        self.redirect = js_commands
+            .into_iter()
+            .find_map(|command| match command.kind {
+                js_command::JsCommandKind::NavigateTo { uri } => Some(uri),
+                _ => None,
+            });
+
+        live_view
+    }
+
+    fn decode_msg<M>(&self, resolvers: &Resolvers) -> anyhow::Result<M>
+    where
+        M: DeserializeOwned,
+    {
+        let msg = percent_encoding::percent_decode_str(&self.msg).decode_utf8()?;
+
+        if let Ok(msg) = serde_json::from_str::<M>(&msg) {
+            return Ok(msg);
+        }
+
+        if let Ok(chain) = serde_json::from_str::<js_command::JsChain<M>>(&msg) {
+            return chain
+                .into_push()
+                .ok_or_else(|| anyhow::anyhow!("JavaScript commands without a message"));
+        }
+
+        let ctor = serde_json::from_str::<CtorMsg>(&msg)?;
+        Ok(ctor.build(resolvers, self.data.as_ref())?)
+    }
 }
 
 impl<'a, M> fmt::Debug for EmbedLiveView<'a, M> {
@@ -64,13 +263,119 @@ impl<'a, M> fmt::Debug for EmbedLiveView<'a, M> {
     }
 }
 
+/// How messages are batched before rendering.
+///
+/// By default every message sent to a view causes the view to be rendered and diffed, and the
+/// diff to be sent to the client. For views that receive lots of messages, such as a ticking
+/// clock or `axm-mousemove` bindings, it can be more efficient to call [`LiveView::update`] for
+/// several messages and then only render and diff once.
+///
+/// Set with [`LiveViewUpgrade::render_batching`].
+///
+/// [`LiveViewUpgrade::render_batching`]: crate::LiveViewUpgrade::render_batching
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum RenderBatching {
+    /// Render after each message.
+    ///
+    /// This is the default.
+    #[default]
+    Disabled,
+    /// Update the view with all messages that are immediately available and then render once.
+    Drain,
+    /// Update the view with all messages received within the given duration after the first
+    /// message and then render once.
+    ///
+    /// A duration of 16ms roughly matches the frame rate of most displays.
+    Frame(Duration),
+}
+
+impl RenderBatching {
+    /// The max number of messages in one batch, so a busy producer can't prevent the view from
+    /// ever being rendered.
+    const MAX_BATCH_SIZE: usize = 1024;
+
+    /// Receive more messages from `stream`, according to the batching policy.
+    ///
+    /// Stops if the stream ends or yields an error.
+    async fn collect<S, T, E>(self, stream: &mut S, items: &mut Vec<Option<Result<T, E>>>)
+    where
+        S: Stream<Item = Result<T, E>> + Unpin,
+    {
+        fn is_last<T, E>(item: &Option<Result<T, E>>) -> bool {
+            !matches!(item, Some(Ok(_)))
+        }
+
+        if !matches!(items.last(), Some(Some(Ok(_)))) {
+            return;
+        }
+
+        match self {
+            Self::Disabled => {}
+            Self::Drain => {
+                while items.len() < Self::MAX_BATCH_SIZE {
+                    match stream.next().now_or_never() {
+                        Some(item) => {
+                            let last = is_last(&item);
+                            items.push(item);
+                            if last {
+                                break;
+                            }
+                        }
+                        None => break,
+                    }
+                }
+            }
+            Self::Frame(duration) => {
+                let deadline = tokio::time::Instant::now() + duration;
+                while items.len() < Self::MAX_BATCH_SIZE {
+                    match tokio::time::timeout_at(deadline, stream.next()).await {
+                        Ok(item) => {
+                            let last = is_last(&item);
+                            items.push(item);
+                            if last {
+                                break;
+                            }
+                        }
+                        Err(_) => break,
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Configuration for [`run_view`].
+#[derive(Debug, Default, Clone)]
+pub(crate) struct RunViewConfig {
+    pub(crate) recorder: Option<Recorder>,
+    pub(crate) render_batching: RenderBatching,
+    pub(crate) shutdown: Option<Shutdown>,
+    #[cfg(feature = "live-reload")]
+    pub(crate) live_reload: Option<crate::live_reload::LiveReload>,
+    /// The build ID the client was loaded from, if it's reconnecting with live reload enabled.
+    #[cfg(feature = "live-reload")]
+    pub(crate) client_build_id: Option<String>,
+}
+
+/// Why [`run_view`] stopped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Stopped {
+    /// The client disconnected.
+    Disconnected,
+    /// The server is shutting down and the socket should be closed before `deadline`.
+    Shutdown { deadline: tokio::time::Instant },
+}
+
 pub(crate) async fn run_view<W, R, L>(
-    mut write: W,
+    write: W,
     read: R,
     view: L,
     uri: Uri,
     headers: HeaderMap,
-) -> Result<(), String>
+    config: RunViewConfig,
+    checkpoint: Option<Checkpoint<L>>,
+) -> Result<Stopped, String>
 where
     L: LiveView,
     W: Sink<MessageToSocket> + Unpin,
@@ -78,86 +383,415 @@ where
     R: TryStream<Ok = MessageFromSocket<L::Message>> + Unpin,
     R::Error: fmt::Display + Send + Sync + 'static,
 {
-    let (handle, rx) = ViewHandle::new();
-
-    let view = spawn_view(view, Some(handle.clone()));
-
-    view.mount(uri, headers, handle)
-        .await
-        .map_err(|err| err.to_string())?;
-
-    let markup = view.render().await.map_err(|err| err.to_string())?;
+    let view_name = std::any::type_name::<L>();
+    let connection_id = ConnectionId::next();
+    let span = instrument::connection_span(view_name, connection_id);
+
+    async move {
+        let (handle, rx) = ViewHandle::new();
+
+        let active = ActiveView::new(view_name, connection_id, &uri, &handle);
+
+        let mut write = write.sink_map_err(|err| {
+            active.socket_error();
+            err
+        });
+
+        let RunViewConfig {
+            recorder,
+            render_batching,
+            shutdown,
+            #[cfg(feature = "live-reload")]
+            live_reload,
+            #[cfg(feature = "live-reload")]
+            client_build_id,
+        } = config;
+
+        let mut shutdown = shutdown.map(|shutdown| shutdown.subscribe());
+
+        #[cfg(feature = "live-reload")]
+        let mut reload = match live_reload {
+            Some(live_reload) => {
+                if matches!(&client_build_id, Some(id) if id != live_reload.build_id()) {
+                    tracing::debug!("server has been restarted. Reloading client");
+                    write_message(
+                        &mut write,
+                        MessageToSocketData::JsCommands(Vec::from([js_command::reload()])),
+                    )
+                    .await
+                    .map_err(|err| err.to_string())?;
+                    return Ok(Stopped::Disconnected);
+                }
 
-    write_message(&mut write, MessageToSocketData::InitialRender(markup))
-        .await
-        .map_err(|err| err.to_string())?;
+                write_message(
+                    &mut write,
+                    MessageToSocketData::BuildId(live_reload.build_id().to_owned()),
+                )
+                .await
+                .map_err(|err| err.to_string())?;
 
-    let rx_stream = ReceiverStream::new(rx).map(|msg| {
-        Ok(MessageFromSocket::Event {
-            msg,
-            data: EventMessageFromSocketData::None,
-        })
-    });
-    let mut stream = crate::util::StreamExt::merge(read.into_stream(), rx_stream);
-
-    loop {
-        let msg = match stream.next().await {
-            Some(Ok(msg)) => msg,
-            Some(Err(err)) => {
-                let err = err.to_string();
-                tracing::trace!(%err, "error from socket");
-                break;
+                Reload {
+                    signal: Some(live_reload.subscribe()),
+                }
             }
-            None => {
-                tracing::trace!("no more messages on socket");
-                break;
+            None => Reload::default(),
+        };
+        #[cfg(not(feature = "live-reload"))]
+        let mut reload = Reload::default();
+
+        let mut recording = if let Some(recorder) = recorder {
+            match recorder.start(connection_id, &uri, &headers).await {
+                Ok(recording) => Some(recording),
+                Err(err) => {
+                    tracing::warn!(%err, "failed to start recording");
+                    None
+                }
             }
+        } else {
+            None
         };
 
-        match msg {
-            MessageFromSocket::Event {
-                msg: msg_for_view,
-                data,
-            } => {
-                let data = Option::<EventData>::from(data);
+        let mut hibernation = view.hibernation();
+        let mut idle_since = tokio::time::Instant::now();
+
+        let task = spawn_view(view, Some(handle.clone()));
+
+        task.mount(uri, headers, handle.clone())
+            .await
+            .map_err(|err| err.to_string())?;
+
+        let Rendered {
+            html,
+            statics,
+            resolvers: initial_resolvers,
+        } = task.render().await.map_err(|err| err.to_string())?;
+        let mut resolvers = initial_resolvers;
+
+        let mut view = ViewTask::Running(task);
+
+        write_message_with_statics(
+            &mut write,
+            MessageToSocketData::InitialRender(html),
+            statics,
+        )
+        .await
+        .map_err(|err| err.to_string())?;
 
-                match view
-                    .update(msg_for_view, data)
+        let rx_stream = ReceiverStream::new(rx).map(|msg| {
+            Ok(MessageFromSocket::Event {
+                msg,
+                data: EventMessageFromSocketData::None,
+                ref_id: None,
+                values: Default::default(),
+            })
+        });
+        let mut stream = crate::util::StreamExt::merge(read.into_stream(), rx_stream);
+
+        let mut stopped = Stopped::Disconnected;
+
+        loop {
+            let hibernate_at = match (&view, &hibernation) {
+                (ViewTask::Running(_), Some(hibernation)) => Some(idle_since + hibernation.idle),
+                _ => None,
+            };
+
+            let msg = tokio::select! {
+                msg = stream.next() => msg,
+                _ = active.disconnect_requested() => {
+                    tracing::trace!("view was disconnected");
+                    break;
+                }
+                _ = reload.requested() => {
+                    tracing::debug!("watched files changed. Reloading client");
+                    write_message(
+                        &mut write,
+                        MessageToSocketData::JsCommands(Vec::from([js_command::reload()])),
+                    )
                     .await
-                    .map_err(|err| err.to_string())?
-                {
-                    UpdateResponse::Diff(diff) => {
-                        write_message(&mut write, MessageToSocketData::Render(diff))
+                    .map_err(|err| err.to_string())?;
+                    continue;
+                }
+                deadline = shutdown_triggered(&mut shutdown) => {
+                    tracing::debug!("shutting down view");
+                    stopped = Stopped::Shutdown { deadline };
+                    break;
+                }
+                _ = sleep_until(hibernate_at) => {
+                    if let (ViewTask::Running(task), Some(config)) = (&view, hibernation) {
+                        let live_view = task.hibernate::<L>().await.map_err(|err| err.to_string())?;
+                        match (config.serialize)(&live_view) {
+                            Ok(state) => {
+                                tracing::debug!(bytes = state.len(), "view hibernated");
+                                view = ViewTask::Hibernating(state.into_boxed_slice());
+                            }
+                            Err(err) => {
+                                tracing::warn!(
+                                    %err,
+                                    "failed to serialize view. Disabling hibernation"
+                                );
+                                hibernation = None;
+                                let task = spawn_view(live_view, Some(handle.clone()));
+                                view = ViewTask::Running(task);
+                            }
+                        }
+                    }
+                    continue;
+                }
+            };
+
+            let mut incoming = Vec::from([msg]);
+            render_batching.collect(&mut stream, &mut incoming).await;
+
+            let mut batch = Vec::new();
+            let mut refs = Vec::new();
+            let mut closed = false;
+
+            for msg in incoming {
+                let msg = match msg {
+                    Some(Ok(msg)) => msg,
+                    Some(Err(err)) => {
+                        active.socket_error();
+                        let err = err.to_string();
+                        tracing::trace!(%err, "error from socket");
+                        closed = true;
+                        break;
+                    }
+                    None => {
+                        tracing::trace!("no more messages on socket");
+                        closed = true;
+                        break;
+                    }
+                };
+
+                let (msg_for_view, data, values) = match msg {
+                    MessageFromSocket::Event {
+                        msg,
+                        data,
+                        ref_id,
+                        values,
+                    } => {
+                        refs.extend(ref_id);
+                        (msg, data, values)
+                    }
+                    MessageFromSocket::Ctor {
+                        ctor,
+                        data,
+                        ref_id,
+                        values,
+                    } => {
+                        refs.extend(ref_id);
+                        let event_data = EventData::from_socket(data.clone(), values.clone());
+                        match ctor.build(&resolvers, event_data.as_ref()) {
+                            Ok(msg) => (msg, data, values),
+                            Err(err) => {
+                                tracing::warn!(%err, "failed to create message from event data");
+                                continue;
+                            }
+                        }
+                    }
+                    MessageFromSocket::Internal {
+                        data: InternalMessageFromSocketData::Health,
+                    } => {
+                        write_message(&mut write, MessageToSocketData::Health)
                             .await
                             .map_err(|err| err.to_string())?;
+                        continue;
+                    }
+                    MessageFromSocket::Internal {
+                        data: InternalMessageFromSocketData::Reconnect,
+                    } => {
+                        tracing::trace!("client reconnected");
+                        active.client_reconnected();
+                        continue;
+                    }
+                };
+
+                active.message_received();
+
+                if let Some(session) = &mut recording {
+                    if let Err(err) = session.record_message(&msg_for_view, &data, &values).await {
+                        tracing::warn!(%err, "failed to record message. Stopping recording");
+                        recording = None;
+                    }
+                }
+
+                batch.push((msg_for_view, EventData::from_socket(data, values)));
+            }
+
+            if !batch.is_empty() {
+                idle_since = tokio::time::Instant::now();
+
+                if let (ViewTask::Hibernating(state), Some(config)) = (&view, hibernation) {
+                    let live_view = (config.deserialize)(state)
+                        .map_err(|err| format!("failed to restore hibernated view: {}", err))?;
+                    tracing::debug!("view restored from hibernation");
+                    view = ViewTask::Running(spawn_view(live_view, Some(handle.clone())));
+                }
+
+                let task = match &view {
+                    ViewTask::Running(task) => task,
+                    ViewTask::Hibernating(_) => unreachable!("view is hibernating without config"),
+                };
+
+                match task.update(batch).await.map_err(|err| err.to_string())? {
+                    UpdateResponse::Diff(Rendered {
+                        html,
+                        statics,
+                        resolvers: new_resolvers,
+                    }) => {
+                        resolvers.extend(new_resolvers);
+                        active.diff_sent(&html);
+                        write_message_with_statics(
+                            &mut write,
+                            MessageToSocketData::Render(html),
+                            statics,
+                        )
+                        .await
+                        .map_err(|err| err.to_string())?;
                     }
                     UpdateResponse::JsCommands(commands) => {
                         write_message(&mut write, MessageToSocketData::JsCommands(commands))
                             .await
                             .map_err(|err| err.to_string())?;
                     }
-                    UpdateResponse::DiffAndJsCommands(diff, commands) => {
-                        write_message(&mut write, MessageToSocketData::Render(diff))
-                            .await
-                            .map_err(|err| err.to_string())?;
+                    UpdateResponse::DiffAndJsCommands(
+                        Rendered {
+                            html,
+                            statics,
+                            resolvers: new_resolvers,
+                        },
+                        commands,
+                    ) => {
+                        resolvers.extend(new_resolvers);
+                        active.diff_sent(&html);
+                        write_message_with_statics(
+                            &mut write,
+                            MessageToSocketData::Render(html),
+                            statics,
+                        )
+                        .await
+                        .map_err(|err| err.to_string())?;
                         write_message(&mut write, MessageToSocketData::JsCommands(commands))
                             .await
                             .map_err(|err| err.to_string())?;
                     }
                     UpdateResponse::Empty => {}
                 }
-            }
-            MessageFromSocket::Internal {
-                data: InternalMessageFromSocketData::Health,
-            } => {
-                write_message(&mut write, MessageToSocketData::Health)
+
+                if !refs.is_empty() {
+                    write_message(&mut write, MessageToSocketData::Ack(refs))
+                        .await
+                        .map_err(|err| err.to_string())?;
+                }
+
+                if let Some(checkpoint) = &checkpoint {
+                    match task
+                        .checkpoint(checkpoint.serialize)
+                        .await
+                        .map_err(|err| err.to_string())?
+                    {
+                        Ok(state) => checkpoint.save(state).await,
+                        Err(err) => tracing::warn!(%err, "failed to serialize view for checkpoint"),
+                    }
+                }
+            } else if !refs.is_empty() {
+                // none of the events produced a message, for example because their constructor
+                // failed, but the client still waits for them to be acknowledged
+                write_message(&mut write, MessageToSocketData::Ack(refs))
                     .await
                     .map_err(|err| err.to_string())?;
             }
+
+            if closed {
+                break;
+            }
+        }
+
+        let finish = async {
+            match (&view, hibernation) {
+                (ViewTask::Running(task), _) => {
+                    task.unmount().await.map_err(|err| err.to_string())?;
+                }
+                // restore the view so it gets to clean up, the same as if it was running
+                (ViewTask::Hibernating(state), Some(config)) => match (config.deserialize)(state) {
+                    Ok(mut live_view) => live_view.unmount(),
+                    Err(err) => {
+                        tracing::warn!(%err, "failed to restore hibernated view for unmounting")
+                    }
+                },
+                (ViewTask::Hibernating(_), None) => {
+                    unreachable!("view is hibernating without config")
+                }
+            }
+
+            if let (Stopped::Shutdown { .. }, Some(shutdown)) = (stopped, &shutdown) {
+                let reconnect_after = shutdown.reconnect_after().as_millis() as u64;
+                write_message(
+                    &mut write,
+                    MessageToSocketData::ServerRestarting(reconnect_after),
+                )
+                .await
+                .map_err(|err| err.to_string())?;
+            }
+
+            Ok::<_, String>(())
+        };
+
+        match stopped {
+            Stopped::Shutdown { deadline } => {
+                match tokio::time::timeout_at(deadline, finish).await {
+                    Ok(result) => result?,
+                    Err(_) => tracing::warn!("view didn't shut down before the deadline"),
+                }
+            }
+            Stopped::Disconnected => finish.await?,
+        }
+
+        Ok(stopped)
+    }
+    .instrument(span)
+    .await
+}
+
+/// The task running a view, or the serialized view if it's hibernating.
+enum ViewTask<M> {
+    Running(ViewTaskHandle<M>),
+    Hibernating(Box<[u8]>),
+}
+
+async fn sleep_until(deadline: Option<tokio::time::Instant>) {
+    match deadline {
+        Some(deadline) => tokio::time::sleep_until(deadline).await,
+        None => futures_util::future::pending().await,
+    }
+}
+
+/// Changes to watched files, if live reload is enabled.
+#[derive(Default)]
+struct Reload {
+    #[cfg(feature = "live-reload")]
+    signal: Option<crate::live_reload::ReloadSignal>,
+}
+
+impl Reload {
+    /// Wait until the client should reload.
+    ///
+    /// Never completes if the `live-reload` feature is disabled.
+    async fn requested(&mut self) {
+        #[cfg(feature = "live-reload")]
+        if let Some(signal) = &mut self.signal {
+            return signal.changed().await;
         }
+
+        futures_util::future::pending::<()>().await;
     }
+}
 
-    Ok(())
+async fn shutdown_triggered(signal: &mut Option<ShutdownSignal>) -> tokio::time::Instant {
+    match signal {
+        Some(signal) => signal.triggered().await,
+        None => futures_util::future::pending().await,
+    }
 }
 
 pub(crate) fn spawn_view<L>(
@@ -168,9 +802,13 @@ where
     L: LiveView,
 {
     let (tx, mut rx) = mpsc::channel(1024);
+    let view_name = std::any::type_name::<L>();
 
-    crate::util::spawn_unit(async move {
+    let task = async move {
         let mut markup = wrap_in_live_view_container(view.render());
+        let mut fingerprint = view.fingerprint();
+        let mut statics_cache = StaticsCache::default();
+        let mut unmounted_tx = None;
 
         while let Some(request) = rx.recv().await {
             match request {
@@ -183,40 +821,120 @@ where
                     let _ = reply_tx.send(view.mount(uri, &headers, handle));
                 }
                 ViewRequest::Render { reply_tx } => {
-                    let _ = reply_tx
-                        .send(serde_json::to_value(&markup).expect("failed to serialize HTML"));
+                    let (html, statics) = match statics_cache.serialize(&markup) {
+                        Ok(serialized) => serialized,
+                        Err(err) => {
+                            // stops the view, which closes the connection
+                            tracing::error!(%err, "failed to serialize HTML");
+                            break;
+                        }
+                    };
+                    let _ = reply_tx.send(Rendered {
+                        html,
+                        statics,
+                        resolvers: Resolvers::of(&markup),
+                    });
                 }
                 ViewRequest::RenderToString { reply_tx } => {
                     let _ = reply_tx.send(markup.render());
                 }
-                ViewRequest::Update {
-                    msg,
+                ViewRequest::Hibernate { reply_tx } => {
+                    let _ = reply_tx.send(Box::new(view));
+                    return;
+                }
+                ViewRequest::Unmount { reply_tx } => {
+                    unmounted_tx = Some(reply_tx);
+                    break;
+                }
+                ViewRequest::Checkpoint {
+                    serialize,
                     reply_tx,
-                    event_data,
                 } => {
-                    let Updated {
-                        live_view: new_view,
-                        js_commands,
-                        spawns,
-                    } = view.update(msg, event_data);
-
-                    if let Some(view_handle) = &view_handle {
-                        for future in spawns {
-                            let view_handle = view_handle.clone();
-                            crate::util::spawn_unit(async move {
-                                let msg = future.await;
-                                let _ = view_handle.send(msg).await;
-                            });
+                    let _ = reply_tx.send(serialize(&view));
+                }
+                ViewRequest::Update { msgs, reply_tx } => {
+                    let span = instrument::update_span(msgs.len());
+                    let _enter = span.enter();
+                    let mut timings = UpdateTimings::default();
+                    let mut js_commands = Vec::new();
+
+                    let start = Instant::now();
+                    for (msg, event_data) in msgs {
+                        let Updated {
+                            live_view: new_view,
+                            js_commands: new_js_commands,
+                            spawns,
+                        } = view.update(msg, event_data);
+
+                        if let Some(view_handle) = &view_handle {
+                            for future in spawns {
+                                let view_handle = view_handle.clone();
+                                crate::util::spawn_unit(async move {
+                                    let msg = future.await;
+                                    let _ = view_handle.send(msg).await;
+                                });
+                            }
                         }
-                    }
-
-                    view = new_view;
 
-                    let new_markup = wrap_in_live_view_container(view.render());
-                    let diff = markup.diff(&new_markup).map(|diff| {
-                        serde_json::to_value(&diff).expect("failed to serialize HTML diff")
+                        view = new_view;
+                        js_commands.extend(new_js_commands);
+                    }
+                    timings.update = start.elapsed();
+
+                    let previous_fingerprint =
+                        std::mem::replace(&mut fingerprint, view.fingerprint());
+                    let unchanged = previous_fingerprint
+                        .zip(fingerprint.as_ref())
+                        .map(|(previous, new)| (new.unchanged_since(&previous), new.len()));
+
+                    let diff = match unchanged {
+                        // nothing has changed so there is no need to render
+                        Some((unchanged, fields)) if unchanged.len() == fields => None,
+                        unchanged => {
+                            let start = Instant::now();
+                            let (new_markup, previous_markup, reused) = match unchanged {
+                                Some((unchanged, _)) => {
+                                    let owner = std::any::type_name::<L>();
+                                    html::render_reusing(markup, owner, unchanged, || {
+                                        wrap_in_live_view_container(view.render())
+                                    })
+                                }
+                                None => (
+                                    wrap_in_live_view_container(view.render()),
+                                    markup,
+                                    Reused::default(),
+                                ),
+                            };
+                            timings.render = start.elapsed();
+
+                            let start = Instant::now();
+                            let diff = previous_markup
+                                .diff_reusing(&new_markup, &reused)
+                                .map(|diff| statics_cache.serialize(&diff))
+                                .transpose();
+                            let diff = match diff {
+                                Ok(diff) => diff.map(|(html, statics)| Rendered {
+                                    html,
+                                    statics,
+                                    resolvers: Resolvers::of(&new_markup),
+                                }),
+                                Err(err) => {
+                                    // stops the view, which closes the connection
+                                    tracing::error!(%err, "failed to serialize HTML diff");
+                                    break;
+                                }
+                            };
+                            timings.diff = start.elapsed();
+                            markup = new_markup;
+                            diff
+                        }
+                    };
+                    timings.diff_bytes = diff.as_ref().map_or(0, |diff| {
+                        instrument::serialized_len(&diff.html)
+                            + instrument::serialized_len(&diff.statics)
                     });
-                    markup = new_markup;
+
+                    timings.record(view_name, &span);
 
                     let response = match (diff, js_commands.is_empty()) {
                         (None, true) => UpdateResponse::Empty,
@@ -229,7 +947,15 @@ where
                 }
             }
         }
-    });
+
+        // also unmount if the handle was dropped without requesting it
+        view.unmount();
+        if let Some(reply_tx) = unmounted_tx {
+            let _ = reply_tx.send(());
+        }
+    };
+
+    crate::util::spawn_unit(task.instrument(Span::current()));
 
     ViewTaskHandle { tx }
 }
@@ -281,7 +1007,7 @@ impl<M> ViewTaskHandle<M> {
         }
     }
 
-    pub(crate) async fn render(&self) -> Result<Value, ChannelClosed> {
+    pub(crate) async fn render(&self) -> Result<Rendered, ChannelClosed> {
         let (reply_tx, reply_rx) = oneshot::channel();
 
         let request = ViewRequest::Render { reply_tx };
@@ -301,18 +1027,14 @@ impl<M> ViewTaskHandle<M> {
         reply_rx.await.map_err(|_| ChannelClosed)
     }
 
+    /// Update the view with a batch of messages and then render and diff once.
     pub(crate) async fn update(
         &self,
-        msg: M,
-        event_data: Option<EventData>,
+        msgs: Vec<(M, Option<EventData>)>,
     ) -> Result<UpdateResponse, ViewRequestError> {
         let (reply_tx, reply_rx) = oneshot::channel();
 
-        let request = ViewRequest::Update {
-            msg,
-            event_data,
-            reply_tx,
-        };
+        let request = ViewRequest::Update { msgs, reply_tx };
 
         self.tx
             .send(request)
@@ -324,6 +1046,60 @@ impl<M> ViewTaskHandle<M> {
             Err(_) => Err(ViewRequestError::ChannelClosed(ChannelClosed)),
         }
     }
+
+    /// Stop the task and get the view back.
+    async fn hibernate<L>(&self) -> Result<L, ChannelClosed>
+    where
+        L: LiveView<Message = M>,
+    {
+        let (reply_tx, reply_rx) = oneshot::channel();
+
+        let request = ViewRequest::Hibernate { reply_tx };
+
+        self.tx.send(request).await.map_err(|_| ChannelClosed)?;
+
+        let view = reply_rx.await.map_err(|_| ChannelClosed)?;
+        Ok(*view
+            .downcast::<L>()
+            .expect("view task contained a different type of view"))
+    }
+
+    /// Unmount the view and stop the task.
+    async fn unmount(&self) -> Result<(), ChannelClosed> {
+        let (reply_tx, reply_rx) = oneshot::channel();
+
+        let request = ViewRequest::Unmount { reply_tx };
+
+        self.tx.send(request).await.map_err(|_| ChannelClosed)?;
+
+        reply_rx.await.map_err(|_| ChannelClosed)
+    }
+
+    /// Serialize the view's current state.
+    async fn checkpoint<L>(
+        &self,
+        serialize: fn(&L) -> serde_json::Result<Vec<u8>>,
+    ) -> Result<serde_json::Result<Vec<u8>>, ChannelClosed>
+    where
+        L: LiveView<Message = M>,
+    {
+        let (reply_tx, reply_rx) = oneshot::channel();
+
+        let serialize = Box::new(move |view: &dyn Any| {
+            serialize(
+                view.downcast_ref::<L>()
+                    .expect("view task contained a different type of view"),
+            )
+        });
+        let request = ViewRequest::Checkpoint {
+            serialize,
+            reply_tx,
+        };
+
+        self.tx.send(request).await.map_err(|_| ChannelClosed)?;
+
+        reply_rx.await.map_err(|_| ChannelClosed)
+    }
 }
 
 enum ViewRequest<M> {
@@ -334,16 +1110,25 @@ enum ViewRequest<M> {
         reply_tx: oneshot::Sender<()>,
     },
     Render {
-        reply_tx: oneshot::Sender<Value>,
+        reply_tx: oneshot::Sender<Rendered>,
     },
     RenderToString {
         reply_tx: oneshot::Sender<String>,
     },
     Update {
-        msg: M,
-        event_data: Option<EventData>,
+        msgs: Vec<(M, Option<EventData>)>,
         reply_tx: oneshot::Sender<UpdateResponse>,
     },
+    Hibernate {
+        reply_tx: oneshot::Sender<Box<dyn Any + Send>>,
+    },
+    Checkpoint {
+        serialize: Box<dyn FnOnce(&dyn Any) -> serde_json::Result<Vec<u8>> + Send>,
+        reply_tx: oneshot::Sender<serde_json::Result<Vec<u8>>>,
+    },
+    Unmount {
+        reply_tx: oneshot::Sender<()>,
+    },
 }
 
 #[derive(Debug)]
@@ -379,16 +1164,28 @@ impl fmt::Display for ChannelClosed {
 impl std::error::Error for ChannelClosed {}
 
 pub(crate) enum UpdateResponse {
-    Diff(Value),
+    Diff(Rendered),
     JsCommands(Vec<JsCommand>),
-    DiffAndJsCommands(Value, Vec<JsCommand>),
+    DiffAndJsCommands(Rendered, Vec<JsCommand>),
     Empty,
 }
 
+/// A serialized HTML template or diff.
+pub(crate) struct Rendered {
+    /// The serialized HTML where statics are replaced by ids.
+    html: Value,
+    /// The statics not previously sent to the client.
+    statics: Statics,
+    /// The resolvers of the constructors in the rendered HTML.
+    resolvers: Resolvers,
+}
+
 #[derive(Serialize)]
 pub(crate) struct MessageToSocket {
     #[serde(flatten)]
     data: MessageToSocketData,
+    #[serde(rename = "s", skip_serializing_if = "BTreeMap::is_empty")]
+    statics: Statics,
 }
 
 #[derive(Serialize)]
@@ -402,13 +1199,35 @@ enum MessageToSocketData {
     JsCommands(Vec<JsCommand>),
     #[serde(rename = "h")]
     Health,
+    /// The server is restarting and the client should reconnect after the given number of
+    /// milliseconds.
+    #[serde(rename = "rs")]
+    ServerRestarting(u64),
+    /// The events with these ref IDs have been processed and the resulting diff has been sent.
+    #[serde(rename = "a")]
+    Ack(Vec<u64>),
+    /// The ID of the server's build, sent when live reload is enabled.
+    #[cfg(feature = "live-reload")]
+    #[serde(rename = "b")]
+    BuildId(String),
 }
 
 async fn write_message<W>(write: &mut W, data: MessageToSocketData) -> Result<(), W::Error>
 where
     W: Sink<MessageToSocket> + Unpin,
 {
-    let msg = MessageToSocket { data };
+    write_message_with_statics(write, data, Statics::new()).await
+}
+
+async fn write_message_with_statics<W>(
+    write: &mut W,
+    data: MessageToSocketData,
+    statics: Statics,
+) -> Result<(), W::Error>
+where
+    W: Sink<MessageToSocket> + Unpin,
+{
+    let msg = MessageToSocket { data, statics };
     write.send(msg).await
 }
 
@@ -423,6 +1242,23 @@ where
         msg: M,
         #[serde(flatten)]
         data: EventMessageFromSocketData,
+        /// Set by the client so it knows when the event has been processed.
+        #[serde(rename = "r", default)]
+        ref_id: Option<u64>,
+        /// Collected from the element's `axm-value-*` attributes.
+        #[serde(rename = "v", default)]
+        values: serde_json::Map<String, serde_json::Value>,
+    },
+    /// An event bound to a message constructor, such as `axm-input={ Msg::TextChanged }`.
+    Ctor {
+        #[serde(rename = "m", deserialize_with = "html::deserialize_ctor")]
+        ctor: CtorMsg,
+        #[serde(flatten)]
+        data: EventMessageFromSocketData,
+        #[serde(rename = "r", default)]
+        ref_id: Option<u64>,
+        #[serde(rename = "v", default)]
+        values: serde_json::Map<String, serde_json::Value>,
     },
     Internal {
         #[serde(flatten)]
@@ -462,7 +1298,7 @@ where
     d.deserialize_str(MsgVisitor(PhantomData))
 }
 
-#[derive(Debug, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(tag = "t", content = "d")]
 #[serde(rename_all = "snake_case")]
 pub(crate) enum EventMessageFromSocketData {
@@ -470,9 +1306,14 @@ pub(crate) enum EventMessageFromSocketData {
     Click,
     WindowFocus,
     WindowBlur,
+    WindowBeforeunload,
+    TransitionEnd,
     Form {
         #[serde(rename = "q")]
         query: String,
+        /// The name of the field that changed, sent with `axm-change`.
+        #[serde(rename = "c", default, skip_serializing_if = "Option::is_none")]
+        changed: Option<String>,
     },
     Input {
         #[serde(rename = "v")]
@@ -520,21 +1361,115 @@ pub(crate) enum EventMessageFromSocketData {
         #[serde(rename = "sy")]
         scroll_y: f64,
     },
-}
-
-#[derive(Deserialize, PartialEq, Debug)]
-#[serde(untagged)]
-pub(crate) enum InputValue {
-    Bool(bool),
-    String(String),
-    Strings(Vec<String>),
-}
+    Pointer {
+        #[serde(rename = "id")]
+        pointer_id: i64,
+        #[serde(rename = "pt")]
+        pointer_type: String,
+        #[serde(rename = "pr")]
+        is_primary: bool,
+        #[serde(rename = "cx")]
+        client_x: f64,
+        #[serde(rename = "cy")]
+        client_y: f64,
+        #[serde(rename = "px")]
+        page_x: f64,
+        #[serde(rename = "py")]
+        page_y: f64,
+        #[serde(rename = "ox")]
+        offset_x: f64,
+        #[serde(rename = "oy")]
+        offset_y: f64,
+        #[serde(rename = "w")]
+        width: f64,
+        #[serde(rename = "h")]
+        height: f64,
+        #[serde(rename = "p")]
+        pressure: f64,
+    },
+    Touch {
+        #[serde(rename = "ts")]
+        touches: Vec<TouchPointData>,
+        #[serde(rename = "cts")]
+        changed_touches: Vec<TouchPointData>,
+    },
+    Wheel {
+        #[serde(rename = "dx")]
+        delta_x: f64,
+        #[serde(rename = "dy")]
+        delta_y: f64,
+        #[serde(rename = "dz")]
+        delta_z: f64,
+        #[serde(rename = "cx")]
+        client_x: f64,
+        #[serde(rename = "cy")]
+        client_y: f64,
+        #[serde(rename = "c")]
+        ctrl: bool,
+    },
+    Drag {
+        #[serde(rename = "id")]
+        dragged_id: Option<String>,
+        #[serde(rename = "cx")]
+        client_x: f64,
+        #[serde(rename = "cy")]
+        client_y: f64,
+    },
+    Resize {
+        #[serde(rename = "w")]
+        width: f64,
+        #[serde(rename = "h")]
+        height: f64,
+    },
+    Visibility {
+        #[serde(rename = "h")]
+        hidden: bool,
+    },
+    Network {
+        #[serde(rename = "o")]
+        online: bool,
+    },
+    Intersection {
+        #[serde(rename = "ir")]
+        ratio: f64,
+        #[serde(rename = "i")]
+        intersecting: bool,
+    },
+}
+
+#[derive(Clone, Serialize, Deserialize, PartialEq, Debug)]
+pub(crate) struct TouchPointData {
+    #[serde(rename = "id")]
+    pub(crate) identifier: i64,
+    #[serde(rename = "cx")]
+    pub(crate) client_x: f64,
+    #[serde(rename = "cy")]
+    pub(crate) client_y: f64,
+    #[serde(rename = "px")]
+    pub(crate) page_x: f64,
+    #[serde(rename = "py")]
+    pub(crate) page_y: f64,
+    #[serde(rename = "sx")]
+    pub(crate) screen_x: f64,
+    #[serde(rename = "sy")]
+    pub(crate) screen_y: f64,
+}
+
+#[derive(Clone, Serialize, Deserialize, PartialEq, Debug)]
+#[serde(untagged)]
+pub(crate) enum InputValue {
+    Bool(bool),
+    String(String),
+    Strings(Vec<String>),
+}
 
 #[derive(Debug, Deserialize, PartialEq)]
 #[serde(tag = "t")]
 pub(crate) enum InternalMessageFromSocketData {
     #[serde(rename = "h")]
     Health,
+    #[serde(rename = "rc")]
+    Reconnect,
 }
 
 fn wrap_in_live_view_container<T>(markup: Html<T>) -> Html<T> {
@@ -550,6 +1485,7 @@ mod tests {
     use super::*;
     use serde::{Deserialize, Serialize};
     use serde_json::json;
+    use std::convert::Infallible;
     use std::time::Duration;
 
     #[test]
@@ -574,6 +1510,104 @@ mod tests {
         );
     }
 
+    #[test]
+    fn serialize_dom_js_commands() {
+        use crate::js_command::{self, VisibilityOptions};
+
+        let cmds = Vec::from([
+            js_command::focus("#search"),
+            js_command::blur("#search"),
+            js_command::focus_first("#modal"),
+            js_command::set_attribute("#button", "aria-expanded", "true"),
+            js_command::remove_attribute("#button", "disabled"),
+            js_command::show(
+                "#modal",
+                VisibilityOptions::new()
+                    .display("flex")
+                    .transition("fade-in", Duration::from_millis(200)),
+            ),
+            js_command::hide("#modal", VisibilityOptions::new()),
+            js_command::scroll_into_view("#bottom"),
+            js_command::dispatch_event("#chart", "chart:update", json!({ "points": [1, 2] })),
+            js_command::announce("saved"),
+        ]);
+        let msg = json!(MessageToSocketData::JsCommands(cmds));
+
+        assert_eq!(
+            msg,
+            json!({
+                "t": "j",
+                "d": [
+                    {
+                        "delay_ms": null,
+                        "kind": { "t": "focus", "selector": "#search" }
+                    },
+                    {
+                        "delay_ms": null,
+                        "kind": { "t": "blur", "selector": "#search" }
+                    },
+                    {
+                        "delay_ms": null,
+                        "kind": { "t": "focus_first", "selector": "#modal" }
+                    },
+                    {
+                        "delay_ms": null,
+                        "kind": {
+                            "t": "set_attribute",
+                            "selector": "#button",
+                            "name": "aria-expanded",
+                            "value": "true"
+                        }
+                    },
+                    {
+                        "delay_ms": null,
+                        "kind": {
+                            "t": "remove_attribute",
+                            "selector": "#button",
+                            "name": "disabled"
+                        }
+                    },
+                    {
+                        "delay_ms": null,
+                        "kind": {
+                            "t": "show",
+                            "selector": "#modal",
+                            "options": {
+                                "display": "flex",
+                                "transition": { "classes": "fade-in", "duration_ms": 200 }
+                            }
+                        }
+                    },
+                    {
+                        "delay_ms": null,
+                        "kind": {
+                            "t": "hide",
+                            "selector": "#modal",
+                            "options": { "display": null, "transition": null }
+                        }
+                    },
+                    {
+                        "delay_ms": null,
+                        "kind": { "t": "scroll_into_view", "selector": "#bottom" }
+                    },
+                    {
+                        "delay_ms": null,
+                        "kind": {
+                            "t": "dispatch_event",
+                            "selector": "#chart",
+                            "event": "chart:update",
+                            "detail": { "points": [1, 2] }
+                        }
+                    },
+                    {
+                        "delay_ms": null,
+                        "kind": { "t": "announce", "text": "saved" }
+                    }
+                ]
+            })
+        );
+    }
+
     #[test]
     fn deserialize_message_from_socket_mount() {
         let msg = serde_json::from_value::<MessageFromSocket<Msg>>(
@@ -584,7 +1618,9 @@ mod tests {
             msg,
             MessageFromSocket::Event {
                 msg: Msg::Incr,
-                data: EventMessageFromSocketData::Click
+                data: EventMessageFromSocketData::Click,
+                ref_id: None,
+                values: Default::default(),
             }
         );
 
@@ -597,10 +1633,1029 @@ mod tests {
             MessageFromSocket::Event {
                 msg: Msg::Incr,
                 data: EventMessageFromSocketData::Form {
-                    query: "name=bob&age=20".to_owned()
+                    query: "name=bob&age=20".to_owned(),
+                    changed: None,
+                },
+                ref_id: None,
+                values: Default::default(),
+            }
+        );
+
+        let msg = serde_json::from_value::<MessageFromSocket<Msg>>(
+            json!({ "m": "%22Incr%22", "t": "transition_end" }),
+        )
+        .unwrap();
+        assert_eq!(
+            msg,
+            MessageFromSocket::Event {
+                msg: Msg::Incr,
+                data: EventMessageFromSocketData::TransitionEnd,
+                ref_id: None,
+                values: Default::default(),
+            }
+        );
+    }
+
+    #[test]
+    fn deserialize_message_from_socket_with_ref() {
+        let msg = serde_json::from_value::<MessageFromSocket<Msg>>(
+            json!({ "m": "%22Incr%22", "t": "click", "r": 1 }),
+        )
+        .unwrap();
+        assert_eq!(
+            msg,
+            MessageFromSocket::Event {
+                msg: Msg::Incr,
+                data: EventMessageFromSocketData::Click,
+                ref_id: Some(1),
+                values: Default::default(),
+            }
+        );
+    }
+
+    #[test]
+    fn deserialize_message_from_socket_window_events() {
+        let data = |msg: Value| match serde_json::from_value::<MessageFromSocket<Msg>>(msg).unwrap()
+        {
+            MessageFromSocket::Event { data, .. } => data,
+            _ => panic!("expected an event"),
+        };
+
+        let resize =
+            data(json!({ "m": "%22Incr%22", "t": "resize", "d": { "w": 800.0, "h": 600.0 } }));
+        assert_eq!(
+            resize,
+            EventMessageFromSocketData::Resize {
+                width: 800.0,
+                height: 600.0
+            }
+        );
+        let resize = EventData::from_socket(resize, Default::default()).unwrap();
+        let resize = resize.as_resize().unwrap();
+        assert_eq!((resize.width(), resize.height()), (800.0, 600.0));
+
+        let visibility = data(json!({ "m": "%22Incr%22", "t": "visibility", "d": { "h": true } }));
+        assert_eq!(
+            visibility,
+            EventMessageFromSocketData::Visibility { hidden: true }
+        );
+        let visibility = EventData::from_socket(visibility, Default::default()).unwrap();
+        assert!(visibility.as_visibility().unwrap().hidden());
+
+        // `axm-online` and `axm-offline` both send the current state
+        for online in [true, false] {
+            let network = data(json!({ "m": "%22Incr%22", "t": "network", "d": { "o": online } }));
+            assert_eq!(network, EventMessageFromSocketData::Network { online });
+            let network = EventData::from_socket(network, Default::default()).unwrap();
+            assert_eq!(network.as_network().unwrap().online(), online);
+        }
+
+        let beforeunload = data(json!({ "m": "%22Incr%22", "t": "window_beforeunload" }));
+        assert_eq!(beforeunload, EventMessageFromSocketData::WindowBeforeunload);
+        assert!(EventData::from_socket(beforeunload, Default::default()).is_none());
+    }
+
+    #[test]
+    fn deserialize_message_from_socket_intersection() {
+        let msg = serde_json::from_value::<MessageFromSocket<Msg>>(json!({
+            "m": "%22Incr%22",
+            "t": "intersection",
+            "d": { "ir": 0.5, "i": true }
+        }))
+        .unwrap();
+        let data = match msg {
+            MessageFromSocket::Event { data, .. } => data,
+            _ => panic!("expected an event"),
+        };
+        assert_eq!(
+            data,
+            EventMessageFromSocketData::Intersection {
+                ratio: 0.5,
+                intersecting: true
+            }
+        );
+
+        let data = EventData::from_socket(data, Default::default()).unwrap();
+        let intersection = data.as_intersection().unwrap();
+        assert_eq!(intersection.ratio(), 0.5);
+        assert!(intersection.intersecting());
+    }
+
+    #[test]
+    fn window_constructor_bindings() {
+        use crate as axum_live_view;
+        use crate::html;
+
+        event_data_argument!(Width(f64), Resize, |data| Some(data.as_resize()?.width()));
+        event_data_argument!(Hidden(bool), Visibility, |data| Some(
+            data.as_visibility()?.hidden()
+        ));
+        event_data_argument!(Online(bool), Network, |data| Some(
+            data.as_network()?.online()
+        ));
+
+        #[derive(Debug, Serialize, Deserialize, PartialEq)]
+        enum Msg {
+            Resized(Width),
+            VisibilityChanged(Hidden),
+            NetworkChanged(Online),
+        }
+
+        let resolvers = html::Resolvers::of::<Msg>(&html! {
+            <div
+                axm-window-resize={ Msg::Resized }
+                axm-visibility-change={ Msg::VisibilityChanged }
+                axm-online={ Msg::NetworkChanged }
+                axm-offline={ Msg::NetworkChanged }
+            ></div>
+        });
+
+        assert_eq!(
+            build_ctor_message(
+                &resolvers,
+                html::Ctor::new(Msg::Resized),
+                json!({ "t": "resize", "d": { "w": 800.0, "h": 600.0 } })
+            ),
+            Msg::Resized(Width(800.0))
+        );
+        assert_eq!(
+            build_ctor_message(
+                &resolvers,
+                html::Ctor::new(Msg::VisibilityChanged),
+                json!({ "t": "visibility", "d": { "h": true } })
+            ),
+            Msg::VisibilityChanged(Hidden(true))
+        );
+        assert_eq!(
+            build_ctor_message(
+                &resolvers,
+                html::Ctor::new(Msg::NetworkChanged),
+                json!({ "t": "network", "d": { "o": false } })
+            ),
+            Msg::NetworkChanged(Online(false))
+        );
+    }
+
+    #[test]
+    fn viewport_constructor_bindings() {
+        use crate as axum_live_view;
+        use crate::html;
+
+        event_data_argument!(InView(bool), Intersection, |data| Some(
+            data.as_intersection()?.intersecting()
+        ));
+
+        #[derive(Debug, Serialize, Deserialize, PartialEq)]
+        enum Msg {
+            Scrolled(InView),
+        }
+
+        let resolvers = html::Resolvers::of::<Msg>(&html! {
+            <div axm-viewport-enter={ Msg::Scrolled } axm-viewport-leave={ Msg::Scrolled }></div>
+        });
+
+        assert_eq!(
+            build_ctor_message(
+                &resolvers,
+                html::Ctor::new(Msg::Scrolled),
+                json!({ "t": "intersection", "d": { "ir": 1.0, "i": true } })
+            ),
+            Msg::Scrolled(InView(true))
+        );
+    }
+
+    #[test]
+    fn deserialize_constructor_message() {
+        use crate as axum_live_view;
+        use crate::html;
+
+        #[derive(Debug, Serialize, Deserialize, PartialEq)]
+        enum Msg {
+            TextChanged(String),
+        }
+
+        let resolvers = html::Resolvers::of::<Msg>(&html! {
+            <input axm-input={ Msg::TextChanged } />
+        });
+
+        let m = serde_json::to_string(&html::Ctor::new(Msg::TextChanged)).unwrap();
+        let msg = serde_json::from_value::<MessageFromSocket<Msg>>(json!({
+            "m": m,
+            "t": "input",
+            "d": { "v": "hi" },
+            "r": 1
+        }))
+        .unwrap();
+        let (ctor, data) = match msg {
+            MessageFromSocket::Ctor {
+                ctor,
+                data,
+                values,
+                ref_id: Some(1),
+            } => (ctor, EventData::from_socket(data, values)),
+            _ => panic!("expected a constructor message"),
+        };
+        assert_eq!(
+            ctor.clone()
+                .build::<Msg>(&resolvers, data.as_ref())
+                .unwrap(),
+            Msg::TextChanged("hi".to_owned())
+        );
+        assert!(ctor.clone().build::<Msg>(&resolvers, None).is_err());
+        assert!(ctor
+            .build::<Msg>(&Default::default(), data.as_ref())
+            .is_err());
+    }
+
+    #[test]
+    fn deserialize_event_data() {
+        let data = |value| match serde_json::from_value::<MessageFromSocket<Msg>>(value).unwrap() {
+            MessageFromSocket::Event { data, values, .. } => {
+                EventData::from_socket(data, values).unwrap()
+            }
+            _ => panic!("expected an event"),
+        };
+
+        let touch = data(json!({
+            "m": "%22Incr%22",
+            "t": "touch",
+            "d": {
+                "ts": [],
+                "cts": [{ "id": 3, "cx": 1.0, "cy": 2.0, "px": 1.0, "py": 2.0, "sx": 5.0, "sy": 6.0 }]
+            }
+        }));
+        let touch = touch.as_touch().unwrap();
+        assert!(touch.touches().is_empty());
+        assert_eq!(touch.changed_touches()[0].identifier(), 3);
+        assert_eq!(touch.changed_touches()[0].screen_y(), 6.0);
+
+        let drag = data(json!({
+            "m": "%22Incr%22",
+            "t": "drag",
+            "d": { "id": "card-1", "cx": 10.0, "cy": 20.0 }
+        }));
+        assert_eq!(drag.as_drag().unwrap().dragged_id(), Some("card-1"));
+
+        let wheel = data(json!({
+            "m": "%22Incr%22",
+            "t": "wheel",
+            "d": { "dx": 0.0, "dy": -48.0, "dz": 0.0, "cx": 1.0, "cy": 1.0, "c": true }
+        }));
+        let wheel = wheel.as_wheel().unwrap();
+        assert_eq!(wheel.delta_y(), -48.0);
+        assert!(wheel.ctrl());
+
+        let pointer = data(json!({
+            "m": "%22Incr%22",
+            "t": "pointer",
+            "d": {
+                "id": 1, "pt": "pen", "pr": true, "cx": 1.0, "cy": 2.0, "px": 1.0, "py": 2.0,
+                "ox": 0.0, "oy": 0.0, "w": 1.0, "h": 1.0, "p": 0.5
+            }
+        }));
+        let pointer = pointer.as_pointer().unwrap();
+        assert_eq!(pointer.pointer_type(), "pen");
+        assert_eq!(pointer.pressure(), 0.5);
+
+        let resize =
+            data(json!({ "m": "%22Incr%22", "t": "resize", "d": { "w": 800.0, "h": 600.0 } }));
+        assert_eq!(resize.as_resize().unwrap().width(), 800.0);
+
+        let visibility = data(json!({ "m": "%22Incr%22", "t": "visibility", "d": { "h": true } }));
+        assert!(visibility.as_visibility().unwrap().hidden());
+
+        let network = data(json!({ "m": "%22Incr%22", "t": "network", "d": { "o": false } }));
+        assert!(!network.as_network().unwrap().online());
+
+        let intersection = data(json!({
+            "m": "%22Incr%22",
+            "t": "intersection",
+            "d": { "ir": 0.25, "i": true }
+        }));
+        let intersection = intersection.as_intersection().unwrap();
+        assert_eq!(intersection.ratio(), 0.25);
+        assert!(intersection.intersecting());
+
+        let click = data(json!({
+            "m": "%22Incr%22",
+            "t": "click",
+            "v": { "id": 1, "name": "Bob" }
+        }));
+        assert!(click.as_mouse().is_none());
+        assert_eq!(click.values().unwrap().get("id"), Some(&json!(1)));
+
+        #[derive(Deserialize)]
+        struct Item {
+            id: u64,
+            name: String,
+        }
+        let item = click.values().unwrap().deserialize::<Item>().unwrap();
+        assert_eq!(item.id, 1);
+        assert_eq!(item.name, "Bob");
+
+        let key = data(json!({
+            "m": "%22Incr%22",
+            "t": "key",
+            "d": { "k": "a", "kc": "KeyA", "a": false, "c": false, "s": false, "me": false },
+            "v": { "id": 2 }
+        }));
+        assert_eq!(key.as_key().unwrap().key(), "a");
+        assert_eq!(key.values().unwrap().get("id"), Some(&json!(2)));
+
+        let msg = serde_json::from_value::<MessageFromSocket<Msg>>(
+            json!({ "m": "%22Incr%22", "t": "click" }),
+        )
+        .unwrap();
+        match msg {
+            MessageFromSocket::Event { data, values, .. } => {
+                assert!(EventData::from_socket(data, values).is_none())
+            }
+            _ => panic!("expected an event"),
+        }
+
+        let msg = serde_json::from_value::<MessageFromSocket<Msg>>(
+            json!({ "m": "%22Incr%22", "t": "window_beforeunload" }),
+        )
+        .unwrap();
+        assert!(matches!(
+            msg,
+            MessageFromSocket::Event {
+                data: EventMessageFromSocketData::WindowBeforeunload,
+                ..
+            }
+        ));
+    }
+
+    #[tokio::test]
+    async fn render_batching_drain() {
+        let mut stream = futures_util::stream::iter([Ok(2), Ok(3), Err(()), Ok(4)]);
+        let mut items = Vec::from([Some(Ok(1))]);
+        RenderBatching::Drain.collect(&mut stream, &mut items).await;
+        assert_eq!(
+            items,
+            [Some(Ok(1)), Some(Ok(2)), Some(Ok(3)), Some(Err(()))]
+        );
+
+        let mut stream = futures_util::stream::iter([Ok::<_, ()>(2)]);
+        let mut items = Vec::from([Some(Ok(1))]);
+        RenderBatching::Drain.collect(&mut stream, &mut items).await;
+        assert_eq!(items, [Some(Ok(1)), Some(Ok(2)), None]);
+
+        let mut stream = futures_util::stream::iter([Ok::<_, ()>(2)]);
+        let mut items = Vec::from([Some(Ok(1))]);
+        RenderBatching::Disabled
+            .collect(&mut stream, &mut items)
+            .await;
+        assert_eq!(items, [Some(Ok(1))]);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn render_batching_frame() {
+        let (tx, rx) = mpsc::channel(16);
+        let mut stream = ReceiverStream::new(rx).map(Ok::<_, ()>);
+
+        tokio::spawn(async move {
+            for n in 2..10 {
+                tokio::time::sleep(Duration::from_millis(5)).await;
+                tx.send(n).await.unwrap();
+            }
+        });
+
+        let mut items = Vec::from([Some(Ok(1))]);
+        RenderBatching::Frame(Duration::from_millis(16))
+            .collect(&mut stream, &mut items)
+            .await;
+        assert_eq!(items, [Some(Ok(1)), Some(Ok(2)), Some(Ok(3)), Some(Ok(4))]);
+    }
+
+    #[tokio::test]
+    async fn change_tracking_skips_render() {
+        use crate as axum_live_view;
+        use crate::{
+            html,
+            live_view::{Fingerprint, LiveState},
+        };
+        use std::sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        };
+
+        #[derive(LiveState)]
+        struct Counter {
+            count: u64,
+            #[live_state(skip)]
+            renders: Arc<AtomicUsize>,
+        }
+
+        impl LiveView for Counter {
+            type Message = Msg;
+
+            fn update(mut self, msg: Msg, _: Option<EventData>) -> Updated<Self> {
+                match msg {
+                    Msg::Incr => self.count += 1,
+                    Msg::Decr => {}
+                }
+                Updated::new(self)
+            }
+
+            fn render(&self) -> Html<Msg> {
+                self.renders.fetch_add(1, Ordering::SeqCst);
+                html! { <p>{ self.count }</p> }
+            }
+
+            fn fingerprint(&self) -> Option<Fingerprint> {
+                Some(self.fingerprint_fields())
+            }
+        }
+
+        let renders = Arc::new(AtomicUsize::new(0));
+        let view = spawn_view(
+            Counter {
+                count: 0,
+                renders: Arc::clone(&renders),
+            },
+            None,
+        );
+
+        // only the initial render
+        let response = view.update(Vec::from([(Msg::Decr, None)])).await.unwrap();
+        assert!(matches!(response, UpdateResponse::Empty));
+        assert_eq!(renders.load(Ordering::SeqCst), 1);
+
+        let response = view.update(Vec::from([(Msg::Incr, None)])).await.unwrap();
+        match response {
+            UpdateResponse::Diff(Rendered { html, .. }) => {
+                assert_eq!(html, json!({ "d": { "0": { "d": { "0": "1" } } } }))
+            }
+            _ => panic!("expected a diff"),
+        }
+        assert_eq!(renders.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn unserializable_html_stops_view() {
+        use crate as axum_live_view;
+        use crate::html;
+        use std::sync::{
+            atomic::{AtomicBool, Ordering},
+            Arc,
+        };
+
+        #[derive(Serialize, Deserialize, Debug, PartialEq)]
+        enum Msg {
+            Show,
+            Toggled { enabled: bool, checked: bool },
+        }
+
+        struct View {
+            show: bool,
+            unmounted: Arc<AtomicBool>,
+        }
+
+        impl LiveView for View {
+            type Message = Msg;
+
+            fn update(mut self, _: Msg, _: Option<EventData>) -> Updated<Self> {
+                self.show = true;
+                Updated::new(self)
+            }
+
+            fn render(&self) -> Html<Msg> {
+                html! {
+                    if self.show {
+                        // the argument can't be told apart from `enabled`
+                        <input
+                            type="checkbox"
+                            axm-change={ |checked| Msg::Toggled { enabled: false, checked } }
+                        />
+                    }
+                }
+            }
+
+            fn unmount(&mut self) {
+                self.unmounted.store(true, Ordering::SeqCst);
+            }
+        }
+
+        let unmounted = Arc::new(AtomicBool::new(false));
+        let view = spawn_view(
+            View {
+                show: false,
+                unmounted: Arc::clone(&unmounted),
+            },
+            None,
+        );
+        view.render().await.unwrap();
+
+        assert!(view.update(Vec::from([(Msg::Show, None)])).await.is_err());
+        assert!(unmounted.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn hibernate_idle_view() {
+        use crate as axum_live_view;
+        use crate::{html, live_view::Hibernation};
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        static HIBERNATIONS: AtomicUsize = AtomicUsize::new(0);
+
+        #[derive(Serialize, Deserialize)]
+        struct Counter {
+            #[serde(serialize_with = "count_hibernations")]
+            count: u64,
+        }
+
+        fn count_hibernations<S>(count: &u64, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: serde::Serializer,
+        {
+            HIBERNATIONS.fetch_add(1, Ordering::SeqCst);
+            count.serialize(serializer)
+        }
+
+        impl LiveView for Counter {
+            type Message = Msg;
+
+            fn update(mut self, msg: Msg, _: Option<EventData>) -> Updated<Self> {
+                match msg {
+                    Msg::Incr => self.count += 1,
+                    Msg::Decr => self.count -= 1,
+                }
+                Updated::new(self)
+            }
+
+            fn render(&self) -> Html<Msg> {
+                html! { { self.count } }
+            }
+
+            fn hibernation(&self) -> Option<Hibernation<Self>> {
+                Some(Hibernation::after(Duration::from_secs(60)))
+            }
+        }
+
+        let (in_tx, mut out_rx) =
+            spawn_run_view(Counter { count: 0 }, RunViewConfig::default(), None);
+
+        let incr = || MessageFromSocket::Event {
+            msg: Msg::Incr,
+            data: EventMessageFromSocketData::Click,
+            ref_id: None,
+            values: Default::default(),
+        };
+        let render =
+            |count: &str| json!({ "t": "r", "d": { "d": { "0": { "d": { "0": count } } } } });
+
+        assert_eq!(out_rx.recv().await.unwrap()["t"], "i");
+        in_tx.send(incr()).await.unwrap();
+        assert_eq!(out_rx.recv().await.unwrap(), render("1"));
+        assert_eq!(HIBERNATIONS.load(Ordering::SeqCst), 0);
+
+        tokio::time::sleep(Duration::from_secs(61)).await;
+        assert_eq!(HIBERNATIONS.load(Ordering::SeqCst), 1);
+
+        // the view is restored with its state intact
+        in_tx.send(incr()).await.unwrap();
+        assert_eq!(out_rx.recv().await.unwrap(), render("2"));
+
+        tokio::time::sleep(Duration::from_secs(30)).await;
+        assert_eq!(HIBERNATIONS.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn unmount_hibernated_view() {
+        use crate as axum_live_view;
+        use crate::{html, live_view::Hibernation, shutdown::Shutdown};
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        // the view is serialized while hibernating so it can't hold on to an `Arc`
+        static UNMOUNTED_AFTER_RESTORE: AtomicUsize = AtomicUsize::new(0);
+
+        #[derive(Serialize, Deserialize)]
+        struct Counter {
+            count: u64,
+            // `false` once the view has been restored from hibernation
+            #[serde(skip)]
+            running: bool,
+        }
+
+        impl LiveView for Counter {
+            type Message = Msg;
+
+            fn update(self, _: Msg, _: Option<EventData>) -> Updated<Self> {
+                Updated::new(self)
+            }
+
+            fn render(&self) -> Html<Msg> {
+                html! { { self.count } }
+            }
+
+            fn hibernation(&self) -> Option<Hibernation<Self>> {
+                Some(Hibernation::after(Duration::from_secs(60)))
+            }
+
+            fn unmount(&mut self) {
+                if !self.running {
+                    UNMOUNTED_AFTER_RESTORE.fetch_add(1, Ordering::SeqCst);
+                }
+            }
+        }
+
+        let counter = || Counter {
+            count: 0,
+            running: true,
+        };
+
+        // the socket closes while the view is hibernating
+        let (in_tx, in_rx) = mpsc::channel::<Result<MessageFromSocket<Msg>, &str>>(16);
+        let mut out_rx = spawn_run_view_reading(
+            counter(),
+            RunViewConfig::default(),
+            None,
+            ReceiverStream::new(in_rx),
+        );
+        assert_eq!(out_rx.recv().await.unwrap()["t"], "i");
+
+        tokio::time::sleep(Duration::from_secs(61)).await;
+        assert_eq!(UNMOUNTED_AFTER_RESTORE.load(Ordering::SeqCst), 0);
+
+        in_tx.send(Err("connection closed")).await.unwrap();
+        assert!(out_rx.recv().await.is_none());
+        assert_eq!(UNMOUNTED_AFTER_RESTORE.load(Ordering::SeqCst), 1);
+
+        // the server shuts down while the view is hibernating
+        let shutdown = Shutdown::new();
+        let (_in_tx, mut out_rx) = spawn_run_view(
+            counter(),
+            RunViewConfig {
+                shutdown: Some(shutdown.clone()),
+                ..Default::default()
+            },
+            None,
+        );
+        assert_eq!(out_rx.recv().await.unwrap()["t"], "i");
+
+        tokio::time::sleep(Duration::from_secs(61)).await;
+        assert_eq!(UNMOUNTED_AFTER_RESTORE.load(Ordering::SeqCst), 1);
+
+        shutdown.shutdown().await;
+        assert_eq!(UNMOUNTED_AFTER_RESTORE.load(Ordering::SeqCst), 2);
+        assert_eq!(out_rx.recv().await.unwrap()["t"], "rs");
+        assert!(out_rx.recv().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn resume_from_session() {
+        use crate as axum_live_view;
+        use crate::{
+            html,
+            session::{MemoryStore, Sessions},
+        };
+
+        #[derive(Serialize, Deserialize)]
+        struct Counter {
+            count: u64,
+        }
+
+        impl LiveView for Counter {
+            type Message = Msg;
+
+            fn update(mut self, msg: Msg, _: Option<EventData>) -> Updated<Self> {
+                match msg {
+                    Msg::Incr => self.count += 1,
+                    Msg::Decr => self.count -= 1,
+                }
+                Updated::new(self)
+            }
+
+            fn render(&self) -> Html<Msg> {
+                html! { { self.count } }
+            }
+        }
+
+        let sessions = Sessions::new(MemoryStore::new(), "0123456789abcdef0123456789abcdef");
+
+        // the initial page load embeds the token
+        let session = sessions.start();
+        let html = EmbedLiveView::noop(Some(&session))
+            .embed_resumable(Counter { count: 0 })
+            .render();
+        assert!(html.contains(&format!("data-axm-session=\"{}\"", session.token)));
+
+        // the first connection has nothing to resume
+        let session = sessions.resume(&session.token).await.unwrap();
+        let mut embedded = None;
+        EmbedLiveView::new(&mut embedded, Some(&session)).embed_resumable(Counter { count: 0 });
+        let (view, checkpoint) = embedded.unwrap();
+        assert_eq!(view.count, 0);
+
+        let (in_tx, mut out_rx) = spawn_run_view(view, RunViewConfig::default(), checkpoint);
+        assert_eq!(out_rx.recv().await.unwrap()["t"], "i");
+        in_tx
+            .send(MessageFromSocket::Event {
+                msg: Msg::Incr,
+                data: EventMessageFromSocketData::Click,
+                ref_id: None,
+                values: Default::default(),
+            })
+            .await
+            .unwrap();
+        assert_eq!(out_rx.recv().await.unwrap()["t"], "r");
+        // the checkpoint is saved before the next message is handled
+        in_tx
+            .send(MessageFromSocket::Internal {
+                data: InternalMessageFromSocketData::Health,
+            })
+            .await
+            .unwrap();
+        assert_eq!(out_rx.recv().await.unwrap()["t"], "h");
+
+        // reconnecting resumes from the checkpoint
+        let session = sessions.resume(&session.token).await.unwrap();
+        let mut embedded = None;
+        EmbedLiveView::new(&mut embedded, Some(&session)).embed_resumable(Counter { count: 0 });
+        let (view, _) = embedded.unwrap();
+        assert_eq!(view.count, 1);
+    }
+
+    #[tokio::test]
+    async fn shutdown() {
+        use crate as axum_live_view;
+        use crate::{html, shutdown::Shutdown};
+        use std::sync::{
+            atomic::{AtomicBool, Ordering},
+            Arc,
+        };
+
+        struct View {
+            unmounted: Arc<AtomicBool>,
+        }
+
+        impl LiveView for View {
+            type Message = Msg;
+
+            fn update(self, _: Msg, _: Option<EventData>) -> Updated<Self> {
+                Updated::new(self)
+            }
+
+            fn render(&self) -> Html<Msg> {
+                html! { "view" }
+            }
+
+            fn unmount(&mut self) {
+                self.unmounted.store(true, Ordering::SeqCst);
+            }
+        }
+
+        let shutdown = Shutdown::new().reconnect_after(Duration::from_secs(2));
+        let unmounted = Arc::new(AtomicBool::new(false));
+
+        let (_in_tx, mut out_rx) = spawn_run_view(
+            View {
+                unmounted: unmounted.clone(),
+            },
+            RunViewConfig {
+                shutdown: Some(shutdown.clone()),
+                ..Default::default()
+            },
+            None,
+        );
+        assert_eq!(out_rx.recv().await.unwrap()["t"], "i");
+
+        shutdown.shutdown().await;
+        assert!(unmounted.load(Ordering::SeqCst));
+
+        let msg = out_rx.recv().await.unwrap();
+        assert_eq!(msg["t"], "rs");
+        let reconnect_after = msg["d"].as_u64().unwrap();
+        assert!((2000..=4000).contains(&reconnect_after));
+
+        assert!(out_rx.recv().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn acknowledge_events() {
+        use crate as axum_live_view;
+        use crate::html;
+
+        struct Counter {
+            count: u64,
+        }
+
+        impl LiveView for Counter {
+            type Message = Msg;
+
+            fn update(mut self, msg: Msg, _: Option<EventData>) -> Updated<Self> {
+                if msg == Msg::Incr {
+                    self.count += 1;
                 }
+                Updated::new(self)
+            }
+
+            fn render(&self) -> Html<Msg> {
+                html! { { self.count } }
+            }
+        }
+
+        let (in_tx, mut out_rx) =
+            spawn_run_view(Counter { count: 0 }, RunViewConfig::default(), None);
+        assert_eq!(out_rx.recv().await.unwrap()["t"], "i");
+
+        // the ack is sent after the diff
+        in_tx
+            .send(MessageFromSocket::Event {
+                msg: Msg::Incr,
+                data: EventMessageFromSocketData::Click,
+                ref_id: Some(1),
+                values: Default::default(),
+            })
+            .await
+            .unwrap();
+        assert_eq!(out_rx.recv().await.unwrap()["t"], "r");
+        assert_eq!(out_rx.recv().await.unwrap(), json!({ "t": "a", "d": [1] }));
+
+        // events that don't change the view are also acknowledged
+        in_tx
+            .send(MessageFromSocket::Event {
+                msg: Msg::Decr,
+                data: EventMessageFromSocketData::Click,
+                ref_id: Some(2),
+                values: Default::default(),
+            })
+            .await
+            .unwrap();
+        assert_eq!(out_rx.recv().await.unwrap(), json!({ "t": "a", "d": [2] }));
+
+        // so are constructors that fail to build a message
+        #[derive(Serialize)]
+        enum Other {
+            Renamed(String),
+        }
+        let m = serde_json::to_string(&html::Ctor::new(Other::Renamed)).unwrap();
+        in_tx
+            .send(
+                serde_json::from_value(json!({
+                    "m": m,
+                    "t": "input",
+                    "d": { "v": "hi" },
+                    "r": 3
+                }))
+                .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(out_rx.recv().await.unwrap(), json!({ "t": "a", "d": [3] }));
+    }
+
+    #[cfg(feature = "live-reload")]
+    #[tokio::test(start_paused = true)]
+    async fn live_reload() {
+        use crate as axum_live_view;
+        use crate::{html, live_reload::LiveReload};
+
+        struct View;
+
+        impl LiveView for View {
+            type Message = Msg;
+
+            fn update(self, _: Msg, _: Option<EventData>) -> Updated<Self> {
+                Updated::new(self)
+            }
+
+            fn render(&self) -> Html<Msg> {
+                html! { "view" }
             }
+        }
+
+        let live_reload = LiveReload::new();
+        let config = |client_build_id: Option<&str>| RunViewConfig {
+            live_reload: Some(live_reload.clone()),
+            client_build_id: client_build_id.map(ToOwned::to_owned),
+            ..Default::default()
+        };
+        let reload = json!([{ "kind": { "t": "reload" }, "delay_ms": null }]);
+
+        // the first connection is told the build ID
+        let (_in_tx, mut out_rx) = spawn_run_view(View, config(None), None);
+        assert_eq!(
+            out_rx.recv().await.unwrap(),
+            json!({ "t": "b", "d": live_reload.build_id() })
+        );
+        assert_eq!(out_rx.recv().await.unwrap()["t"], "i");
+
+        // watched files changing reloads the client
+        live_reload.trigger();
+        live_reload.trigger();
+        let msg = out_rx.recv().await.unwrap();
+        assert_eq!(msg, json!({ "t": "j", "d": reload }));
+
+        // reconnecting to the same build doesn't reload
+        let (_in_tx, mut out_rx) = spawn_run_view(View, config(Some(live_reload.build_id())), None);
+        assert_eq!(out_rx.recv().await.unwrap()["t"], "b");
+        assert_eq!(out_rx.recv().await.unwrap()["t"], "i");
+
+        // reconnecting to a new build does
+        let (_in_tx, mut out_rx) = spawn_run_view(View, config(Some("old")), None);
+        assert_eq!(
+            out_rx.recv().await.unwrap(),
+            json!({ "t": "j", "d": reload })
         );
+        assert!(out_rx.recv().await.is_none());
+    }
+
+    /// Define an argument for constructor bindings that's read from the data of `$kind` events.
+    ///
+    /// Each argument only fits the kind of event it's read from, so bindings using it only compile
+    /// if `html!` maps the attribute to the right kind.
+    macro_rules! event_data_argument {
+        ($name:ident($ty:ty), $kind:ident, |$data:ident| $read:expr) => {
+            #[derive(Debug, Default, Serialize, Deserialize, PartialEq)]
+            struct $name($ty);
+
+            impl crate::event_data::FromEventData for $name {
+                type Kind = crate::event_data::kind::$kind;
+
+                fn from_event_data(
+                    data: Option<&EventData>,
+                ) -> Result<Self, crate::event_data::FromEventDataError> {
+                    data.and_then(|$data| $read).map(Self).ok_or_else(|| {
+                        crate::event_data::FromEventDataError::unexpected(stringify!($kind), data)
+                    })
+                }
+            }
+        };
+    }
+    use event_data_argument;
+
+    /// Build the message for `ctor` from an event sent by the client.
+    fn build_ctor_message<M>(resolvers: &html::Resolvers, ctor: html::Ctor<M>, event: Value) -> M
+    where
+        M: Serialize + DeserializeOwned,
+    {
+        let mut event = event;
+        event["m"] = json!(serde_json::to_string(&ctor).unwrap());
+        match serde_json::from_value::<MessageFromSocket<M>>(event).unwrap() {
+            MessageFromSocket::Ctor {
+                ctor, data, values, ..
+            } => ctor
+                .build::<M>(resolvers, EventData::from_socket(data, values).as_ref())
+                .unwrap(),
+            _ => panic!("expected a constructor message"),
+        }
+    }
+
+    /// Run `view` in the background, returning a sender for messages from the client and a
+    /// receiver for the messages sent to it.
+    fn spawn_run_view<L>(
+        view: L,
+        config: RunViewConfig,
+        checkpoint: Option<Checkpoint<L>>,
+    ) -> (
+        mpsc::Sender<MessageFromSocket<L::Message>>,
+        mpsc::UnboundedReceiver<Value>,
+    )
+    where
+        L: LiveView,
+    {
+        let (in_tx, in_rx) = mpsc::channel(16);
+        let read = ReceiverStream::new(in_rx).map(Ok::<_, Infallible>);
+        let out_rx = spawn_run_view_reading(view, config, checkpoint, read);
+        (in_tx, out_rx)
+    }
+
+    /// Like [`spawn_run_view`] but reads messages from the client from `read`, which can fail
+    /// like a socket does when the connection is closed.
+    fn spawn_run_view_reading<L, R>(
+        view: L,
+        config: RunViewConfig,
+        checkpoint: Option<Checkpoint<L>>,
+        read: R,
+    ) -> mpsc::UnboundedReceiver<Value>
+    where
+        L: LiveView,
+        R: TryStream<Ok = MessageFromSocket<L::Message>> + Unpin + Send + 'static,
+        R::Error: fmt::Display + Send + Sync + 'static,
+    {
+        let (out_tx, out_rx) = mpsc::unbounded_channel();
+
+        let write = Box::pin(futures_util::sink::unfold(
+            out_tx,
+            |out_tx, msg: MessageToSocket| async move {
+                out_tx.send(serde_json::to_value(msg).unwrap()).unwrap();
+                Ok::<_, Infallible>(out_tx)
+            },
+        ));
+
+        tokio::spawn(run_view(
+            write,
+            read,
+            view,
+            Uri::from_static("/"),
+            HeaderMap::new(),
+            config,
+            checkpoint,
+        ));
+
+        out_rx
     }
 
     #[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
@@ -0,0 +1,164 @@
+//! Reload the browser when the server restarts or files change during development.
+//!
+//! Without live reload, restarting the server, for example with [`cargo watch`], makes the client
+//! reconnect to the new server but it keeps showing the old page. With [`LiveReload`] enabled each
+//! server boot gets a new build ID. The client remembers the build ID of the server it first
+//! connected to, and if it reconnects to a server with a different build ID it's told to reload
+//! the page.
+//!
+//! Directories with static files, such as CSS, can also be watched. Every connected client
+//! reloads when something in them changes.
+//!
+//! Live reload is enabled by adding a [`LiveReload`] as an [`Extension`]:
+//!
+//! ```no_run
+//! use axum::{routing::get, Extension, Router};
+//! use axum_live_view::live_reload::LiveReload;
+//!
+//! let live_reload = LiveReload::new();
+//! live_reload.watch("assets").unwrap();
+//!
+//! let app = Router::new()
+//!     .route("/", get(|| async { /* ... */ }))
+//!     .layer(Extension(live_reload));
+//! # let _: Router = app;
+//! ```
+//!
+//! This requires the `live-reload` feature, which should only be enabled during development.
+//!
+//! [`cargo watch`]: https://crates.io/crates/cargo-watch
+//! [`Extension`]: axum::Extension
+
+use std::{
+    fmt,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+use tokio::sync::watch;
+
+/// Reload the browser when the server restarts or files change.
+///
+/// See the [module docs](self) for more details.
+#[derive(Clone)]
+pub struct LiveReload {
+    build_id: Arc<str>,
+    /// Incremented every time a watched file changes.
+    changes: Arc<watch::Sender<u64>>,
+    /// The file watchers, kept alive for as long as the `LiveReload`.
+    watchers: Arc<Mutex<Vec<notify::RecommendedWatcher>>>,
+}
+
+impl LiveReload {
+    /// Create a new `LiveReload` with a random build ID.
+    ///
+    /// This should only be called once when the server boots.
+    pub fn new() -> Self {
+        let (changes, _) = watch::channel(0);
+        Self {
+            build_id: format!("{:016x}", rand::random::<u64>()).into(),
+            changes: Arc::new(changes),
+            watchers: Default::default(),
+        }
+    }
+
+    /// Reload every connected client when something in `path` changes.
+    ///
+    /// If `path` is a directory it's watched recursively.
+    pub fn watch<P>(&self, path: P) -> Result<(), WatchError>
+    where
+        P: AsRef<std::path::Path>,
+    {
+        use notify::{EventKind, RecursiveMode, Watcher};
+
+        let changes = Arc::clone(&self.changes);
+        let mut watcher =
+            notify::recommended_watcher(move |event: notify::Result<notify::Event>| match event {
+                Ok(event) => {
+                    if matches!(
+                        event.kind,
+                        EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)
+                    ) {
+                        tracing::debug!(paths = ?event.paths, "watched file changed");
+                        changes.send_modify(|changes| *changes += 1);
+                    }
+                }
+                Err(err) => tracing::warn!(%err, "error while watching files"),
+            })
+            .map_err(|inner| WatchError { inner })?;
+
+        watcher
+            .watch(path.as_ref(), RecursiveMode::Recursive)
+            .map_err(|inner| WatchError { inner })?;
+
+        self.watchers.lock().unwrap().push(watcher);
+
+        Ok(())
+    }
+
+    pub(crate) fn build_id(&self) -> &str {
+        &self.build_id
+    }
+
+    #[cfg(test)]
+    pub(crate) fn trigger(&self) {
+        self.changes.send_modify(|changes| *changes += 1);
+    }
+
+    pub(crate) fn subscribe(&self) -> ReloadSignal {
+        ReloadSignal {
+            rx: self.changes.subscribe(),
+        }
+    }
+}
+
+impl Default for LiveReload {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for LiveReload {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LiveReload")
+            .field("build_id", &self.build_id)
+            .finish()
+    }
+}
+
+/// Notifies a single view about changes to watched files.
+#[derive(Debug)]
+pub(crate) struct ReloadSignal {
+    rx: watch::Receiver<u64>,
+}
+
+impl ReloadSignal {
+    /// Editors often write files in several steps so wait until things have settled down.
+    const DEBOUNCE: Duration = Duration::from_millis(50);
+
+    /// Wait until a watched file has changed.
+    pub(crate) async fn changed(&mut self) {
+        if self.rx.changed().await.is_err() {
+            futures_util::future::pending::<()>().await;
+        }
+
+        while let Ok(Ok(())) = tokio::time::timeout(Self::DEBOUNCE, self.rx.changed()).await {}
+    }
+}
+
+/// Error returned from [`LiveReload::watch`].
+#[derive(Debug)]
+pub struct WatchError {
+    inner: notify::Error,
+}
+
+impl fmt::Display for WatchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "failed to watch files")
+    }
+}
+
+impl std::error::Error for WatchError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.inner)
+    }
+}
@@ -94,6 +94,10 @@ where
             handle.clone().with(Either1::T1),
         );
     }
+    fn unmount(&mut self) {
+        let Self { views: (T1,), .. } = self;
+        T1.unmount();
+    }
     fn update(mut self, msg: Self::Message, data: Option<EventData>) -> Updated<Self> {
         match msg {
             Either1::T1(msg) => {
@@ -158,6 +162,13 @@ where
             handle.clone().with(Either2::T2),
         );
     }
+    fn unmount(&mut self) {
+        let Self {
+            views: (T1, T2), ..
+        } = self;
+        T1.unmount();
+        T2.unmount();
+    }
     fn update(mut self, msg: Self::Message, data: Option<EventData>) -> Updated<Self> {
         match msg {
             Either2::T1(msg) => {
@@ -253,6 +264,15 @@ where
             handle.clone().with(Either3::T3),
         );
     }
+    fn unmount(&mut self) {
+        let Self {
+            views: (T1, T2, T3),
+            ..
+        } = self;
+        T1.unmount();
+        T2.unmount();
+        T3.unmount();
+    }
     fn update(mut self, msg: Self::Message, data: Option<EventData>) -> Updated<Self> {
         match msg {
             Either3::T1(msg) => {
@@ -382,6 +402,16 @@ where
             handle.clone().with(Either4::T4),
         );
     }
+    fn unmount(&mut self) {
+        let Self {
+            views: (T1, T2, T3, T4),
+            ..
+        } = self;
+        T1.unmount();
+        T2.unmount();
+        T3.unmount();
+        T4.unmount();
+    }
     fn update(mut self, msg: Self::Message, data: Option<EventData>) -> Updated<Self> {
         match msg {
             Either4::T1(msg) => {
@@ -543,6 +573,17 @@ where
             handle.clone().with(Either5::T5),
         );
     }
+    fn unmount(&mut self) {
+        let Self {
+            views: (T1, T2, T3, T4, T5),
+            ..
+        } = self;
+        T1.unmount();
+        T2.unmount();
+        T3.unmount();
+        T4.unmount();
+        T5.unmount();
+    }
     fn update(mut self, msg: Self::Message, data: Option<EventData>) -> Updated<Self> {
         match msg {
             Either5::T1(msg) => {
@@ -790,6 +831,18 @@ where
             handle.clone().with(Either6::T6),
         );
     }
+    fn unmount(&mut self) {
+        let Self {
+            views: (T1, T2, T3, T4, T5, T6),
+            ..
+        } = self;
+        T1.unmount();
+        T2.unmount();
+        T3.unmount();
+        T4.unmount();
+        T5.unmount();
+        T6.unmount();
+    }
     fn update(mut self, msg: Self::Message, data: Option<EventData>) -> Updated<Self> {
         match msg {
             Either6::T1(msg) => {
@@ -1099,6 +1152,19 @@ where
             handle.clone().with(Either7::T7),
         );
     }
+    fn unmount(&mut self) {
+        let Self {
+            views: (T1, T2, T3, T4, T5, T6, T7),
+            ..
+        } = self;
+        T1.unmount();
+        T2.unmount();
+        T3.unmount();
+        T4.unmount();
+        T5.unmount();
+        T6.unmount();
+        T7.unmount();
+    }
     fn update(mut self, msg: Self::Message, data: Option<EventData>) -> Updated<Self> {
         match msg {
             Either7::T1(msg) => {
@@ -1459,6 +1525,20 @@ where
             handle.clone().with(Either8::T8),
         );
     }
+    fn unmount(&mut self) {
+        let Self {
+            views: (T1, T2, T3, T4, T5, T6, T7, T8),
+            ..
+        } = self;
+        T1.unmount();
+        T2.unmount();
+        T3.unmount();
+        T4.unmount();
+        T5.unmount();
+        T6.unmount();
+        T7.unmount();
+        T8.unmount();
+    }
     fn update(mut self, msg: Self::Message, data: Option<EventData>) -> Updated<Self> {
         match msg {
             Either8::T1(msg) => {
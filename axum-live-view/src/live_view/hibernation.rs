@@ -0,0 +1,100 @@
+use serde::{de::DeserializeOwned, Serialize};
+use std::{fmt, time::Duration};
+
+/// Hibernate views that have been idle for a while.
+///
+/// Each connected view keeps a task, the view itself, and its last rendered HTML in memory for as
+/// long as the client is connected, even if nothing happens. For views whose state is
+/// [`Serialize`] and [`DeserializeOwned`] that can be avoided with hibernation. After the view
+/// hasn't received any messages for the configured duration its state is serialized to a compact
+/// buffer and everything else is dropped. The view is transparently restored on the next message,
+/// regardless of whether it comes from the client or a [`ViewHandle`].
+///
+/// Hibernation is enabled by returning a `Hibernation` from [`LiveView::hibernation`]:
+///
+/// ```
+/// use axum_live_view::{
+///     html,
+///     event_data::EventData,
+///     live_view::{Hibernation, Updated},
+///     Html, LiveView,
+/// };
+/// use serde::{Deserialize, Serialize};
+/// use std::time::Duration;
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Counter {
+///     count: u64,
+/// }
+///
+/// impl LiveView for Counter {
+///     type Message = Msg;
+///
+///     fn update(mut self, msg: Msg, _: Option<EventData>) -> Updated<Self> {
+///         match msg {
+///             Msg::Increment => self.count += 1,
+///         }
+///         Updated::new(self)
+///     }
+///
+///     fn render(&self) -> Html<Msg> {
+///         html! {
+///             { self.count }
+///             <button axm-click={ Msg::Increment }>"+"</button>
+///         }
+///     }
+///
+///     fn hibernation(&self) -> Option<Hibernation<Self>> {
+///         Some(Hibernation::after(Duration::from_secs(5 * 60)))
+///     }
+/// }
+///
+/// #[derive(Serialize, Deserialize, Debug, PartialEq)]
+/// enum Msg {
+///     Increment,
+/// }
+/// ```
+///
+/// Note that [`LiveView::mount`] isn't called again when a view is restored. Fields skipped with
+/// `#[serde(skip)]` will have their default value after the view has been restored. A view that
+/// stops while hibernating is restored so [`LiveView::unmount`] can be called.
+///
+/// [`LiveView::hibernation`]: super::LiveView::hibernation
+/// [`LiveView::mount`]: super::LiveView::mount
+/// [`LiveView::unmount`]: super::LiveView::unmount
+/// [`ViewHandle`]: super::ViewHandle
+pub struct Hibernation<L> {
+    pub(crate) idle: Duration,
+    pub(crate) serialize: fn(&L) -> serde_json::Result<Vec<u8>>,
+    pub(crate) deserialize: fn(&[u8]) -> serde_json::Result<L>,
+}
+
+impl<L> Hibernation<L>
+where
+    L: Serialize + DeserializeOwned,
+{
+    /// Hibernate the view after it hasn't received any messages for `idle`.
+    pub fn after(idle: Duration) -> Self {
+        Self {
+            idle,
+            serialize: |view| serde_json::to_vec(view),
+            deserialize: |bytes| serde_json::from_slice(bytes),
+        }
+    }
+}
+
+impl<L> Clone for Hibernation<L> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<L> Copy for Hibernation<L> {}
+
+impl<L> fmt::Debug for Hibernation<L> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Hibernation")
+            .field("idle", &self.idle)
+            .finish()
+    }
+}
@@ -0,0 +1,225 @@
+use std::{
+    collections::{hash_map::DefaultHasher, HashSet},
+    hash::{Hash, Hasher},
+};
+
+/// Track which fields of a [`LiveView`] change between renders.
+///
+/// By default every update calls [`LiveView::render`] and diffs the entire template against the
+/// previous one, even if `update` only touched a single field. With change tracking enabled
+/// [`html!`] takes note of which fields of `self` each dynamic part of a template reads. Parts
+/// whose fields haven't changed since the previous render are reused without being evaluated or
+/// diffed. If no fields changed at all the view isn't rendered.
+///
+/// Change tracking is enabled by deriving `LiveState` and forwarding [`LiveView::fingerprint`] to
+/// it:
+///
+/// ```
+/// use axum_live_view::{
+///     html,
+///     event_data::EventData,
+///     live_view::{Fingerprint, LiveState, Updated},
+///     Html, LiveView,
+/// };
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(LiveState)]
+/// struct Counter {
+///     count: u64,
+///     clicks: u64,
+/// }
+///
+/// impl LiveView for Counter {
+///     type Message = Msg;
+///
+///     fn update(mut self, msg: Msg, _: Option<EventData>) -> Updated<Self> {
+///         match msg {
+///             Msg::Increment => self.count += 1,
+///             Msg::Click => self.clicks += 1,
+///         }
+///         Updated::new(self)
+///     }
+///
+///     fn render(&self) -> Html<Msg> {
+///         html! {
+///             // only evaluated when `count` changes
+///             <p>{ self.count }</p>
+///             // only evaluated when `clicks` changes
+///             <p>{ self.clicks } " clicks"</p>
+///             <button axm-click={ Msg::Increment }>"+"</button>
+///         }
+///     }
+///
+///     fn fingerprint(&self) -> Option<Fingerprint> {
+///         Some(self.fingerprint_fields())
+///     }
+/// }
+///
+/// #[derive(Serialize, Deserialize, Debug, PartialEq)]
+/// enum Msg {
+///     Increment,
+///     Click,
+/// }
+/// ```
+///
+/// # How changes are detected
+///
+/// The derive requires every field to implement [`Hash`] and changes are detected by comparing
+/// the hash of each field before and after calling [`LiveView::update`]. Fields that aren't
+/// rendered, such as channels or database pools, can be excluded with `#[live_state(skip)]`.
+/// Changes to skipped fields never cause the view to be rendered.
+///
+/// Each field is reduced to a single 64-bit hash with [`DefaultHasher`]. If a field changes to a
+/// value with the same hash the change is missed, and the parts reading it show the old value
+/// until they're rendered again. That is unlikely but not impossible. The hashes are only compared
+/// within the running server, so it doesn't matter that [`DefaultHasher`] may change between Rust
+/// releases. Fields where a stale render isn't acceptable can be marked with
+/// `#[live_state(untracked)]`. They don't have to implement [`Hash`] and count as changed after
+/// every update, so the view is always rendered and parts reading them are always evaluated.
+///
+/// # What gets tracked
+///
+/// Which fields a dynamic part of a template reads is determined from the code inside `html!`, so
+/// tracking is conservative. A part is only tracked if the only variables it uses are fields of
+/// `self`, it doesn't call methods on `self`, and it doesn't use macros, functions, or statics.
+/// For example:
+///
+/// ```
+/// # use axum_live_view::{html, Html};
+/// # struct View { count: u64, items: Vec<String> }
+/// # impl View {
+/// # fn total(&self) -> usize { 0 }
+/// # }
+/// # const MAX: u64 = 10;
+/// # impl View {
+/// fn render(&self) -> Html<()> {
+///     let offset = 1;
+///
+///     html! {
+///         // tracked, depends on `count`
+///         { self.count + 1 }
+///
+///         // tracked, depends on `items`
+///         for item in &self.items {
+///             <li>{ item }</li>
+///         }
+///
+///         // not tracked since it uses a local variable
+///         { self.count + offset }
+///
+///         // not tracked since methods can read any field
+///         { self.total() }
+///
+///         // not tracked since macros can expand to anything
+///         { format!("{} items", self.items.len()) }
+///
+///         // not tracked since `MAX` might be a static
+///         { MAX - self.count }
+///     }
+/// }
+/// # }
+/// ```
+///
+/// Untracked parts are evaluated on every render, exactly as if change tracking was disabled.
+///
+/// Since tracked parts are only evaluated when their fields change they must not depend on
+/// anything else, such as the current time or global state.
+///
+/// [`LiveView`]: super::LiveView
+/// [`LiveView::render`]: super::LiveView::render
+/// [`LiveView::update`]: super::LiveView::update
+/// [`LiveView::fingerprint`]: super::LiveView::fingerprint
+/// [`html!`]: crate::html!
+pub trait LiveState {
+    /// Compute the [`Fingerprint`] of each field.
+    fn fingerprint_fields(&self) -> Fingerprint;
+}
+
+/// The hashes of a [`LiveState`]'s fields.
+///
+/// Created by `#[derive(LiveState)]`. See [`LiveState`] for more details.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Fingerprint {
+    /// The hash of each field, or `None` for fields that always count as changed.
+    fields: Vec<(&'static str, Option<u64>)>,
+}
+
+impl Fingerprint {
+    #[doc(hidden)]
+    pub fn field<T>(&mut self, name: &'static str, value: &T)
+    where
+        T: Hash + ?Sized,
+    {
+        let mut hasher = DefaultHasher::new();
+        value.hash(&mut hasher);
+        self.fields.push((name, Some(hasher.finish())));
+    }
+
+    #[doc(hidden)]
+    pub fn untracked_field(&mut self, name: &'static str) {
+        self.fields.push((name, None));
+    }
+
+    /// The fields that have the same hash in `self` and `previous`.
+    pub(crate) fn unchanged_since(&self, previous: &Self) -> HashSet<&'static str> {
+        self.fields
+            .iter()
+            .filter(|field| field.1.is_some() && previous.fields.contains(field))
+            .map(|(name, _)| *name)
+            .collect()
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.fields.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate as axum_live_view;
+    use crate::live_view::LiveState;
+
+    #[derive(LiveState)]
+    struct State {
+        count: u64,
+        name: String,
+        #[live_state(skip)]
+        #[allow(dead_code)]
+        skipped: f64,
+        // doesn't implement `Hash`
+        #[live_state(untracked)]
+        #[allow(dead_code)]
+        ratio: f64,
+    }
+
+    #[derive(LiveState)]
+    struct Tuple(u64, &'static str);
+
+    #[test]
+    fn unchanged_fields() {
+        let mut state = State {
+            count: 0,
+            name: "foo".to_owned(),
+            skipped: 0.0,
+            ratio: 0.0,
+        };
+        let before = state.fingerprint_fields();
+        assert_eq!(before.len(), 3);
+
+        state.count += 1;
+        state.skipped += 1.0;
+        let after = state.fingerprint_fields();
+
+        assert_eq!(after.unchanged_since(&before), HashSet::from(["name"]));
+        // untracked fields always count as changed, even if nothing changed
+        assert_eq!(
+            after.unchanged_since(&after),
+            HashSet::from(["count", "name"])
+        );
+
+        let before = Tuple(0, "foo").fingerprint_fields();
+        let after = Tuple(1, "foo").fingerprint_fields();
+        assert_eq!(after.unchanged_since(&before), HashSet::from(["1"]));
+    }
+}
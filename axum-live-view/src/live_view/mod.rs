@@ -8,6 +8,18 @@ use std::{fmt, future::Future, pin::Pin};
 use tokio::sync::mpsc;
 
 mod combine;
+mod hibernation;
+mod live_state;
+
+pub use self::{
+    hibernation::Hibernation,
+    live_state::{Fingerprint, LiveState},
+};
+
+/// Derive [`LiveState`](trait@LiveState).
+///
+/// See [`LiveState`](trait@LiveState) for more details.
+pub use axum_live_view_macros::LiveState;
 
 /// A server-rendered live view.
 ///
@@ -94,6 +106,16 @@ pub trait LiveView: Sized + Send + Sync + 'static {
     #[allow(unused_variables)]
     fn mount(&mut self, uri: Uri, request_headers: &HeaderMap, handle: ViewHandle<Self::Message>) {}
 
+    /// Perform cleanup before the view is dropped.
+    ///
+    /// The default implementation does nothing and simply returns immediately.
+    ///
+    /// This is called when the client disconnects or the server is shutting down. See the
+    /// [`shutdown`](crate::shutdown) module for more details on the latter. Views that are
+    /// hibernating when the connection ends are restored first and then unmounted. See
+    /// [`Hibernation`] for more details.
+    fn unmount(&mut self) {}
+
     /// React to a message and asynchronously update the view.
     ///
     /// If an error is returned the view will be shutdown, the JavaScript client will reconnect,
@@ -106,6 +128,23 @@ pub trait LiveView: Sized + Send + Sync + 'static {
     /// This method will be called after [`update`](LiveView::update) and the changes will be
     /// effeciently sent to the client.
     fn render(&self) -> Html<Self::Message>;
+
+    /// Fingerprint the view's fields to enable change tracking.
+    ///
+    /// The default implementation returns `None` which disables change tracking. Derive
+    /// [`LiveState`] and return `Some(self.fingerprint_fields())` to enable it. See
+    /// [`LiveState`] for more details.
+    fn fingerprint(&self) -> Option<Fingerprint> {
+        None
+    }
+
+    /// Hibernate the view when it has been idle for a while.
+    ///
+    /// This is called once when the view is connected. The default implementation returns `None`
+    /// which disables hibernation. See [`Hibernation`] for more details.
+    fn hibernation(&self) -> Option<Hibernation<Self>> {
+        None
+    }
 }
 
 /// An updated live view as returned by [`LiveView::update`].
@@ -310,6 +349,12 @@ impl<M> ViewHandle<M> {
         }
     }
 
+    /// The number of messages waiting to be processed by the view.
+    #[cfg(feature = "dashboard")]
+    pub(crate) fn mailbox_depth(&self) -> usize {
+        self.tx.max_capacity() - self.tx.capacity()
+    }
+
     pub(crate) fn with<F, M2>(self, f: F) -> ViewHandle<M2>
     where
         F: Fn(M2) -> M + Send + Sync + 'static,
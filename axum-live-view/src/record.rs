@@ -0,0 +1,149 @@
+//! Record the messages sent to live views so sessions can be replayed.
+//!
+//! Bugs reported by users can be hard to reproduce since the state of a view depends on the
+//! entire sequence of messages it has received. A [`Recorder`] writes the URI and headers the view
+//! was mounted with, along with every message and its event data, to a [JSON lines] file per
+//! connection. The file can then be fed to [`test::replay`] to deterministically reproduce the
+//! session.
+//!
+//! Recording is enabled by adding a [`Recorder`] as an [`Extension`]:
+//!
+//! ```
+//! use axum::{routing::get, Extension, Router};
+//! use axum_live_view::record::Recorder;
+//!
+//! let app = Router::new()
+//!     .route("/", get(|| async { /* ... */ }))
+//!     .layer(Extension(Recorder::new("recordings")));
+//! # let _: Router = app;
+//! ```
+//!
+//! Note that recordings include the request headers, such as cookies, the view was mounted with,
+//! so they should be treated as sensitive.
+//!
+//! [JSON lines]: https://jsonlines.org
+//! [`test::replay`]: crate::test::replay
+//! [`Extension`]: axum::Extension
+
+use crate::{instrument::ConnectionId, life_cycle::EventMessageFromSocketData};
+use http::{HeaderMap, Uri};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::{
+    io,
+    path::PathBuf,
+    sync::Arc,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tokio::{fs, io::AsyncWriteExt};
+
+/// Records live view sessions to files in a directory.
+///
+/// See the [module docs](self) for more details.
+#[derive(Debug, Clone)]
+pub struct Recorder {
+    dir: Arc<PathBuf>,
+}
+
+impl Recorder {
+    /// Create a new `Recorder` that writes its recordings to `dir`.
+    ///
+    /// Each connection gets its own file named `{timestamp}-{connection id}.jsonl`. The directory
+    /// is created if it doesn't already exist.
+    pub fn new<P>(dir: P) -> Self
+    where
+        P: Into<PathBuf>,
+    {
+        Self {
+            dir: Arc::new(dir.into()),
+        }
+    }
+
+    pub(crate) async fn start(
+        &self,
+        connection_id: ConnectionId,
+        uri: &Uri,
+        headers: &HeaderMap,
+    ) -> io::Result<SessionRecorder> {
+        fs::create_dir_all(&*self.dir).await?;
+
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis();
+        let path = self
+            .dir
+            .join(format!("{}-{}.jsonl", timestamp, connection_id));
+        let file = fs::File::create(&path).await?;
+
+        let mut recorder = SessionRecorder { file };
+        recorder
+            .write(&RecordedEvent::Mount {
+                uri: uri.to_string(),
+                headers: headers
+                    .iter()
+                    .map(|(name, value)| {
+                        (
+                            name.as_str().to_owned(),
+                            String::from_utf8_lossy(value.as_bytes()).into_owned(),
+                        )
+                    })
+                    .collect(),
+            })
+            .await?;
+
+        tracing::debug!(path = %path.display(), "recording session");
+
+        Ok(recorder)
+    }
+}
+
+/// The recording of a single connection.
+pub(crate) struct SessionRecorder {
+    file: fs::File,
+}
+
+impl SessionRecorder {
+    pub(crate) async fn record_message<M>(
+        &mut self,
+        msg: &M,
+        data: &EventMessageFromSocketData,
+        values: &Map<String, Value>,
+    ) -> io::Result<()>
+    where
+        M: Serialize,
+    {
+        let msg = serde_json::to_value(msg)?;
+        self.write(&RecordedEvent::Message {
+            msg,
+            data: data.clone(),
+            values: values.clone(),
+        })
+        .await
+    }
+
+    async fn write(&mut self, event: &RecordedEvent) -> io::Result<()> {
+        let mut line = serde_json::to_vec(event)?;
+        line.push(b'\n');
+        self.file.write_all(&line).await?;
+        // tokio writes files in the background so flush to make sure the line isn't lost if the
+        // connection ends
+        self.file.flush().await
+    }
+}
+
+/// A line in a recording.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum RecordedEvent {
+    Mount {
+        uri: String,
+        headers: Vec<(String, String)>,
+    },
+    Message {
+        msg: Value,
+        data: EventMessageFromSocketData,
+        #[serde(default, skip_serializing_if = "Map::is_empty")]
+        values: Map<String, Value>,
+    },
+}
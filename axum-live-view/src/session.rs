@@ -0,0 +1,473 @@
+//! Resume views after reconnecting, even on another server instance.
+//!
+//! By default a view only lives as long as its WebSocket connection. When the client reconnects
+//! the HTTP handler runs again and the view starts over from its initial value. That is fine when
+//! there is a single server, but behind a load balancer without sticky sessions a reconnect
+//! usually lands on a different instance and all state is lost.
+//!
+//! With sessions enabled, views embedded with [`EmbedLiveView::embed_resumable`] checkpoint their
+//! state to a [`SessionStore`] after each update. The initial page load assigns each view a
+//! session token, signed so clients can't forge one for another session, and the client sends it
+//! back when connecting. If the store contains a checkpoint for the session the view is rebuilt
+//! from it, instead of from the value given by the handler.
+//!
+//! Sessions are enabled by adding [`Sessions`] as an [`Extension`]:
+//!
+//! ```no_run
+//! use axum::{routing::get, Extension, Router};
+//! use axum_live_view::session::{FileStore, Sessions};
+//!
+//! // every server instance must use the same store and key
+//! let key = std::env::var("SESSION_KEY").expect("SESSION_KEY must be set");
+//! let sessions = Sessions::new(FileStore::new("/mnt/shared/sessions"), key);
+//!
+//! let app = Router::new()
+//!     .route("/", get(|| async { /* ... */ }))
+//!     .layer(Extension(sessions));
+//! # let _: Router = app;
+//! ```
+//!
+//! Note that [`LiveView::mount`] is called again when a view is resumed, and fields skipped with
+//! `#[serde(skip)]` will have their default value.
+//!
+//! [`EmbedLiveView::embed_resumable`]: crate::extract::EmbedLiveView::embed_resumable
+//! [`LiveView::mount`]: crate::LiveView::mount
+//! [`Extension`]: axum::Extension
+
+use async_trait::async_trait;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::{
+    collections::HashMap,
+    fmt::{self, Write},
+    io,
+    path::PathBuf,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant, SystemTime},
+};
+use tokio::fs;
+
+/// Alias for a type-erased error type.
+pub type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Storage for the state of resumable views.
+///
+/// State is stored as opaque bytes keyed by a [`SessionId`]. Sessions are never removed
+/// explicitly, since the client might reconnect at any time, so stores should expire sessions
+/// that haven't been saved for a while.
+///
+/// See the [module docs](self) for more details.
+#[async_trait]
+pub trait SessionStore: Send + Sync + 'static {
+    /// Load the state of a session, or `None` if the session doesn't exist or has expired.
+    async fn load(&self, id: &SessionId) -> Result<Option<Vec<u8>>, BoxError>;
+
+    /// Save the state of a session, replacing any previous state.
+    async fn save(&self, id: &SessionId, state: Vec<u8>) -> Result<(), BoxError>;
+}
+
+/// The id of a session.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SessionId(String);
+
+impl SessionId {
+    fn random() -> Self {
+        Self(hex(&rand::random::<[u8; 16]>()))
+    }
+
+    /// Parse an id from a session token, accepting only ids [`SessionId::random`] could have
+    /// created.
+    fn parse(id: &str) -> Option<Self> {
+        Self::is_valid(id).then(|| Self(id.to_owned()))
+    }
+
+    fn is_valid(id: &str) -> bool {
+        id.len() == 32 && id.bytes().all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f'))
+    }
+
+    /// Get the id as a string.
+    ///
+    /// The id only contains ASCII hex digits, so it's safe to use as a file name.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for SessionId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+/// Enables resumable views.
+///
+/// See the [module docs](self) for more details.
+#[derive(Clone)]
+pub struct Sessions {
+    store: Arc<dyn SessionStore>,
+    key: Arc<[u8]>,
+}
+
+impl Sessions {
+    /// Create a new `Sessions` that stores state in `store` and signs session tokens with `key`.
+    ///
+    /// The key should be random, kept secret, and shared by all server instances.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `key` is shorter than 32 bytes.
+    pub fn new<S, K>(store: S, key: K) -> Self
+    where
+        S: SessionStore,
+        K: AsRef<[u8]>,
+    {
+        let key = key.as_ref();
+        assert!(
+            key.len() >= MIN_KEY_LEN,
+            "session key must be at least {} bytes, got {}",
+            MIN_KEY_LEN,
+            key.len(),
+        );
+
+        Self {
+            store: Arc::new(store),
+            key: key.into(),
+        }
+    }
+
+    /// Start a new session.
+    pub(crate) fn start(&self) -> Session {
+        let id = SessionId::random();
+        let token = format!("{}.{}", id, hex(&self.sign(&id).finalize().into_bytes()));
+        Session {
+            sessions: self.clone(),
+            id,
+            token,
+            state: None,
+        }
+    }
+
+    /// Resume the session identified by `token`, loading its state from the store.
+    ///
+    /// Returns `None` if the token isn't validly signed.
+    pub(crate) async fn resume(&self, token: &str) -> Option<Session> {
+        let (id, signature) = token.split_once('.')?;
+        let id = SessionId::parse(id)?;
+        self.sign(&id).verify_slice(&unhex(signature)?).ok()?;
+
+        let state = match self.store.load(&id).await {
+            Ok(state) => state,
+            Err(err) => {
+                tracing::warn!(%err, %id, "failed to load session");
+                None
+            }
+        };
+
+        Some(Session {
+            sessions: self.clone(),
+            id,
+            token: token.to_owned(),
+            state,
+        })
+    }
+
+    fn sign(&self, id: &SessionId) -> Hmac<Sha256> {
+        let mut mac = Hmac::<Sha256>::new_from_slice(&self.key).expect("HMAC accepts any key size");
+        mac.update(id.as_str().as_bytes());
+        mac
+    }
+}
+
+impl fmt::Debug for Sessions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Sessions").finish()
+    }
+}
+
+/// The session of a single view.
+#[derive(Debug)]
+pub(crate) struct Session {
+    sessions: Sessions,
+    id: SessionId,
+    pub(crate) token: String,
+    /// The state the session was resumed with, if any.
+    pub(crate) state: Option<Vec<u8>>,
+}
+
+impl Session {
+    pub(crate) fn checkpoint<L>(
+        &self,
+        serialize: fn(&L) -> serde_json::Result<Vec<u8>>,
+    ) -> Checkpoint<L> {
+        Checkpoint {
+            sessions: self.sessions.clone(),
+            id: self.id.clone(),
+            serialize,
+        }
+    }
+}
+
+/// Saves the state of a view after each update.
+pub(crate) struct Checkpoint<L> {
+    sessions: Sessions,
+    id: SessionId,
+    pub(crate) serialize: fn(&L) -> serde_json::Result<Vec<u8>>,
+}
+
+impl<L> Checkpoint<L> {
+    pub(crate) async fn save(&self, state: Vec<u8>) {
+        if let Err(err) = self.sessions.store.save(&self.id, state).await {
+            tracing::warn!(%err, id = %self.id, "failed to save session");
+        }
+    }
+}
+
+impl<L> fmt::Debug for Checkpoint<L> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Checkpoint").field("id", &self.id).finish()
+    }
+}
+
+/// A [`SessionStore`] that keeps sessions in memory.
+///
+/// Only useful with a single server instance, where it allows views to survive the client
+/// reconnecting, or for testing.
+#[derive(Debug, Clone)]
+pub struct MemoryStore {
+    sessions: Arc<Mutex<HashMap<SessionId, (Instant, Vec<u8>)>>>,
+    ttl: Duration,
+}
+
+impl MemoryStore {
+    /// Create a new empty `MemoryStore`.
+    ///
+    /// Sessions expire after an hour by default.
+    pub fn new() -> Self {
+        Self {
+            sessions: Default::default(),
+            ttl: DEFAULT_TTL,
+        }
+    }
+
+    /// Set how long sessions are kept after they were last saved.
+    pub fn ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+}
+
+impl Default for MemoryStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl SessionStore for MemoryStore {
+    async fn load(&self, id: &SessionId) -> Result<Option<Vec<u8>>, BoxError> {
+        let sessions = self.sessions.lock().unwrap();
+        Ok(sessions
+            .get(id)
+            .filter(|(saved_at, _)| saved_at.elapsed() < self.ttl)
+            .map(|(_, state)| state.clone()))
+    }
+
+    async fn save(&self, id: &SessionId, state: Vec<u8>) -> Result<(), BoxError> {
+        let mut sessions = self.sessions.lock().unwrap();
+        sessions.retain(|_, (saved_at, _)| saved_at.elapsed() < self.ttl);
+        sessions.insert(id.clone(), (Instant::now(), state));
+        Ok(())
+    }
+}
+
+/// A [`SessionStore`] that keeps each session in a file.
+///
+/// Multiple server instances can share sessions by pointing the store at a shared directory.
+/// Expired sessions are ignored but not deleted from the directory, which can be done with a
+/// periodic job.
+#[derive(Debug, Clone)]
+pub struct FileStore {
+    dir: Arc<PathBuf>,
+    ttl: Duration,
+}
+
+impl FileStore {
+    /// Create a new `FileStore` that stores sessions in `dir`.
+    ///
+    /// The directory is created if it doesn't already exist. Sessions expire after an hour by
+    /// default.
+    pub fn new<P>(dir: P) -> Self
+    where
+        P: Into<PathBuf>,
+    {
+        Self {
+            dir: Arc::new(dir.into()),
+            ttl: DEFAULT_TTL,
+        }
+    }
+
+    /// Set how long sessions are kept after they were last saved.
+    pub fn ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    fn path(&self, id: &SessionId) -> io::Result<PathBuf> {
+        // ids are validated when parsed, but they end up in a path so check again
+        if !SessionId::is_valid(id.as_str()) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("invalid session id {:?}", id.as_str()),
+            ));
+        }
+        Ok(self.dir.join(id.as_str()))
+    }
+}
+
+#[async_trait]
+impl SessionStore for FileStore {
+    async fn load(&self, id: &SessionId) -> Result<Option<Vec<u8>>, BoxError> {
+        let path = self.path(id)?;
+
+        let modified = match fs::metadata(&path).await {
+            Ok(metadata) => metadata.modified()?,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err.into()),
+        };
+        let age = SystemTime::now()
+            .duration_since(modified)
+            .unwrap_or_default();
+        if age >= self.ttl {
+            return Ok(None);
+        }
+
+        Ok(Some(fs::read(&path).await?))
+    }
+
+    async fn save(&self, id: &SessionId, state: Vec<u8>) -> Result<(), BoxError> {
+        fs::create_dir_all(&*self.dir).await?;
+
+        // write to a temporary file first so readers never see a partially written session
+        let path = self.path(id)?;
+        let tmp = path.with_extension("tmp");
+        fs::write(&tmp, state).await?;
+        fs::rename(&tmp, &path).await?;
+
+        Ok(())
+    }
+}
+
+const DEFAULT_TTL: Duration = Duration::from_secs(60 * 60);
+
+const MIN_KEY_LEN: usize = 32;
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().fold(String::new(), |mut out, byte| {
+        let _ = write!(out, "{:02x}", byte);
+        out
+    })
+}
+
+fn unhex(s: &str) -> Option<Vec<u8>> {
+    s.as_bytes()
+        .chunks(2)
+        .map(|pair| {
+            let pair = std::str::from_utf8(pair)
+                .ok()
+                .filter(|pair| pair.len() == 2)?;
+            u8::from_str_radix(pair, 16).ok()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEY: &str = "0123456789abcdef0123456789abcdef";
+
+    #[tokio::test]
+    async fn tokens_are_signed() {
+        let sessions = Sessions::new(MemoryStore::new(), KEY);
+        let session = sessions.start();
+        sessions
+            .store
+            .save(&session.id, b"state".to_vec())
+            .await
+            .unwrap();
+
+        let resumed = sessions.resume(&session.token).await.unwrap();
+        assert_eq!(resumed.id, session.id);
+        assert_eq!(resumed.state.unwrap(), b"state");
+
+        let other_key = Sessions::new(MemoryStore::new(), KEY.to_uppercase());
+        assert!(other_key.resume(&session.token).await.is_none());
+
+        let forged = format!(
+            "{}.{}",
+            SessionId::random(),
+            session.token.split_once('.').unwrap().1
+        );
+        assert!(sessions.resume(&forged).await.is_none());
+        assert!(sessions.resume(session.id.as_str()).await.is_none());
+        assert!(sessions.resume("").await.is_none());
+    }
+
+    #[tokio::test]
+    async fn ids_are_validated() {
+        let sessions = Sessions::new(MemoryStore::new(), KEY);
+
+        // validly signed tokens for ids `SessionId::random` can't create
+        for id in [
+            "../../etc/passwd",
+            "",
+            "0123456789abcdef",
+            "0123456789ABCDEF0123456789ABCDEF",
+            "0123456789abcdef0123456789abcdef0",
+        ] {
+            let id = SessionId(id.to_owned());
+            let token = format!(
+                "{}.{}",
+                id,
+                hex(&sessions.sign(&id).finalize().into_bytes())
+            );
+            assert!(sessions.resume(&token).await.is_none(), "{}", id);
+        }
+
+        let store = FileStore::new(std::env::temp_dir());
+        let id = SessionId("../escaped".to_owned());
+        assert!(store.load(&id).await.is_err());
+        assert!(store.save(&id, b"state".to_vec()).await.is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "session key must be at least 32 bytes, got 0")]
+    fn empty_key_is_rejected() {
+        Sessions::new(MemoryStore::new(), "");
+    }
+
+    #[test]
+    #[should_panic(expected = "session key must be at least 32 bytes, got 6")]
+    fn short_key_is_rejected() {
+        Sessions::new(MemoryStore::new(), "secret");
+    }
+
+    #[tokio::test]
+    async fn file_store() {
+        let dir =
+            std::env::temp_dir().join(format!("axum-live-view-sessions-{}", SessionId::random()));
+        let store = FileStore::new(&dir);
+        let id = SessionId::random();
+
+        assert!(store.load(&id).await.unwrap().is_none());
+
+        store.save(&id, b"one".to_vec()).await.unwrap();
+        store.save(&id, b"two".to_vec()).await.unwrap();
+        assert_eq!(store.load(&id).await.unwrap().unwrap(), b"two");
+
+        let expired = store.clone().ttl(Duration::ZERO);
+        assert!(expired.load(&id).await.unwrap().is_none());
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,142 @@
+//! Gracefully shut down live views.
+//!
+//! Live views run for as long as the client is connected, which is usually much longer than
+//! regular requests. So when the server is shut down, for example during a deploy, the views
+//! have to be told to stop. Otherwise graceful shutdown would wait for them forever and they'd
+//! eventually be killed.
+//!
+//! When a [`Shutdown`] is triggered every connected view is stopped and
+//! [`LiveView::unmount`] is called. The client is told the server is restarting along with how
+//! long it should wait before reconnecting, and the WebSocket is closed with the "service
+//! restart" close code. The delay is jittered so all clients don't reconnect at the same time.
+//!
+//! Shutdown is enabled by adding a [`Shutdown`] as an [`Extension`]:
+//!
+//! ```
+//! use axum::{routing::get, Extension, Router};
+//! use axum_live_view::shutdown::Shutdown;
+//!
+//! # async {
+//! let shutdown = Shutdown::new();
+//!
+//! let app = Router::new()
+//!     .route("/", get(|| async { /* ... */ }))
+//!     .layer(Extension(shutdown.clone()));
+//!
+//! axum::Server::bind(&"0.0.0.0:3000".parse().unwrap())
+//!     .serve(app.into_make_service())
+//!     .with_graceful_shutdown(async move {
+//!         tokio::signal::ctrl_c().await.unwrap();
+//!         shutdown.shutdown().await;
+//!     })
+//!     .await
+//!     .unwrap();
+//! # };
+//! ```
+//!
+//! [`LiveView::unmount`]: crate::LiveView::unmount
+//! [`Extension`]: axum::Extension
+
+use std::{sync::Arc, time::Duration};
+use tokio::{sync::watch, time::Instant};
+
+/// Signal used to shut down live views.
+///
+/// See the [module docs](self) for more details.
+#[derive(Debug, Clone)]
+pub struct Shutdown {
+    /// The deadline for views to shut down by, once shutdown has been triggered.
+    signal: Arc<watch::Sender<Option<Instant>>>,
+    deadline: Duration,
+    reconnect_after: Duration,
+}
+
+impl Shutdown {
+    /// Create a new `Shutdown`.
+    pub fn new() -> Self {
+        let (signal, _) = watch::channel(None);
+        Self {
+            signal: Arc::new(signal),
+            deadline: Duration::from_secs(10),
+            reconnect_after: Duration::from_secs(1),
+        }
+    }
+
+    /// Set how long views have to shut down before their connections are abandoned.
+    ///
+    /// Defaults to 10 seconds.
+    pub fn deadline(mut self, deadline: Duration) -> Self {
+        self.deadline = deadline;
+        self
+    }
+
+    /// Set how long clients should wait before reconnecting.
+    ///
+    /// Each client waits for a random duration between `reconnect_after` and twice that, so
+    /// they don't all reconnect at once. Defaults to 1 second.
+    pub fn reconnect_after(mut self, reconnect_after: Duration) -> Self {
+        self.reconnect_after = reconnect_after;
+        self
+    }
+
+    /// Shut down all connected views and wait until they've stopped, or the deadline has passed.
+    ///
+    /// Views that connect after shutdown has been triggered are stopped immediately.
+    pub async fn shutdown(&self) {
+        let deadline = Instant::now() + self.deadline;
+        self.signal.send_replace(Some(deadline));
+
+        if tokio::time::timeout_at(deadline, self.signal.closed())
+            .await
+            .is_err()
+        {
+            tracing::warn!(
+                views = self.signal.receiver_count(),
+                "live views didn't shut down before the deadline"
+            );
+        }
+    }
+
+    pub(crate) fn subscribe(&self) -> ShutdownSignal {
+        ShutdownSignal {
+            rx: self.signal.subscribe(),
+            reconnect_after: self.reconnect_after,
+        }
+    }
+}
+
+impl Default for Shutdown {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The shutdown signal for a single view.
+///
+/// [`Shutdown::shutdown`] waits until all signals have been dropped.
+#[derive(Debug)]
+pub(crate) struct ShutdownSignal {
+    rx: watch::Receiver<Option<Instant>>,
+    reconnect_after: Duration,
+}
+
+impl ShutdownSignal {
+    /// Wait until shutdown is triggered and return the deadline for shutting down.
+    pub(crate) async fn triggered(&mut self) -> Instant {
+        loop {
+            if let Some(deadline) = *self.rx.borrow() {
+                return deadline;
+            }
+
+            if self.rx.changed().await.is_err() {
+                // all `Shutdown`s have been dropped so shutdown will never be triggered
+                futures_util::future::pending::<()>().await;
+            }
+        }
+    }
+
+    /// How long the client should wait before reconnecting, with jitter applied.
+    pub(crate) fn reconnect_after(&self) -> Duration {
+        self.reconnect_after + self.reconnect_after.mul_f64(rand::random::<f64>())
+    }
+}
@@ -69,9 +69,10 @@ use crate::{
     js_command::JsCommand,
     life_cycle::{UpdateResponse, ViewRequestError, ViewTaskHandle},
     live_view::ViewHandle,
+    record::RecordedEvent,
     LiveView,
 };
-use http::{HeaderMap, Uri};
+use http::{HeaderMap, HeaderName, HeaderValue, Uri};
 use serde::Serialize;
 use std::fmt;
 
@@ -186,7 +187,7 @@ where
     /// This calls [`LiveView::update`] on the view followed by [`LiveView::render`] and returns
     /// the HTML template and any [`JsCommand`]s included.
     pub async fn send(&self, msg: M, data: Option<EventData>) -> (String, Vec<JsCommand>) {
-        let js_commands = match self.handle.update(msg, data).await {
+        let js_commands = match self.handle.update(Vec::from([(msg, data)])).await {
             Ok(UpdateResponse::Diff(_) | UpdateResponse::Empty) => Vec::new(),
             Ok(UpdateResponse::JsCommands(cmds) | UpdateResponse::DiffAndJsCommands(_, cmds)) => {
                 cmds
@@ -207,11 +208,187 @@ impl<M> fmt::Debug for TestViewHandle<M> {
     }
 }
 
+/// Replay a session recorded with a [`Recorder`].
+///
+/// The view is mounted with the URI and headers from the recording after which every recorded
+/// message is sent to the view, in order. The rendered HTML is returned for each step. The first
+/// item is the HTML after mounting and the following items are the HTML after each message.
+///
+/// Like [`TestViewHandleBuilder::mount`] the [`ViewHandle`] passed to [`LiveView::mount`] isn't
+/// connected to the view and futures passed to [`Updated::spawn`] are ignored. However messages
+/// that were sent through the [`ViewHandle`] while recording are part of the recording and will
+/// be replayed.
+///
+/// # Example
+///
+/// ```
+/// use axum_live_view::test::replay;
+/// # use axum_live_view::{event_data::EventData, html, live_view::Updated, Html, LiveView};
+/// # use serde::{Deserialize, Serialize};
+///
+/// async fn replay_bug_report(path: &str) {
+///     let log = std::fs::read_to_string(path).unwrap();
+///
+///     let steps = replay(Counter::default(), &log).await.unwrap();
+///
+///     for html in steps {
+///         println!("{}", html);
+///     }
+/// }
+/// #
+/// # #[derive(Default)]
+/// # struct Counter { count: u64 }
+/// # impl LiveView for Counter {
+/// #     type Message = Msg;
+/// #     fn update(mut self, msg: Msg, _: Option<EventData>) -> Updated<Self> {
+/// #         self.count += 1;
+/// #         Updated::new(self)
+/// #     }
+/// #     fn render(&self) -> Html<Msg> {
+/// #         html! { { self.count } }
+/// #     }
+/// # }
+/// # #[derive(Serialize, Deserialize, Debug, PartialEq)]
+/// # enum Msg { Increment }
+/// ```
+///
+/// [`Recorder`]: crate::record::Recorder
+/// [`Updated::spawn`]: crate::live_view::Updated::spawn
+pub async fn replay<L>(view: L, log: &str) -> Result<Vec<String>, ReplayError>
+where
+    L: LiveView,
+{
+    let mut lines = log
+        .lines()
+        .enumerate()
+        .map(|(idx, line)| (idx + 1, line))
+        .filter(|(_, line)| !line.trim().is_empty());
+
+    let (uri, headers) = match lines.next() {
+        Some((line_number, line)) => match parse_line(line_number, line)? {
+            RecordedEvent::Mount { uri, headers } => {
+                let uri = uri.parse::<Uri>().map_err(|err| {
+                    ReplayError::new(line_number, ReplayErrorKind::InvalidUri(err))
+                })?;
+                let headers = headers
+                    .into_iter()
+                    .map(|(name, value)| {
+                        let name = name.parse::<HeaderName>().map_err(|err| {
+                            ReplayError::new(line_number, ReplayErrorKind::InvalidHeaderName(err))
+                        })?;
+                        let value = value.parse::<HeaderValue>().map_err(|err| {
+                            ReplayError::new(line_number, ReplayErrorKind::InvalidHeaderValue(err))
+                        })?;
+                        Ok((name, value))
+                    })
+                    .collect::<Result<HeaderMap, _>>()?;
+                (uri, headers)
+            }
+            RecordedEvent::Message { .. } => {
+                return Err(ReplayError::new(line_number, ReplayErrorKind::MissingMount))
+            }
+        },
+        None => return Err(ReplayError::new(0, ReplayErrorKind::MissingMount)),
+    };
+
+    let mut messages = Vec::new();
+    for (line_number, line) in lines {
+        match parse_line(line_number, line)? {
+            RecordedEvent::Message { msg, data, values } => {
+                let msg = serde_json::from_value::<L::Message>(msg)
+                    .map_err(|err| ReplayError::new(line_number, ReplayErrorKind::Json(err)))?;
+                messages.push((msg, EventData::from_socket(data, values)));
+            }
+            RecordedEvent::Mount { .. } => {
+                return Err(ReplayError::new(
+                    line_number,
+                    ReplayErrorKind::UnexpectedMount,
+                ))
+            }
+        }
+    }
+
+    let handle = run_live_view(view)
+        .mount_uri(uri)
+        .mount_headers(headers)
+        .mount()
+        .await;
+
+    let mut steps = Vec::with_capacity(messages.len() + 1);
+    steps.push(handle.render().await);
+    for (msg, data) in messages {
+        let (html, _) = handle.send(msg, data).await;
+        steps.push(html);
+    }
+
+    Ok(steps)
+}
+
+fn parse_line(line_number: usize, line: &str) -> Result<RecordedEvent, ReplayError> {
+    serde_json::from_str(line)
+        .map_err(|err| ReplayError::new(line_number, ReplayErrorKind::Json(err)))
+}
+
+/// The error returned by [`replay`] if the recording is invalid.
+#[derive(Debug)]
+pub struct ReplayError {
+    line: usize,
+    kind: ReplayErrorKind,
+}
+
+#[derive(Debug)]
+enum ReplayErrorKind {
+    Json(serde_json::Error),
+    InvalidUri(http::uri::InvalidUri),
+    InvalidHeaderName(http::header::InvalidHeaderName),
+    InvalidHeaderValue(http::header::InvalidHeaderValue),
+    MissingMount,
+    UnexpectedMount,
+}
+
+impl ReplayError {
+    fn new(line: usize, kind: ReplayErrorKind) -> Self {
+        Self { line, kind }
+    }
+
+    /// The line in the recording that caused the error.
+    pub fn line(&self) -> usize {
+        self.line
+    }
+}
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: ", self.line)?;
+        match &self.kind {
+            ReplayErrorKind::Json(inner) => inner.fmt(f),
+            ReplayErrorKind::InvalidUri(inner) => inner.fmt(f),
+            ReplayErrorKind::InvalidHeaderName(inner) => inner.fmt(f),
+            ReplayErrorKind::InvalidHeaderValue(inner) => inner.fmt(f),
+            ReplayErrorKind::MissingMount => write!(f, "recording must start with a mount"),
+            ReplayErrorKind::UnexpectedMount => write!(f, "recording contains multiple mounts"),
+        }
+    }
+}
+
+impl std::error::Error for ReplayError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match &self.kind {
+            ReplayErrorKind::Json(inner) => Some(inner),
+            ReplayErrorKind::InvalidUri(inner) => Some(inner),
+            ReplayErrorKind::InvalidHeaderName(inner) => Some(inner),
+            ReplayErrorKind::InvalidHeaderValue(inner) => Some(inner),
+            ReplayErrorKind::MissingMount | ReplayErrorKind::UnexpectedMount => None,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate as axum_live_view;
     use crate::event_data::Input;
+    use crate::life_cycle::{EventMessageFromSocketData, InputValue};
     use crate::{live_view::Updated, Html};
     use axum_live_view_macros::html;
     use serde::Deserialize;
@@ -244,6 +421,86 @@ mod tests {
         assert!(html.contains("10"));
     }
 
+    #[tokio::test]
+    async fn replay_recording() {
+        let dir = std::env::temp_dir().join(format!(
+            "axum-live-view-test-recording-{}",
+            std::process::id()
+        ));
+        let recorder = crate::record::Recorder::new(&dir);
+
+        let mut headers = HeaderMap::new();
+        headers.insert("x-foo", HeaderValue::from_static("bar"));
+        let mut session = recorder
+            .start(
+                crate::instrument::ConnectionId::next(),
+                &Uri::from_static("/counter"),
+                &headers,
+            )
+            .await
+            .unwrap();
+
+        session
+            .record_message(
+                &Msg::Incr,
+                &EventMessageFromSocketData::Click,
+                &Default::default(),
+            )
+            .await
+            .unwrap();
+        session
+            .record_message(
+                &Msg::IncrBy,
+                &EventMessageFromSocketData::Input {
+                    value: InputValue::String("10".to_owned()),
+                },
+                &Default::default(),
+            )
+            .await
+            .unwrap();
+        session
+            .record_message(
+                &Msg::Decr,
+                &EventMessageFromSocketData::None,
+                &Default::default(),
+            )
+            .await
+            .unwrap();
+        drop(session);
+
+        let mut entries = std::fs::read_dir(&dir).unwrap();
+        let path = entries.next().unwrap().unwrap().path();
+        let log = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        let steps = replay(Counter::default(), &log)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|html| {
+                html.trim_start_matches("<div id=\"live-view-container\">")
+                    .trim_end_matches("</div>")
+                    .to_owned()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(steps, ["0", "1", "11", "10"]);
+    }
+
+    #[tokio::test]
+    async fn replay_invalid_recording() {
+        let err = replay(
+            Counter::default(),
+            r#"{"message":{"msg":"Incr","data":{"t":"click"}}}"#,
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.line(), 1);
+
+        let log = "{\"mount\":{\"uri\":\"/\",\"headers\":[]}}\n\n{\"message\":{\"msg\":\"Nope\",\"data\":{\"t\":\"none\"}}}";
+        let err = replay(Counter::default(), log).await.unwrap_err();
+        assert_eq!(err.line(), 3);
+    }
+
     #[derive(Default, Clone)]
     struct Counter {
         count: u64,
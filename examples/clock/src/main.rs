@@ -1,8 +1,11 @@
-use axum::{async_trait, response::IntoResponse, routing::get, Router};
+use axum::{response::IntoResponse, routing::get, Router};
 use axum_live_view::{
-    event_data::EventData, html, live_view::Updated, Html, LiveView, LiveViewUpgrade,
+    event_data::EventData, extract::RenderBatching, html, live_view::Updated, Html, LiveView,
+    LiveViewUpgrade,
 };
-use std::{convert::Infallible, net::SocketAddr};
+use serde::{Deserialize, Serialize};
+use std::{net::SocketAddr, sync::Arc, time::Duration};
+use tokio::sync::watch;
 
 #[tokio::main]
 async fn main() {
@@ -23,30 +26,43 @@ async fn root(live: LiveViewUpgrade) -> impl IntoResponse {
     let format =
         time::format_description::parse("[hour]:[minute]:[second].[subsecond digits:6]").unwrap();
 
-    let view = Clock { format };
+    let view = Clock {
+        format,
+        hidden: Arc::new(watch::channel(false).0),
+    };
 
-    live.response(move |embed| {
-        html! {
-            <!DOCTYPE html>
-            <html>
-                <head>
-                </head>
-                <body>
-                    { embed.embed(view) }
-                    <script src="/bundle.js"></script>
-                </body>
-            </html>
-        }
-    })
+    // the clock ticks every millisecond but rendering once per frame is plenty
+    live.render_batching(RenderBatching::Frame(Duration::from_millis(16)))
+        .response(move |embed| {
+            html! {
+                <!DOCTYPE html>
+                <html>
+                    <head>
+                    </head>
+                    <body>
+                        { embed.embed(view) }
+                        <script src="/bundle.js"></script>
+                    </body>
+                </html>
+            }
+        })
 }
 
 #[derive(Clone)]
 struct Clock {
     format: Vec<time::format_description::FormatItem<'static>>,
+    // shared with the ticker so it can pause while the tab is in the background
+    hidden: Arc<watch::Sender<bool>>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+enum Msg {
+    Tick,
+    VisibilityChanged,
 }
 
 impl LiveView for Clock {
-    type Message = ();
+    type Message = Msg;
 
     fn mount(
         &mut self,
@@ -54,18 +70,33 @@ impl LiveView for Clock {
         _request_headers: &axum::http::HeaderMap,
         handle: axum_live_view::live_view::ViewHandle<Self::Message>,
     ) {
+        let mut hidden = self.hidden.subscribe();
         tokio::spawn(async move {
-            let mut interval = tokio::time::interval(std::time::Duration::from_millis(1));
+            let mut interval = tokio::time::interval(Duration::from_millis(1));
+            // don't catch up on the ticks missed while hidden
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
             loop {
+                // sleep until the tab is visible again
+                while *hidden.borrow_and_update() {
+                    if hidden.changed().await.is_err() {
+                        return;
+                    }
+                }
                 interval.tick().await;
-                if handle.send(()).await.is_err() {
+                if handle.send(Msg::Tick).await.is_err() {
                     return;
                 }
             }
         });
     }
 
-    fn update(self, _msg: Self::Message, _data: Option<EventData>) -> Updated<Self> {
+    fn update(self, msg: Self::Message, data: Option<EventData>) -> Updated<Self> {
+        if msg == Msg::VisibilityChanged {
+            if let Some(visibility) = data.as_ref().and_then(|data| data.as_visibility()) {
+                self.hidden.send_replace(visibility.hidden());
+            }
+        }
+
         Updated::new(self)
     }
 
@@ -73,7 +104,9 @@ impl LiveView for Clock {
         let now = time::OffsetDateTime::now_utc();
 
         html! {
-            "Current time:" { now.format(&self.format).unwrap() }
+            <div axm-visibility-change={ Msg::VisibilityChanged }>
+                "Current time:" { now.format(&self.format).unwrap() }
+            </div>
         }
     }
 }
@@ -1,6 +1,10 @@
 use axum::{response::IntoResponse, routing::get, Router};
 use axum_live_view::{
-    event_data::EventData, html, live_view::Updated, Html, LiveView, LiveViewUpgrade,
+    event_data::{deserialize_form, kind, EventData, FromEventData, FromEventDataError},
+    extract::FormPost,
+    html,
+    live_view::Updated,
+    Html, LiveView, LiveViewUpgrade,
 };
 use serde::{Deserialize, Serialize};
 use std::{collections::HashMap, net::SocketAddr};
@@ -10,7 +14,7 @@ async fn main() {
     tracing_subscriber::fmt::init();
 
     let app = Router::new()
-        .route("/", get(root))
+        .route("/", get(root).post(root))
         .route("/bundle.js", axum_live_view::precompiled_js());
 
     let addr = SocketAddr::from(([0, 0, 0, 0], 3000));
@@ -20,10 +24,10 @@ async fn main() {
         .unwrap();
 }
 
-async fn root(live: LiveViewUpgrade) -> impl IntoResponse {
+async fn root(live: LiveViewUpgrade, form: Option<FormPost>) -> impl IntoResponse {
     let view = FormView::default();
 
-    live.response(move |embed| {
+    live.forms(form).response(move |embed| {
         html! {
             <!DOCTYPE html>
             <html>
@@ -51,12 +55,10 @@ impl LiveView for FormView {
 
     fn update(mut self, msg: Msg, data: Option<EventData>) -> Updated<Self> {
         match msg {
-            Msg::Validate => {
-                let values: FormValues = data.unwrap().as_form().unwrap().deserialize().unwrap();
+            Msg::Validate(values) => {
                 self.perform_validations(&values);
             }
-            Msg::Submit => {
-                let values: FormValues = data.unwrap().as_form().unwrap().deserialize().unwrap();
+            Msg::Submit(values) => {
                 self.perform_validations(&values);
 
                 if self.errors.is_empty() {
@@ -66,23 +68,11 @@ impl LiveView for FormView {
                 }
                 self.values = Some(values);
             }
-            Msg::TextInputChanged => {
-                self.text_input_value = data
-                    .unwrap()
-                    .as_input()
-                    .unwrap()
-                    .as_str()
-                    .unwrap()
-                    .to_owned();
+            Msg::TextInputChanged(value) => {
+                self.text_input_value = value;
             }
-            Msg::TextAreaChanged => {
-                self.textarea_value = data
-                    .unwrap()
-                    .as_input()
-                    .unwrap()
-                    .as_str()
-                    .unwrap()
-                    .to_owned();
+            Msg::TextAreaChanged(value) => {
+                self.textarea_value = value;
             }
             Msg::Focus => {
                 tracing::info!(?data, "focus");
@@ -201,10 +191,10 @@ impl LiveView for FormView {
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
 enum Msg {
-    Validate,
-    Submit,
-    TextInputChanged,
-    TextAreaChanged,
+    Validate(FormValues),
+    Submit(FormValues),
+    TextInputChanged(String),
+    TextAreaChanged(String),
     Changed(Input),
     Focus,
     Blur,
@@ -262,21 +252,7 @@ impl FormView {
     }
 }
 
-#[derive(Debug, Deserialize)]
-#[serde(untagged)]
-enum ChangedInputValue {
-    Select(String),
-    MultiSelect(Vec<String>),
-    RadioOrCheckbox(bool),
-}
-
-#[derive(Debug, Deserialize)]
-struct ChangedInput {
-    #[allow(dead_code)]
-    input: String,
-}
-
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq, Eq)]
 struct FormValues {
     input: String,
     textarea: String,
@@ -289,4 +265,12 @@ struct FormValues {
     checkboxes: HashMap<String, bool>,
 }
 
+impl FromEventData for FormValues {
+    type Kind = kind::Form;
+
+    fn from_event_data(data: Option<&EventData>) -> Result<Self, FromEventDataError> {
+        deserialize_form(data)
+    }
+}
+
 const TEXTAREA_MAX_LEN: i32 = 10;
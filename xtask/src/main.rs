@@ -238,6 +238,11 @@ fn codegen() -> Result {
                     )*
                 };
 
+                let unmount = quote! {
+                    let Self { views: (#(#types,)*), .. } = self;
+                    #( #types.unmount(); )*
+                };
+
                 let update = {
                     let match_arms = types.iter().map(|ty| {
                         quote! {
@@ -298,6 +303,10 @@ fn codegen() -> Result {
                             #mount
                         }
 
+                        fn unmount(&mut self) {
+                            #unmount
+                        }
+
                         fn update(
                             mut self,
                             msg: Self::Message,